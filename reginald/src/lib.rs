@@ -1,9 +1,14 @@
+mod mmio;
 mod traits;
 
+pub use mmio::*;
 pub use traits::*;
 
-#[cfg(feature = "derive")]
+#[cfg(any(feature = "derive", feature = "include_map"))]
 extern crate reginald_derive;
 
 #[cfg(feature = "derive")]
-pub use reginald_derive::{FromBytes, ToBytes, TryFromBytes, WrappingFromBytes};
+pub use reginald_derive::{FromBytes, Register, ToBytes, TryFromBytes, WrappingFromBytes};
+
+#[cfg(feature = "include_map")]
+pub use reginald_derive::include_map;
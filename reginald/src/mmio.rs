@@ -0,0 +1,36 @@
+use core::cell::UnsafeCell;
+
+/// Memory-mapped register cell.
+///
+/// All accesses to the contained value are volatile. Intended to be used as a field
+/// inside of a `#[repr(C)]` register block struct which is overlayed over a peripheral's
+/// memory region.
+#[repr(transparent)]
+pub struct VolatileCell<T: Copy> {
+    value: UnsafeCell<T>,
+}
+
+impl<T: Copy> VolatileCell<T> {
+    /// Volatile read of the register value.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        // SAFETY: `self` is a valid reference, and therefor points to a properly aligned
+        // and initialized `T`.
+        unsafe { core::ptr::read_volatile(self.value.get()) }
+    }
+
+    /// Volatile write of the register value.
+    #[inline(always)]
+    pub fn write(&self, value: T) {
+        // SAFETY: `self` is a valid reference, and therefor points to a properly aligned `T`.
+        unsafe { core::ptr::write_volatile(self.value.get(), value) }
+    }
+
+    /// Read-modify-write of the register value.
+    ///
+    /// Note: This is not atomic.
+    #[inline(always)]
+    pub fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        self.write(f(self.read()));
+    }
+}
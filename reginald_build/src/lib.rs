@@ -0,0 +1,204 @@
+//! Generate rust register definitions from a reginald listing in a `build.rs` script.
+//!
+//! The listing is converted using the `rs-structs` generator, and the result is
+//! written to cargo's `OUT_DIR`, wrapped in a module named after the listing.
+//! Cargo is instructed to re-run the build script whenever the listing changes.
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! reginald_build::Builder::new("regs/chip.yaml").build();
+//! ```
+//!
+//! In the crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/chip.rs"));
+//!
+//! use chip::*;
+//! ```
+//!
+//! [`Builder::build`] aborts the build script with a readable error message if
+//! the listing cannot be read or converted. Use [`Builder::generate`] to handle
+//! errors instead.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use reginald_codegen::regmap::RegisterMap;
+
+pub use reginald_codegen::builtin::rs::structs::GeneratorOpts;
+pub use reginald_codegen::error::Error;
+
+/// Register definition generator for use in build scripts.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    listing: PathBuf,
+    out_dir: Option<PathBuf>,
+    module_name: Option<String>,
+    map_name: Option<String>,
+    opts: GeneratorOpts,
+}
+
+impl Builder {
+    /// Generate definitions for the given listing file.
+    ///
+    /// Relative paths are relative to the package root, which is the working
+    /// directory of build scripts.
+    pub fn new(listing: impl AsRef<Path>) -> Self {
+        Self {
+            listing: listing.as_ref().to_path_buf(),
+            out_dir: None,
+            module_name: None,
+            map_name: None,
+            opts: GeneratorOpts::default(),
+        }
+    }
+
+    /// Directory to write the generated file to.
+    ///
+    /// Defaults to cargo's `OUT_DIR`.
+    pub fn out_dir(mut self, out_dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(out_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Name of the generated module. The file is named `<module_name>.rs`.
+    ///
+    /// Defaults to the file name of the listing, without extension.
+    pub fn module_name(mut self, module_name: impl Into<String>) -> Self {
+        self.module_name = Some(module_name.into());
+        self
+    }
+
+    /// Overwrite the map name given in the listing.
+    pub fn map_name(mut self, map_name: impl Into<String>) -> Self {
+        self.map_name = Some(map_name.into());
+        self
+    }
+
+    /// Options passed to the `rs-structs` generator.
+    pub fn opts(mut self, opts: GeneratorOpts) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    /// Generate the definitions, returning the path of the generated file.
+    pub fn generate(&self) -> Result<PathBuf, Error> {
+        println!("cargo:rerun-if-changed={}", self.listing.display());
+
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR").map(PathBuf::from).ok_or_else(|| {
+                Error::GeneratorError(
+                    "OUT_DIR is not set. Set an output directory when not running in a build script".to_string(),
+                )
+            })?,
+        };
+
+        let module_name = match &self.module_name {
+            Some(module_name) => module_name.clone(),
+            None => self
+                .listing
+                .file_stem()
+                .map(|x| module_name_from_stem(&x.to_string_lossy()))
+                .ok_or_else(|| Error::GeneratorError(format!("Invalid listing path '{}'", self.listing.display())))?,
+        };
+
+        let mut map = RegisterMap::from_file(&self.listing)?;
+        if let Some(map_name) = &self.map_name {
+            map.name.clone_from(map_name);
+        }
+
+        let mut out = String::new();
+        out.push_str(&format!("pub mod {module_name} {{\n"));
+        reginald_codegen::builtin::rs::structs::generate(&mut out, &map, &self.opts)?;
+        out.push_str("}\n");
+
+        let path = out_dir.join(format!("{module_name}.rs"));
+        fs::write(&path, out)?;
+        Ok(path)
+    }
+
+    /// Generate the definitions, exiting the build script with an error message on failure.
+    pub fn build(&self) -> PathBuf {
+        match self.generate() {
+            Ok(path) => path,
+            Err(err) => {
+                eprintln!("error: Failed to generate register definitions from {}: {err}", self.listing.display());
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Rust module name derived from a listing file name.
+fn module_name_from_stem(stem: &str) -> String {
+    let name: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = r#"
+        name: test
+        registers:
+          CTRL: !Register
+            adr: 0x10
+            bitwidth: 8
+            layout: !Layout
+              EN:
+                bits: 0
+    "#;
+
+    #[test]
+    fn test_generate() {
+        let dir = tempfile::tempdir().unwrap();
+        let listing = dir.path().join("My-Chip.yaml");
+        fs::write(&listing, MAP).unwrap();
+
+        let path = Builder::new(&listing).out_dir(dir.path()).generate().unwrap();
+        assert_eq!(path, dir.path().join("my_chip.rs"));
+
+        let out = fs::read_to_string(path).unwrap();
+        assert!(out.starts_with("pub mod my_chip {\n"));
+        assert!(out.contains("pub struct Ctrl"));
+        assert!(out.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_generate_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let listing = dir.path().join("chip.yaml");
+        fs::write(&listing, MAP.replace("bits: 0", "bits: 8")).unwrap();
+
+        let err = Builder::new(&listing).out_dir(dir.path()).generate().unwrap_err();
+        assert!(err.to_string().contains("CTRL"));
+        assert!(!dir.path().join("chip.rs").exists());
+    }
+
+    #[test]
+    fn test_module_name_from_stem() {
+        assert_eq!(module_name_from_stem("chip"), "chip");
+        assert_eq!(module_name_from_stem("My-Chip.v2"), "my_chip_v2");
+        assert_eq!(module_name_from_stem("8051"), "_8051");
+    }
+}
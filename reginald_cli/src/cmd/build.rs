@@ -0,0 +1,299 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{CommandFactory, Parser};
+use reginald_codegen::error::Error;
+use serde::Deserialize;
+
+use super::generate::{self, Generator};
+
+#[derive(Parser, Debug)]
+#[command(about = "Generate all outputs listed in a project configuration file")]
+#[command(long_about = "Generate all outputs listed in a project configuration file
+
+Each output is described by an [[output]] table, giving the input listing,
+output path, generator and generator options. Options are named like the
+generator's command line flags, and list values are passed as repeated flags.
+Positional arguments, such as the template of the 'template' generator, are
+given by their name as well. Additional raw command line arguments can be given
+as 'args'. Relative paths, including path-valued generator options and
+arguments, are relative to the configuration file.
+
+Example:
+
+  [[output]]
+  input = \"maps/chip.yaml\"
+  output = \"include/chip_regs.h\"
+  generator = \"c-funcpack\"
+  opts = { endian = [\"little\"], add_include = [\"chip_types.h\"] }
+
+  [[output]]
+  input = \"maps/chip.yaml\"
+  output = \"src/regs.rs\"
+  generator = \"rs-structs\"")]
+pub struct Command {
+    /// Configuration file path
+    #[arg(short, default_value = "reginald.toml")]
+    pub config: PathBuf,
+
+    /// Verify that all existing output files are up-to-date
+    ///
+    /// Instead of generating the output files, verify that they exist and
+    /// match what would have been generated.
+    #[arg(long, default_value = "false", verbatim_doc_comment)]
+    pub verify: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default, rename = "output")]
+    outputs: Vec<OutputConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct OutputConfig {
+    input: PathBuf,
+    output: PathBuf,
+    generator: String,
+    overwrite_map_name: Option<String>,
+    #[serde(default)]
+    opts: toml::Table,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+/// Wrapper to parse a generator and its options from a list of arguments.
+#[derive(Parser, Debug)]
+#[command(no_binary_name = true)]
+struct GeneratorArgs {
+    #[command(subcommand)]
+    generator: Generator,
+}
+
+struct Output {
+    input: PathBuf,
+    output: PathBuf,
+    overwrite_map_name: Option<String>,
+    generator: Generator,
+}
+
+pub fn cmd(build: Command) -> Result<(), Error> {
+    let outputs = read_config(&build.config)?;
+
+    let mut failures = 0;
+    for output in &outputs {
+        let result = generate::run(
+            &output.input,
+            &output.output,
+            output.overwrite_map_name.as_deref(),
+            &output.generator,
+            build.verify,
+        );
+        match result {
+            Ok(()) if build.verify => println!("Verified {}", output.output.to_string_lossy()),
+            Ok(()) => println!("Generated {}", output.output.to_string_lossy()),
+            Err(err) => {
+                failures += 1;
+                eprintln!("{}: {err}", output.output.to_string_lossy());
+            }
+        }
+    }
+
+    match (failures, build.verify) {
+        (0, _) => Ok(()),
+        (_, true) => {
+            Err(Error::VerificationError(format!("{failures} of {} outputs are not up-to-date", outputs.len())))
+        }
+        (_, false) => Err(Error::GeneratorError(format!("Failed to generate {failures} of {} outputs", outputs.len()))),
+    }
+}
+
+fn read_config(path: &Path) -> Result<Vec<Output>, Error> {
+    let content = fs::read_to_string(path)?;
+    let config: Config = toml::from_str(&content)
+        .map_err(|err| Error::ConfigError(format!("Failed to parse {}: {err}", path.to_string_lossy())))?;
+
+    let base = path.parent().unwrap_or(Path::new(""));
+
+    let mut outputs = vec![];
+    for output in config.outputs {
+        let mut args = vec![output.generator.clone()];
+        args.extend(opts_to_args(&output.generator, &output.opts)?);
+        args.extend(output.args);
+
+        let mut generator = GeneratorArgs::try_parse_from(&args)
+            .map_err(|err| {
+                Error::ConfigError(format!(
+                    "Invalid generator or options for output {}:\n{}",
+                    output.output.to_string_lossy(),
+                    err.render().to_string().trim_end()
+                ))
+            })?
+            .generator;
+        generator.rebase_paths(base);
+
+        outputs.push(Output {
+            input: base.join(&output.input),
+            output: if output.output.to_string_lossy().trim() == "-" {
+                output.output
+            } else {
+                base.join(&output.output)
+            },
+            overwrite_map_name: output.overwrite_map_name,
+            generator,
+        });
+    }
+
+    Ok(outputs)
+}
+
+/// Convert a table of generator options to command line arguments of the given generator.
+fn opts_to_args(generator: &str, opts: &toml::Table) -> Result<Vec<String>, Error> {
+    let cmd = GeneratorArgs::command();
+    let positionals: Vec<String> = match cmd.find_subcommand(generator) {
+        Some(subcmd) => subcmd.get_positionals().map(|x| x.get_id().to_string()).collect(),
+        None => vec![],
+    };
+
+    let mut args = vec![];
+    for (key, value) in opts {
+        let positional = positionals.contains(&key.replace('-', "_"));
+        let flag = format!("--{}", key.replace('_', "-"));
+        let values = match value {
+            toml::Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(i) => i.to_string(),
+                toml::Value::Float(f) => f.to_string(),
+                toml::Value::Boolean(b) => b.to_string(),
+                _ => return Err(Error::ConfigError(format!("Unsupported value for option '{key}'"))),
+            };
+            if positional {
+                args.push(value);
+            } else {
+                args.push(format!("{flag}={value}"));
+            }
+        }
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a configuration file to a fresh directory, and return its path.
+    fn write_config(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("reginald_build_{name}_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("reginald.toml");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_opts_to_args() {
+        let opts: toml::Table = toml::from_str(
+            r#"
+            endian = ["little", "big"]
+            include_guards = false
+            max_enum_bitwidth = 8
+            add_include = "types.h"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            opts_to_args("c-funcpack", &opts).unwrap(),
+            vec![
+                "--add-include=types.h",
+                "--endian=little",
+                "--endian=big",
+                "--include-guards=false",
+                "--max-enum-bitwidth=8",
+            ]
+        );
+
+        // Positional arguments are passed without flag:
+        let opts: toml::Table = toml::from_str(r#"template = "t.j2""#).unwrap();
+        assert_eq!(opts_to_args("template", &opts).unwrap(), vec!["t.j2"]);
+
+        let opts: toml::Table = toml::from_str(r#"endian = { a = 1 }"#).unwrap();
+        assert!(opts_to_args("c-funcpack", &opts).is_err());
+    }
+
+    #[test]
+    fn test_read_config() {
+        let path = write_config(
+            "read_config",
+            r#"
+            [[output]]
+            input = "map.yaml"
+            output = "out/template.txt"
+            generator = "template"
+            opts = { template = "t.j2" }
+
+            [[output]]
+            input = "map.yaml"
+            output = "-"
+            generator = "md-regdump-decode"
+            args = ["dump.yaml"]
+
+            [[output]]
+            input = "/abs/map.yaml"
+            output = "datasheet.md"
+            generator = "md-datasheet"
+            opts = { template = "/abs/t.j2" }
+            "#,
+        );
+        let base = path.parent().unwrap();
+        let outputs = read_config(&path).unwrap();
+        assert_eq!(outputs.len(), 3);
+
+        assert_eq!(outputs[0].input, base.join("map.yaml"));
+        assert_eq!(outputs[0].output, base.join("out/template.txt"));
+        let Generator::Template(opts) = &outputs[0].generator else {
+            panic!("Unexpected generator {:?}", outputs[0].generator);
+        };
+        assert_eq!(opts.template, base.join("t.j2"));
+
+        assert_eq!(outputs[1].output, PathBuf::from("-"));
+        let Generator::MdRegdumpDecode(opts) = &outputs[1].generator else {
+            panic!("Unexpected generator {:?}", outputs[1].generator);
+        };
+        assert_eq!(opts.map, base.join("dump.yaml"));
+
+        assert_eq!(outputs[2].input, PathBuf::from("/abs/map.yaml"));
+        let Generator::MdDatasheet(opts) = &outputs[2].generator else {
+            panic!("Unexpected generator {:?}", outputs[2].generator);
+        };
+        assert_eq!(opts.template, Some(PathBuf::from("/abs/t.j2")));
+    }
+
+    #[test]
+    fn test_read_config_invalid() {
+        let path = write_config(
+            "read_config_invalid",
+            r#"
+            [[output]]
+            input = "map.yaml"
+            output = "out.h"
+            generator = "c-funcpack"
+            opts = { no_such_option = true }
+            "#,
+        );
+        let Err(Error::ConfigError(msg)) = read_config(&path) else {
+            panic!("Expected configuration error");
+        };
+        assert!(msg.contains("out.h"));
+
+        let path = write_config("read_config_unknown_field", "[[output]]\ninput = \"map.yaml\"\n");
+        assert!(matches!(read_config(&path), Err(Error::ConfigError(_))));
+    }
+}
@@ -85,6 +85,16 @@ pub struct Cli {
     #[arg(verbatim_doc_comment)]
     pub include_guards: bool,
 
+    /// Generate memory-mapped register access.
+    ///
+    /// Generates `volatile` struct overlays for register blocks, and read/write
+    /// functions for each register that access it relative to a base pointer.
+    #[arg(long)]
+    #[arg(action = clap::ArgAction::Set)]
+    #[arg(default_value_t = Self::default().mmio_access)]
+    #[arg(verbatim_doc_comment)]
+    pub mmio_access: bool,
+
     /// Only generate a subset of the elements/sections usually included in
     /// a complete output file.
     ///
@@ -126,6 +136,7 @@ impl Default for Cli {
             funcs_as_prototypes: false,
             clang_format_guard: true,
             include_guards: true,
+            mmio_access: false,
             only_generate: vec![],
             dont_generate: vec![],
         }
@@ -156,6 +167,7 @@ impl From<&Cli> for c::funcpack::GeneratorOpts {
             funcs_as_prototypes: cli.funcs_as_prototypes,
             clang_format_guard: cli.clang_format_guard,
             include_guards: cli.include_guards,
+            mmio_access: cli.mmio_access,
             to_generate,
         }
     }
@@ -1,12 +1,19 @@
 mod c;
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use reginald_codegen::builtin::c as codegen_c;
+use reginald_codegen::builtin::cpp;
+use reginald_codegen::builtin::csv;
+use reginald_codegen::builtin::diagram;
+use reginald_codegen::builtin::html;
 use reginald_codegen::builtin::md;
+use reginald_codegen::builtin::py;
 use reginald_codegen::builtin::rs;
+use reginald_codegen::builtin::sv;
+use reginald_codegen::builtin::template;
 use reginald_codegen::error::Error;
 use reginald_codegen::regmap::RegisterMap;
 
@@ -44,52 +51,115 @@ pub struct Command {
 
 #[derive(Parser, Debug)]
 pub enum Generator {
+    /// C header with register read/write/modify functions using user-provided transport callbacks
+    CDriver(codegen_c::driver::GeneratorOpts),
     /// C header with register structs, and packing/unpacking functions
     CFuncpack(c::funcpack::Cli),
     /// C header with field mask/shift macros
     CMacromap(codegen_c::macromap::GeneratorOpts),
+    /// C++ header with register classes, constexpr packing/unpacking, and register property templates
+    Cpp(cpp::GeneratorOpts),
+    /// CSV table with one row per register, field and enum entry
+    Csv(csv::GeneratorOpts),
+    /// Register bitfield diagram as WaveDrom JSON or SVG
+    Diagram(diagram::GeneratorOpts),
+    /// Self-contained HTML datasheet with register index and bit diagrams
+    HtmlDatasheet,
     /// Markdown datasheet
-    MdDatasheet,
+    MdDatasheet(md::datasheet::GeneratorOpts),
     /// Markdown decode report of register dump
     MdRegdumpDecode(md::datasheet::regdump::GeneratorOpts),
+    /// Python module with register dataclasses and enums
+    Py(py::GeneratorOpts),
     /// Rust module with register structs and no dependencies
     RsStructs(rs::structs::GeneratorOpts),
+    /// SystemVerilog register file module with bus interface and hardware ports
+    SvRegfile(sv::regfile::GeneratorOpts),
+    /// SystemVerilog package with a UVM register model
+    SvUvmRal(sv::uvm_ral::GeneratorOpts),
+    /// Output of a user-provided minijinja template rendered with the register map
+    Template(template::GeneratorOpts),
+}
+
+impl Generator {
+    /// Resolve relative paths in the generator options against the given directory.
+    pub fn rebase_paths(&mut self, base: &Path) {
+        match self {
+            Generator::MdDatasheet(opts) => {
+                if let Some(template) = &mut opts.template {
+                    *template = base.join(&template);
+                }
+            }
+            Generator::MdRegdumpDecode(opts) => opts.map = base.join(&opts.map),
+            Generator::Template(opts) => opts.template = base.join(&opts.template),
+            Generator::CDriver(_)
+            | Generator::CFuncpack(_)
+            | Generator::CMacromap(_)
+            | Generator::Cpp(_)
+            | Generator::Csv(_)
+            | Generator::Diagram(_)
+            | Generator::HtmlDatasheet
+            | Generator::Py(_)
+            | Generator::RsStructs(_)
+            | Generator::SvRegfile(_)
+            | Generator::SvUvmRal(_) => (),
+        }
+    }
 }
 
 pub fn cmd(generate: Command) -> Result<(), Error> {
+    run(&generate.input, &generate.output, generate.overwrite_map_name.as_deref(), &generate.generator, generate.verify)
+}
+
+/// Generate a single output, or verify that it is up-to-date.
+pub fn run(
+    input: &PathBuf,
+    output: &PathBuf,
+    overwrite_map_name: Option<&str>,
+    generator: &Generator,
+    verify: bool,
+) -> Result<(), Error> {
     // Read input map:
-    let mut map = RegisterMap::from_file(&generate.input)?;
+    let mut map = RegisterMap::from_file(input)?;
 
-    if let Some(name) = &generate.overwrite_map_name {
+    if let Some(name) = overwrite_map_name {
         map.name = name.to_string();
     }
 
     // Generate output:
     let mut out = String::new();
-    match &generate.generator {
-        Generator::CFuncpack(opts) => codegen_c::funcpack::generate(&mut out, &map, &generate.output, opts.into())?,
-        Generator::CMacromap(opts) => codegen_c::macromap::generate(&mut out, &map, &generate.output, opts)?,
-        Generator::MdDatasheet => md::datasheet::generate(&mut out, &map)?,
+    match generator {
+        Generator::CDriver(opts) => codegen_c::driver::generate(&mut out, &map, output, opts)?,
+        Generator::CFuncpack(opts) => codegen_c::funcpack::generate(&mut out, &map, output, opts.into())?,
+        Generator::CMacromap(opts) => codegen_c::macromap::generate(&mut out, &map, output, opts)?,
+        Generator::Cpp(opts) => cpp::generate(&mut out, &map, output, opts)?,
+        Generator::Csv(opts) => csv::generate(&mut out, &map, opts)?,
+        Generator::Diagram(opts) => diagram::generate(&mut out, &map, opts)?,
+        Generator::HtmlDatasheet => html::datasheet::generate(&mut out, &map)?,
+        Generator::MdDatasheet(opts) => md::datasheet::generate(&mut out, &map, opts)?,
         Generator::MdRegdumpDecode(opts) => md::datasheet::regdump::generate(&mut out, &map, opts)?,
+        Generator::Py(opts) => py::generate(&mut out, &map, opts)?,
         Generator::RsStructs(opts) => rs::structs::generate(&mut out, &map, opts)?,
+        Generator::SvRegfile(opts) => sv::regfile::generate(&mut out, &map, opts)?,
+        Generator::SvUvmRal(opts) => sv::uvm_ral::generate(&mut out, &map, opts)?,
+        Generator::Template(opts) => template::generate(&mut out, &map, opts)?,
     };
 
     // Verify or write ouput:
-    if generate.verify {
-        let output_content = fs::read_to_string(&generate.output)?;
+    if verify {
+        let output_content = fs::read_to_string(output)?;
         if output_content != out {
             let diff_msg = diff::diff_report(&output_content, &out);
-            let msg =
-                format!("File {} differs from generator output!\n{}", generate.output.to_string_lossy(), diff_msg);
+            let msg = format!("File {} differs from generator output!\n{}", output.to_string_lossy(), diff_msg);
             Err(Error::VerificationError(msg))?;
         }
         return Ok(());
     }
 
-    if generate.output.to_string_lossy().trim() == "-" {
+    if output.to_string_lossy().trim() == "-" {
         println!("{}", out);
     } else {
-        fs::write(generate.output, out)?;
+        fs::write(output, out)?;
     }
 
     Ok(())
@@ -0,0 +1,63 @@
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use reginald_codegen::{error::Error, import};
+
+#[derive(Parser, Debug)]
+#[command(about = "Reconstruct a register listing from existing register descriptions")]
+#[command(subcommand_value_name = "FORMAT")]
+#[command(subcommand_help_heading = "Formats")]
+pub struct Command {
+    /// Input file path
+    #[arg(short)]
+    pub input: PathBuf,
+
+    /// Output yaml or json listing file path or '-' for stdout.
+    #[arg(short)]
+    pub output: PathBuf,
+
+    /// Input format
+    #[command(subcommand)]
+    pub format: Format,
+}
+
+#[derive(Parser, Debug)]
+pub enum Format {
+    /// C header with register address and field mask/shift macros
+    CHeader(import::c_header::ImportOpts),
+    /// CSV table, for example exported from a spreadsheet, with configurable column mapping
+    Csv(import::csv::ImportOpts),
+}
+
+pub fn cmd(cmd: Command) -> Result<(), Error> {
+    let input = fs::read_to_string(&cmd.input)?;
+
+    let imported = match &cmd.format {
+        Format::CHeader(opts) => import::c_header::import(&input, opts)?,
+        Format::Csv(opts) => {
+            let mut opts = opts.clone();
+            if opts.name.is_none() {
+                opts.name = cmd.input.file_stem().map(|x| x.to_string_lossy().to_string());
+            }
+            import::csv::import(&input, &opts)?
+        }
+    };
+
+    for warning in &imported.warnings {
+        eprintln!("Warning: {warning}.");
+    }
+
+    let ext = cmd.output.extension().and_then(|x| x.to_str()).map(str::to_lowercase);
+    let out = match ext {
+        Some(ext) if ext == "json" || ext == "hjson" => imported.map.to_json()?,
+        _ => imported.map.to_yaml()?,
+    };
+
+    if cmd.output.to_string_lossy().trim() == "-" {
+        println!("{out}");
+    } else {
+        fs::write(cmd.output, out)?;
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod build;
 pub mod completion;
 pub mod generate;
+pub mod import;
 pub mod tool;
@@ -1,7 +1,10 @@
 use std::{fs, path::PathBuf};
 
 use clap::Parser;
-use reginald_codegen::{builtin::rs::CONVERSION_TRAITS, error::Error};
+use reginald_codegen::{
+    builtin::rs::{CONVERSION_TRAITS, MMIO_TYPES},
+    error::Error,
+};
 
 #[derive(Parser, Debug)]
 #[command(about = "Built-in tools and utilities")]
@@ -15,7 +18,7 @@ pub struct Command {
 
 #[derive(Parser, Debug)]
 pub enum Tool {
-    /// Emit rust reginald trait definitions
+    /// Emit rust reginald trait and memory-mapped register type definitions
     ///
     /// If a project requries multiple rs-structs register maps, it is
     /// desireable to have them share the same traits instead of having
@@ -35,7 +38,7 @@ pub struct RsReginaldTraits {
 pub fn cmd(tool: Command) -> Result<(), Error> {
     match tool.tool {
         Tool::RsReginaldTraits(opts) => {
-            let traits = CONVERSION_TRAITS;
+            let traits = format!("{CONVERSION_TRAITS}\n{MMIO_TYPES}");
             if opts.output.to_string_lossy().trim() == "-" {
                 println!("{traits}");
             } else {
@@ -10,6 +10,8 @@ use std::process::ExitCode;
 #[allow(clippy::large_enum_variant)]
 enum Cli {
     Gen(cmd::generate::Command),
+    Build(cmd::build::Command),
+    Import(cmd::import::Command),
     Completion(cmd::completion::Command),
     Tool(cmd::tool::Command),
 }
@@ -19,6 +21,8 @@ fn main() -> ExitCode {
 
     let err = match cli {
         Cli::Gen(generate) => cmd::generate::cmd(generate),
+        Cli::Build(build) => cmd::build::cmd(build),
+        Cli::Import(import) => cmd::import::cmd(import),
         Cli::Completion(c) => cmd::completion::cmd(c),
         Cli::Tool(tool) => cmd::tool::cmd(tool),
     };
@@ -212,7 +212,27 @@ pub fn mask_to_bit_ranges_str(mask: TypeValue) -> String {
 /// assert_eq!(bitwidth_to_width_bytes(9), 2);
 /// ```
 pub fn bitwidth_to_width_bytes(bitwidth: TypeBitwidth) -> TypeBitwidth {
-    (bitwidth + 7) / 8
+    bitwidth.div_ceil(8)
+}
+
+/// Interpret the lowest N bits of a value as a two's complement number.
+///
+/// Example:
+/// ```rust
+/// # use reginald_codegen::bits::sign_extend;
+/// assert_eq!(sign_extend(0b0111, 4), 7);
+/// assert_eq!(sign_extend(0b1000, 4), -8);
+/// assert_eq!(sign_extend(0b1111, 4), -1);
+/// ```
+pub fn sign_extend(val: TypeValue, bitwidth: TypeBitwidth) -> i128 {
+    if bitwidth == 0 {
+        0
+    } else if bitwidth >= MAX_BITWIDTH {
+        val as i128
+    } else {
+        let shift = MAX_BITWIDTH - bitwidth;
+        ((val << shift) as i128) >> shift
+    }
 }
 
 #[cfg(test)]
@@ -320,4 +340,17 @@ mod tests {
         assert_eq!(mask_width(0b1100), 2);
         assert_eq!(mask_width(0b1101), 4);
     }
+
+    #[test]
+    fn test_sign_extend() {
+        assert_eq!(sign_extend(0b0, 0), 0);
+        assert_eq!(sign_extend(0b1, 1), -1);
+        assert_eq!(sign_extend(0b011, 3), 3);
+        assert_eq!(sign_extend(0b100, 3), -4);
+        assert_eq!(sign_extend(0xFF, 8), -1);
+        assert_eq!(sign_extend(0x1FF, 8), -1);
+        assert_eq!(sign_extend(u64::MAX.into(), 64), -1);
+        assert_eq!(sign_extend(u64::MAX.into(), 65), i128::from(u64::MAX));
+        assert_eq!(sign_extend(TypeValue::MAX, 128), -1);
+    }
 }
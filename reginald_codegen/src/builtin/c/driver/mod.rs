@@ -0,0 +1,418 @@
+use std::{fmt::Write, path::Path};
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+
+use crate::{
+    bits::msb_pos,
+    error::Error,
+    regmap::{Docs, Register, RegisterMap},
+    utils::Endianess,
+};
+
+use super::{c_code, c_fitting_unsigned_type, c_generate_doxy_comment, c_generate_section_header_comment, c_macro};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Endianess of the values transfered by the transport callbacks.
+    ///
+    /// The included funcpack header must provide the conversion functions
+    /// of this endianess.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(value_enum, default_value_t = Self::default().endian))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub endian: Endianess,
+
+    /// C type to use for register addresses in the transport callbacks.
+    ///
+    /// If none is specified, the smallest unsigned type capable of storing
+    /// the largest address will be used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub address_type: Option<String>,
+
+    /// c-funcpack header that provides the register structs and conversion functions.
+    ///
+    /// If not given, the funcpack header must be included through `--add-include`.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub funcpack_header: Option<String>,
+
+    /// Header file that should be included at the top of the generated header
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub add_include: Vec<String>,
+
+    /// Make all functions static inline.
+    ///
+    /// May be disabled if splitting code into header and source.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().funcs_static_inline))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub funcs_static_inline: bool,
+
+    /// Generate function prototypes instead of full implementations.
+    ///
+    /// May be enabled if splitting code into header and source.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().funcs_as_prototypes))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub funcs_as_prototypes: bool,
+
+    /// Generate the driver context struct and error code.
+    ///
+    /// May be disabled if splitting code into header and source, with the
+    /// source including a header that provides them.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().generate_context))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub generate_context: bool,
+
+    /// Surround header with a clang-format off guard
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().clang_format_guard))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub clang_format_guard: bool,
+
+    /// Generate include guard
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().include_guards))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub include_guards: bool,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            endian: Endianess::Little,
+            address_type: None,
+            funcpack_header: None,
+            add_include: vec![],
+            funcs_static_inline: true,
+            funcs_as_prototypes: false,
+            generate_context: true,
+            clang_format_guard: true,
+            include_guards: true,
+        }
+    }
+}
+
+// ====== Generator ============================================================
+
+struct Input<'a> {
+    opts: &'a GeneratorOpts,
+    map: &'a RegisterMap,
+    output_file: &'a Path,
+    address_type: String,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, output_file: &Path, opts: &GeneratorOpts) -> Result<(), Error> {
+    // Determine address type: Use option override, or smallest
+    // unsigned type that fits the largest address in the map.
+    let address_type = if let Some(address_type) = &opts.address_type {
+        address_type.clone()
+    } else {
+        let max_addr = map.registers.values().map(|x| x.adr).max().unwrap_or(0);
+        c_fitting_unsigned_type(msb_pos(max_addr.into()) + 1)?
+    };
+
+    let inp = Input {
+        opts,
+        map,
+        output_file,
+        address_type,
+    };
+
+    generate_header(out, &inp)?;
+    if opts.generate_context {
+        generate_context(out, &inp)?;
+    }
+
+    let mut regs: Vec<_> = map.registers.values().collect();
+    regs.sort_by_key(|x| x.adr);
+    for register in regs {
+        writeln!(out)?;
+        c_generate_section_header_comment(out, &format!("{} Register", register.name))?;
+        generate_read_func(out, &inp, register)?;
+        generate_write_func(out, &inp, register)?;
+        generate_modify_func(out, &inp, register)?;
+    }
+
+    generate_footer(out, &inp)?;
+    Ok(())
+}
+
+/// Generate header comment, include guard, includes.
+fn generate_header(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    if inp.opts.clang_format_guard {
+        writeln!(out, "// clang-format off")?;
+    }
+
+    let output_file_name = inp
+        .output_file
+        .file_name()
+        .ok_or(Error::GeneratorError("Failed to extract file name from output path!".to_string()))?
+        .to_string_lossy();
+
+    writeln!(out, "/**")?;
+    writeln!(out, " * @file {output_file_name}")?;
+    writeln!(out, " * @brief {} driver", inp.map.name)?;
+    if let Some(input_file) = &inp.map.from_file {
+        writeln!(
+            out,
+            " * @note do not edit directly: generated using reginald from {}.",
+            input_file.to_string_lossy()
+        )?;
+    } else {
+        writeln!(out, " * @note do not edit directly: generated using reginald.",)?;
+    }
+    writeln!(out, " *")?;
+    writeln!(out, " * Generator: c.driver")?;
+    if let Some(author) = &inp.map.author {
+        writeln!(out, " *")?;
+        writeln!(out, " * Listing file author: {author}")?;
+    }
+    if let Some(note) = &inp.map.notice {
+        writeln!(out, " *")?;
+        writeln!(out, " * Listing file notice:")?;
+        for line in note.lines() {
+            writeln!(out, " *   {line}")?;
+        }
+    }
+    writeln!(out, " */")?;
+
+    if inp.opts.include_guards {
+        writeln!(out, "#ifndef REGINALD_{}", c_macro(&output_file_name))?;
+        writeln!(out, "#define REGINALD_{}", c_macro(&output_file_name))?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "#include <stdint.h>")?;
+    writeln!(out, "#include <stddef.h>")?;
+    if let Some(funcpack_header) = &inp.opts.funcpack_header {
+        writeln!(out, "#include \"{funcpack_header}\"")?;
+    }
+    for include in &inp.opts.add_include {
+        writeln!(out, "#include \"{include}\"")?;
+    }
+
+    Ok(())
+}
+
+/// Generate file footer
+fn generate_footer(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    writeln!(out)?;
+
+    let output_file_name = inp
+        .output_file
+        .file_name()
+        .ok_or(Error::GeneratorError("Failed to extract file name from output path!".to_string()))?
+        .to_string_lossy();
+
+    if inp.opts.include_guards {
+        writeln!(out, "#endif /* REGINALD_{} */", c_macro(&output_file_name))?;
+    }
+
+    if inp.opts.clang_format_guard {
+        writeln!(out, "// clang-format on")?;
+    }
+
+    Ok(())
+}
+
+/// Generate error code and driver context struct.
+fn generate_context(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    let macro_prefix = c_macro(&inp.map.name);
+    let ctx_name = ctx_struct_name(inp);
+    let address_type = &inp.address_type;
+
+    writeln!(out)?;
+    c_generate_section_header_comment(out, "Driver Context")?;
+
+    writeln!(out)?;
+    writeln!(out, "#ifndef {macro_prefix}_ERR_UNPACK")?;
+    writeln!(out, "#define {macro_prefix}_ERR_UNPACK (-1) //!< Returned if a read value could not be unpacked.")?;
+    writeln!(out, "#endif")?;
+
+    writeln!(out)?;
+    let docs = Docs {
+        brief: Some(format!("{} driver context.", inp.map.name)),
+        doc: Some(String::from(
+            "Provides the transport callbacks used by all register access functions.\n\
+             Callbacks return 0 on success, and any other value to indicate an error,\n\
+             which is propagated to the caller.",
+        )),
+    };
+    c_generate_doxy_comment(out, &docs, "", vec![])?;
+    writeln!(out, "struct {ctx_name} {{")?;
+    writeln!(out, "  /** @brief Read `len` bytes from register at address `adr` into `buf`. */")?;
+    writeln!(out, "  int (*read)(void *user, {address_type} adr, uint8_t *buf, size_t len);")?;
+    writeln!(out, "  /** @brief Write `len` bytes from `buf` to register at address `adr`. */")?;
+    writeln!(out, "  int (*write)(void *user, {address_type} adr, const uint8_t *buf, size_t len);")?;
+    writeln!(out, "  /** @brief User data passed to each callback. */")?;
+    writeln!(out, "  void *user;")?;
+    writeln!(out, "}};")?;
+
+    Ok(())
+}
+
+fn generate_read_func(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
+    let (code_prefix, macro_prefix, ctx_name, struct_name, reg_name) = names(inp, register);
+    let func_prefix = func_prefix(inp);
+    let width_bytes = register.layout.width_bytes();
+    let endian = inp.opts.endian.short();
+
+    let docs = Docs {
+        brief: Some(format!("Read and unpack {} register.", register.name)),
+        doc: None,
+    };
+    writeln!(out)?;
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![
+            (String::from("returns"), String::from("0 if successful.")),
+            (String::from("returns"), format!("{macro_prefix}_ERR_UNPACK if the read value is invalid.")),
+            (String::from("returns"), String::from("the read callback error if the transfer failed.")),
+        ],
+    )?;
+    let func_sig = format!(
+        "{func_prefix}int {code_prefix}_read_{reg_name}(const struct {ctx_name} *ctx, struct {struct_name} *out)"
+    );
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+        return Ok(());
+    }
+    writeln!(out, "{func_sig} {{")?;
+    writeln!(out, "  uint8_t val[{width_bytes}] = {{0}};")?;
+    writeln!(out, "  int err = ctx->read(ctx->user, 0x{:X}U, val, {width_bytes});", register.adr)?;
+    writeln!(out, "  if (err != 0) {{ return err; }}")?;
+    writeln!(out, "  if ({struct_name}_try_unpack_{endian}(val, out) != 0) {{ return {macro_prefix}_ERR_UNPACK; }}")?;
+    writeln!(out, "  return 0;")?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+fn generate_write_func(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
+    let (code_prefix, _, ctx_name, struct_name, reg_name) = names(inp, register);
+    let func_prefix = func_prefix(inp);
+    let width_bytes = register.layout.width_bytes();
+    let endian = inp.opts.endian.short();
+
+    let docs = Docs {
+        brief: Some(format!("Pack and write {} register.", register.name)),
+        doc: None,
+    };
+    writeln!(out)?;
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![
+            (String::from("returns"), String::from("0 if successful.")),
+            (String::from("returns"), String::from("the write callback error if the transfer failed.")),
+        ],
+    )?;
+    let func_sig = format!(
+        "{func_prefix}int {code_prefix}_write_{reg_name}(const struct {ctx_name} *ctx, const struct {struct_name} *in)"
+    );
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+        return Ok(());
+    }
+    writeln!(out, "{func_sig} {{")?;
+    writeln!(out, "  uint8_t val[{width_bytes}] = {{0}};")?;
+    writeln!(out, "  {struct_name}_pack_{endian}(in, val);")?;
+    writeln!(out, "  return ctx->write(ctx->user, 0x{:X}U, val, {width_bytes});", register.adr)?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+fn generate_modify_func(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
+    let (code_prefix, macro_prefix, ctx_name, struct_name, reg_name) = names(inp, register);
+    let func_prefix = func_prefix(inp);
+    let width_bytes = register.layout.width_bytes();
+    let endian = inp.opts.endian.short();
+
+    let docs = Docs {
+        brief: Some(format!("Read-modify-write {} register.", register.name)),
+        doc: Some(String::from(
+            "Reads and unpacks the register, passes it to `modify`, and packs and writes\n\
+             the result. Bits not covered by any field retain their read value.",
+        )),
+    };
+    writeln!(out)?;
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![
+            (String::from("returns"), String::from("0 if successful.")),
+            (String::from("returns"), format!("{macro_prefix}_ERR_UNPACK if the read value is invalid.")),
+            (String::from("returns"), String::from("the callback error if a transfer failed.")),
+        ],
+    )?;
+    let func_sig = format!(
+        "{func_prefix}int {code_prefix}_modify_{reg_name}(const struct {ctx_name} *ctx, void (*modify)(struct {struct_name} *r, void *arg), void *arg)"
+    );
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+        return Ok(());
+    }
+    writeln!(out, "{func_sig} {{")?;
+    writeln!(out, "  uint8_t val[{width_bytes}] = {{0}};")?;
+    writeln!(out, "  struct {struct_name} r;")?;
+    writeln!(out, "  int err = ctx->read(ctx->user, 0x{:X}U, val, {width_bytes});", register.adr)?;
+    writeln!(out, "  if (err != 0) {{ return err; }}")?;
+    writeln!(out, "  if ({struct_name}_try_unpack_{endian}(val, &r) != 0) {{ return {macro_prefix}_ERR_UNPACK; }}")?;
+    writeln!(out, "  modify(&r, arg);")?;
+    writeln!(out, "  {struct_name}_pack_{endian}(&r, val);")?;
+    writeln!(out, "  return ctx->write(ctx->user, 0x{:X}U, val, {width_bytes});", register.adr)?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+// ====== Generator Utils ======================================================
+
+fn ctx_struct_name(inp: &Input) -> String {
+    format!("{}_ctx", c_code(&inp.map.name))
+}
+
+/// Code prefix, macro prefix, context struct name, layout struct name and register name
+/// used by a register's access functions.
+fn names(inp: &Input, register: &Register) -> (String, String, String, String, String) {
+    let code_prefix = c_code(&inp.map.name);
+    let macro_prefix = c_macro(&inp.map.name);
+    let ctx_name = ctx_struct_name(inp);
+    let struct_name = format!("{code_prefix}_{}", c_code(&register.layout.name));
+    let reg_name = c_code(&register.name);
+    (code_prefix, macro_prefix, ctx_name, struct_name, reg_name)
+}
+
+/// Decide what each function should be prefixed with, depending on
+/// given opts.
+fn func_prefix(inp: &Input) -> &'static str {
+    if inp.opts.funcs_static_inline {
+        "static inline "
+    } else {
+        ""
+    }
+}
@@ -4,7 +4,7 @@ use reginald_utils::RangeStyle;
 
 use crate::{
     error::Error,
-    regmap::{Docs, FieldType, Layout, LayoutField},
+    regmap::{Docs, FieldConstraints, FieldType, Layout, LayoutField, Scaling, ScalingConversion, scaling_str},
     utils::{
         Endianess, ShiftDirection, field_byte_to_packed_byte_transform, field_to_packed_byte_transform, grab_byte,
         packed_byte_to_field_byte_transform,
@@ -13,8 +13,8 @@ use crate::{
 };
 
 use super::{
-    Element, Input, assemble_numeric_field, c_code, c_fitting_unsigned_type, c_generate_doxy_comment, c_macro, enums,
-    func_prefix, swap_loop,
+    Element, Input, assemble_numeric_field, c_code, c_fitting_signed_type, c_fitting_unsigned_type,
+    c_generate_doxy_comment, c_int64, c_macro, enums, func_prefix, swap_loop,
 };
 
 pub fn generate_layout(out: &mut dyn Write, inp: &Input, layout: &Layout) -> Result<(), Error> {
@@ -89,7 +89,7 @@ fn generate_layout_struct(out: &mut dyn Write, inp: &Input, layout: &Layout) ->
         // Members are bitifields, if configured:
         let bitfield_str = if inp.opts.registers_as_bitfields {
             match &field.accepts {
-                FieldType::Enum(_) | FieldType::Bool | FieldType::UInt => {
+                FieldType::Enum(_) | FieldType::Bool | FieldType::UInt | FieldType::SInt => {
                     format!(": {}", field.bits.width())
                 }
                 FieldType::Layout(_) => String::new(),
@@ -124,6 +124,11 @@ fn generate_layout_funcs(out: &mut dyn Write, inp: &Input, layout: &Layout) -> R
     for endian in &inp.opts.endian {
         generate_layout_try_unpack_func(out, inp, layout, *endian)?;
     }
+    for field in layout.fields_with_content() {
+        if let Some(scaling) = &field.scaling {
+            generate_field_scaling_funcs(out, inp, layout, field, scaling)?;
+        }
+    }
 
     Ok(())
 }
@@ -191,31 +196,51 @@ fn generate_layout_pack_func(
     for field in layout.fields.values() {
         let field_name = c_code(&field.name);
 
-        let bit_str = field.bits.to_string(RangeStyle::Verilog);
+        let bit_str = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
         writeln!(out, "  // {} @ {code_name}[{bit_str}]:", field.name)?;
 
         match &field.accepts {
-            FieldType::UInt | FieldType::Bool | FieldType::Enum(_) => {
-                // Numeric field that can be directly converted:
-                for byte in 0..width_bytes {
-                    let Some(transform) = field_to_packed_byte_transform(
-                        endian,
-                        field.bits.unpositioned_mask(),
-                        field.bits.lsb_pos(),
-                        byte,
-                        width_bytes,
-                    ) else {
-                        continue;
+            FieldType::UInt | FieldType::SInt | FieldType::Bool | FieldType::Enum(_) => {
+                // Numeric field that can be directly converted. Signed fields are
+                // converted to unsigned before shifting:
+                let field_val = if matches!(field.accepts, FieldType::SInt) {
+                    format!("(({})r->{field_name})", c_fitting_unsigned_type(field.bits.width())?)
+                } else {
+                    format!("r->{field_name}")
+                };
+
+                // Each range of a split field holds the bits of the value starting at 'value_pos':
+                for (range, value_pos) in field.bits.pieces() {
+                    let piece_val = if value_pos == 0 {
+                        field_val.clone()
+                    } else {
+                        format!("({field_val} >> {value_pos})")
                     };
 
-                    let field_byte = match &transform.shift {
-                        Some((ShiftDirection::Left, amnt)) => format!("(r->{field_name} << {amnt})"),
-                        Some((ShiftDirection::Right, amnt)) => format!("(r->{field_name} >> {amnt})"),
-                        None => format!("r->{field_name}"),
-                    };
+                    for byte in 0..width_bytes {
+                        let Some(transform) = field_to_packed_byte_transform(
+                            endian,
+                            range.unpositioned_mask(),
+                            range.lsb_pos(),
+                            byte,
+                            width_bytes,
+                        ) else {
+                            continue;
+                        };
 
-                    writeln!(out, "  val[{byte}] &= (uint8_t)~0x{:X}U;", transform.mask)?;
-                    writeln!(out, "  val[{byte}] |= (uint8_t)(((uint8_t){field_byte}) & 0x{:X}U);", transform.mask)?;
+                        let field_byte = match &transform.shift {
+                            Some((ShiftDirection::Left, amnt)) => format!("({piece_val} << {amnt})"),
+                            Some((ShiftDirection::Right, amnt)) => format!("({piece_val} >> {amnt})"),
+                            None => piece_val.clone(),
+                        };
+
+                        writeln!(out, "  val[{byte}] &= (uint8_t)~0x{:X}U;", transform.mask)?;
+                        writeln!(
+                            out,
+                            "  val[{byte}] |= (uint8_t)(((uint8_t){field_byte}) & 0x{:X}U);",
+                            transform.mask
+                        )?;
+                    }
                 }
             }
 
@@ -223,7 +248,7 @@ fn generate_layout_pack_func(
                 // Fixed value:
                 for byte in 0..width_bytes {
                     let mask_byte = grab_byte(endian, field.bits.mask(), byte, width_bytes);
-                    let value_byte = grab_byte(endian, *fixed << field.bits.lsb_pos(), byte, width_bytes);
+                    let value_byte = grab_byte(endian, field.bits.insert(*fixed), byte, width_bytes);
                     if mask_byte == 0 {
                         continue;
                     };
@@ -350,11 +375,11 @@ fn generate_layout_unpack_func(
     for field in layout.fields_with_content() {
         let code_field_name = c_code(&field.name);
 
-        let bit_str = field.bits.to_string(RangeStyle::Verilog);
+        let bit_str = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
         writeln!(out, "  // {} @ {code_name}[{bit_str}]:", field.name)?;
 
         match &field.accepts {
-            FieldType::UInt | FieldType::Bool => {
+            FieldType::UInt | FieldType::SInt | FieldType::Bool => {
                 // Numeric fields can be directly converted:
                 let numeric_value = assemble_numeric_field(layout, field, endian)?;
 
@@ -474,6 +499,10 @@ fn generate_layout_validation_func(out: &mut dyn Write, inp: &Input, layout: &La
         match &field.accepts {
             FieldType::UInt => {
                 writeln!(out, "  if ((r->{field_name} & ~({uint_type})0x{unpos_mask:X}) != 0) return {error_code};")?;
+                if let Some(constraints) = &field.constraints {
+                    let checks = constraint_checks(&format!("r->{field_name}"), constraints);
+                    writeln!(out, "  if ({}) return {error_code};", checks.join(" || "))?;
+                }
                 valid_stmt_cnt += 1;
             }
             FieldType::Enum(e) => {
@@ -486,6 +515,20 @@ fn generate_layout_validation_func(out: &mut dyn Write, inp: &Input, layout: &La
                 writeln!(out, "  if ({code_prefix}_validate_{layout_name}(&r->{field_name})) return {error_code};")?;
                 valid_stmt_cnt += 1;
             }
+            FieldType::SInt => {
+                // Full-width signed fields can hold any value of their type:
+                let width = field.bits.width();
+                if matches!(width, 8 | 16 | 32 | 64) {
+                    continue;
+                }
+                let sign_bit = 1_u64 << (width - 1);
+                writeln!(
+                    out,
+                    "  if (r->{field_name} < -0x{sign_bit:X} || r->{field_name} > 0x{:X}) return {error_code};",
+                    sign_bit - 1
+                )?;
+                valid_stmt_cnt += 1;
+            }
             FieldType::Bool => continue,
             FieldType::Fixed(_) => unreachable!(),
         }
@@ -553,6 +596,174 @@ fn generate_layout_try_unpack_func(
     Ok(())
 }
 
+/// Conditions that are true if the given field value violates the constraints.
+fn constraint_checks(val: &str, constraints: &FieldConstraints) -> Vec<String> {
+    let mut checks = vec![];
+    if let Some(min) = constraints.min {
+        checks.push(format!("{val} < 0x{min:X}U"));
+    }
+    if let Some(max) = constraints.max {
+        checks.push(format!("{val} > 0x{max:X}U"));
+    }
+    if let Some(step) = constraints.step {
+        match constraints.min {
+            Some(min) => checks.push(format!("({val} - 0x{min:X}U) % 0x{step:X}U != 0")),
+            None => checks.push(format!("{val} % 0x{step:X}U != 0")),
+        }
+    }
+    for reserved in &constraints.reserved {
+        checks.push(format!("{val} == 0x{reserved:X}U"));
+    }
+    checks
+}
+
+/// Generate physical unit conversion functions for a scaled field.
+fn generate_field_scaling_funcs(
+    out: &mut dyn Write,
+    inp: &Input,
+    layout: &Layout,
+    field: &LayoutField,
+    scaling: &Scaling,
+) -> Result<(), Error> {
+    // Strings:
+    let struct_name = format!("{}_{}", c_code(&inp.map.name), c_code(&layout.name));
+    let func_prefix = func_prefix(inp);
+    let field_name = c_code(&field.name);
+    let field_type = struct_field_type(inp, field)?;
+    let unit_name = scaling.unit_name();
+    let unit = &scaling.unit;
+    let conversion = scaling_str(scaling);
+    let Some(range) = field.numeric_range() else {
+        return Ok(());
+    };
+
+    let table_init = |table: &[i64]| {
+        let entries = table.iter().map(|x| c_int64(*x)).collect::<Vec<_>>().join(", ");
+        format!("static const int64_t table[{}] = {{{entries}}};", table.len())
+    };
+
+    // Field -> Physical:
+    writeln!(out)?;
+    let docs = Docs {
+        brief: Some(format!("Convert {field_name} field value to {unit_name} ({unit}: {conversion}).")),
+        doc: None,
+    };
+    let can_always_convert = scaling.can_always_convert(field.bits.width());
+    if can_always_convert {
+        c_generate_doxy_comment(out, &docs, "", vec![])?;
+    } else {
+        c_generate_doxy_comment(
+            out,
+            &docs,
+            "",
+            vec![
+                (String::from("returns"), String::from("0 if the value has a physical equivalent.")),
+                (String::from("returns"), String::from("nonzero otherwise.")),
+            ],
+        )?;
+    }
+
+    let func_sig = if can_always_convert {
+        format!("{func_prefix}int64_t {struct_name}_{field_name}_to_{unit_name}({field_type} val)")
+    } else {
+        format!("{func_prefix}int {struct_name}_{field_name}_to_{unit_name}({field_type} val, int64_t *{unit_name})")
+    };
+
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+    } else {
+        writeln!(out, "{func_sig} {{")?;
+        match &scaling.conversion {
+            ScalingConversion::Linear { scale, offset } => {
+                let scaled = match scale {
+                    1 => "(int64_t)val".to_string(),
+                    -1 => "-(int64_t)val".to_string(),
+                    scale => format!("{} * (int64_t)val", c_int64(*scale)),
+                };
+                let physical = match offset {
+                    0 => scaled,
+                    offset => format!("{} + {scaled}", c_int64(*offset)),
+                };
+                writeln!(out, "  return {physical};")?;
+            }
+            ScalingConversion::Table(table) if can_always_convert => {
+                writeln!(out, "  {}", table_init(table))?;
+                writeln!(out, "  return table[val & 0x{:X}U];", field.bits.unpositioned_mask())?;
+            }
+            ScalingConversion::Table(table) => {
+                writeln!(out, "  {}", table_init(table))?;
+                writeln!(out, "  if (val >= {}) return 1;", table.len())?;
+                writeln!(out, "  *{unit_name} = table[val];")?;
+                writeln!(out, "  return 0;")?;
+            }
+        }
+        writeln!(out, "}}")?;
+    }
+
+    // Physical -> Field:
+    writeln!(out)?;
+    let docs = Docs {
+        brief: Some(format!("Convert {unit_name} to {field_name} field value.")),
+        doc: None,
+    };
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![
+            (String::from("returns"), String::from("0 if the value can be represented exactly.")),
+            (String::from("returns"), String::from("nonzero otherwise.")),
+        ],
+    )?;
+
+    let func_sig =
+        format!("{func_prefix}int {struct_name}_{field_name}_from_{unit_name}(int64_t {unit_name}, {field_type} *val)");
+
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+        return Ok(());
+    }
+
+    writeln!(out, "{func_sig} {{")?;
+    match &scaling.conversion {
+        ScalingConversion::Linear { scale, offset } => {
+            // Validation guarantees that all physical values, and the differences
+            // between them, fit into an int64_t:
+            let physical = |val: i128| i64::try_from(i128::from(*offset) + i128::from(*scale) * val).unwrap();
+            let (min, max) = if *scale > 0 {
+                (physical(*range.start()), physical(*range.end()))
+            } else {
+                (physical(*range.end()), physical(*range.start()))
+            };
+            let offset_val = match offset {
+                0 => unit_name.clone(),
+                offset => format!("({unit_name} - {})", c_int64(*offset)),
+            };
+            writeln!(out, "  if ({unit_name} < {} || {unit_name} > {}) return 1;", c_int64(min), c_int64(max))?;
+            if *scale == 1 {
+                writeln!(out, "  *val = ({field_type}){offset_val};")?;
+            } else {
+                let scale = c_int64(*scale);
+                writeln!(out, "  if ({offset_val} % {scale} != 0) return 1;")?;
+                writeln!(out, "  *val = ({field_type})({offset_val} / {scale});")?;
+            }
+            writeln!(out, "  return 0;")?;
+        }
+        ScalingConversion::Table(table) => {
+            writeln!(out, "  {}", table_init(table))?;
+            writeln!(out, "  for (uint32_t i = 0; i < {}; i++) {{", table.len())?;
+            writeln!(out, "    if (table[i] == {unit_name}) {{")?;
+            writeln!(out, "      *val = ({field_type})i;")?;
+            writeln!(out, "      return 0;")?;
+            writeln!(out, "    }}")?;
+            writeln!(out, "  }}")?;
+            writeln!(out, "  return 1;")?;
+        }
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
 fn struct_field_type(inp: &Input, field: &LayoutField) -> Result<String, Error> {
     let code_prefix = c_code(&inp.map.name);
     let field_width = field.bits.width();
@@ -567,6 +778,7 @@ fn struct_field_type(inp: &Input, field: &LayoutField) -> Result<String, Error>
             }
         }
         FieldType::UInt => c_fitting_unsigned_type(field_width)?,
+        FieldType::SInt => c_fitting_signed_type(field_width)?,
         FieldType::Bool => "bool".to_string(),
         FieldType::Layout(layout) => {
             let name = c_code(&layout.name);
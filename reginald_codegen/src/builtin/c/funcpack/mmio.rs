@@ -0,0 +1,256 @@
+use std::{fmt::Write, rc::Rc};
+
+use crate::{
+    error::Error,
+    regmap::{Docs, Layout, Register, RegisterBlock, RegisterBlockMember, TypeAdr, TypeBitwidth},
+};
+
+use super::{Element, Input, c_code, c_generate_doxy_comment, c_macro, func_prefix};
+
+/// Generate memory-mapped access functions for an individual register.
+///
+/// The register address is treated as an offset from the given base pointer.
+pub fn generate_register_mmio(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
+    if !inp.opts.mmio_access || !inp.opts.is_enabled(Element::StructConversionFuncs) {
+        return Ok(());
+    }
+
+    writeln!(out)?;
+    writeln!(out, "// Memory-mapped access:")?;
+    let func_name = format!("{}_{}", c_code(&inp.map.name), c_code(&register.name));
+    generate_access_funcs(out, inp, &func_name, &register.name, &register.layout, register.adr)?;
+    Ok(())
+}
+
+/// Generate `volatile` struct overlay for a register block, alongside
+/// memory-mapped access functions for each member.
+///
+/// Member offsets are treated as an offset from the given base pointer, which
+/// is expected to point at the start of a block instance.
+///
+/// The overlay is generated as part of [`Element::Structs`], the instance pointers as
+/// part of [`Element::RegisterProperties`], and the access functions as part of
+/// [`Element::StructConversionFuncs`].
+pub fn generate_register_block_mmio(out: &mut dyn Write, inp: &Input, block: &RegisterBlock) -> Result<(), Error> {
+    if !inp.opts.mmio_access {
+        return Ok(());
+    }
+
+    let code_prefix = c_code(&inp.map.name);
+    let macro_prefix = c_macro(&inp.map.name);
+    let block_name = &block.name;
+    let struct_name = format!("{code_prefix}_{}_regs", c_code(block_name));
+
+    let mut members: Vec<_> = block.members.values().collect();
+    members.sort_by_key(|x| x.offset);
+
+    // Validate member placement, even if the overlay itself is not generated:
+    let mut pos: TypeAdr = 0;
+    for member in &members {
+        let width_bytes = member.layout.width_bytes();
+
+        if member.offset < pos {
+            return Err(Error::GeneratorError(format!(
+                "Register block {block_name} member {} at offset 0x{:X} overlaps with previous member.",
+                member.name_raw, member.offset
+            )));
+        }
+
+        if member.offset % mmio_alignment(width_bytes) != 0 {
+            return Err(Error::GeneratorError(format!(
+                "Register block {block_name} member {} at offset 0x{:X} is not aligned to its {width_bytes}-byte access width.",
+                member.name_raw, member.offset
+            )));
+        }
+
+        pos = member.offset + TypeAdr::from(width_bytes);
+    }
+
+    if inp.opts.is_enabled(Element::Structs) {
+        generate_register_block_overlay(out, &struct_name, block_name, &members)?;
+    }
+
+    // Instance pointers:
+    if inp.opts.is_enabled(Element::RegisterProperties) && !block.instances.is_empty() {
+        writeln!(out)?;
+        for instance in block.instances.values() {
+            writeln!(
+                out,
+                "#define {macro_prefix}_{}_INSTANCE_{}_REGS ((struct {struct_name} *)(uintptr_t)0x{:X}U) //!< {} instance {} registers",
+                c_macro(block_name),
+                c_macro(&instance.name),
+                instance.adr,
+                block_name,
+                instance.name,
+            )?;
+        }
+    }
+
+    // Member access functions:
+    if inp.opts.is_enabled(Element::StructConversionFuncs) {
+        for member in &members {
+            let func_name = format!("{code_prefix}_{}", c_code(&member.name));
+            generate_access_funcs(out, inp, &func_name, &member.name, &member.layout, member.offset)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate `volatile` struct overlay for a register block, with offset checks.
+fn generate_register_block_overlay(
+    out: &mut dyn Write,
+    struct_name: &str,
+    block_name: &str,
+    members: &[&Rc<RegisterBlockMember>],
+) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "// Memory-mapped register block:")?;
+    writeln!(out)?;
+    writeln!(out, "/** @brief {block_name} register block memory layout. */")?;
+    writeln!(out, "struct {struct_name} {{")?;
+
+    let mut pos: TypeAdr = 0;
+    let mut reserved_cnt = 0;
+    for member in members {
+        let width_bytes = member.layout.width_bytes();
+        let member_name = c_code(&member.name_raw);
+
+        if member.offset > pos {
+            writeln!(out, "  uint8_t _reserved{reserved_cnt}[0x{:X}U];", member.offset - pos)?;
+            reserved_cnt += 1;
+        }
+
+        let comment = format!("//!< {} register (offset 0x{:X})", member.name, member.offset);
+        match mmio_uint_type(width_bytes) {
+            Some(uint_type) => writeln!(out, "  volatile {uint_type} {member_name}; {comment}")?,
+            None => writeln!(out, "  volatile uint8_t {member_name}[{width_bytes}]; {comment}")?,
+        }
+
+        pos = member.offset + TypeAdr::from(width_bytes);
+    }
+    writeln!(out, "}};")?;
+
+    // Offset checks:
+    if !members.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "#if defined(__STDC_VERSION__) && (__STDC_VERSION__ >= 201112L)")?;
+        for member in members {
+            let member_name = c_code(&member.name_raw);
+            writeln!(
+                out,
+                "_Static_assert(offsetof(struct {struct_name}, {member_name}) == 0x{:X}U, \"{} offset mismatch\");",
+                member.offset, member.name
+            )?;
+        }
+        writeln!(out, "#endif")?;
+    }
+
+    Ok(())
+}
+
+/// Generate read/write functions for a register at a given offset from a base pointer.
+fn generate_access_funcs(
+    out: &mut dyn Write,
+    inp: &Input,
+    func_name: &str,
+    reg_name: &str,
+    layout: &Layout,
+    offset: TypeAdr,
+) -> Result<(), Error> {
+    let func_prefix = func_prefix(inp);
+    let width_bytes = layout.width_bytes();
+    let uint_type = mmio_uint_type(width_bytes);
+
+    // Read:
+    let docs = Docs {
+        brief: Some(format!("Read {reg_name} register at offset 0x{offset:X} from base.")),
+        doc: None,
+    };
+    writeln!(out)?;
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![(String::from("note"), String::from("performs a volatile access in native byte order."))],
+    )?;
+    let func_sig = match &uint_type {
+        Some(uint_type) => format!("{func_prefix}{uint_type} {func_name}_read(const volatile void *base)"),
+        None => format!("{func_prefix}void {func_name}_read(const volatile void *base, uint8_t val[{width_bytes}])"),
+    };
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+    } else {
+        writeln!(out, "{func_sig} {{")?;
+        match &uint_type {
+            Some(uint_type) => {
+                writeln!(
+                    out,
+                    "  return *(const volatile {uint_type} *)((const volatile uint8_t *)base + 0x{offset:X}U);"
+                )?;
+            }
+            None => {
+                writeln!(out, "  const volatile uint8_t *reg = (const volatile uint8_t *)base + 0x{offset:X}U;")?;
+                writeln!(out, "  for (size_t i = 0; i < {width_bytes}; i++) {{ val[i] = reg[i]; }}")?;
+            }
+        }
+        writeln!(out, "}}")?;
+    }
+
+    // Write:
+    let docs = Docs {
+        brief: Some(format!("Write {reg_name} register at offset 0x{offset:X} from base.")),
+        doc: None,
+    };
+    writeln!(out)?;
+    c_generate_doxy_comment(
+        out,
+        &docs,
+        "",
+        vec![(String::from("note"), String::from("performs a volatile access in native byte order."))],
+    )?;
+    let func_sig = match &uint_type {
+        Some(uint_type) => format!("{func_prefix}void {func_name}_write(volatile void *base, {uint_type} val)"),
+        None => {
+            format!("{func_prefix}void {func_name}_write(volatile void *base, const uint8_t val[{width_bytes}])")
+        }
+    };
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+    } else {
+        writeln!(out, "{func_sig} {{")?;
+        match &uint_type {
+            Some(uint_type) => {
+                writeln!(out, "  *(volatile {uint_type} *)((volatile uint8_t *)base + 0x{offset:X}U) = val;")?;
+            }
+            None => {
+                writeln!(out, "  volatile uint8_t *reg = (volatile uint8_t *)base + 0x{offset:X}U;")?;
+                writeln!(out, "  for (size_t i = 0; i < {width_bytes}; i++) {{ reg[i] = val[i]; }}")?;
+            }
+        }
+        writeln!(out, "}}")?;
+    }
+
+    Ok(())
+}
+
+/// Unsigned type used to access a register of given width, if it can be
+/// accessed as a single integer.
+fn mmio_uint_type(width_bytes: TypeBitwidth) -> Option<&'static str> {
+    match width_bytes {
+        1 => Some("uint8_t"),
+        2 => Some("uint16_t"),
+        4 => Some("uint32_t"),
+        8 => Some("uint64_t"),
+        _ => None,
+    }
+}
+
+/// Required alignment of a register of given width.
+fn mmio_alignment(width_bytes: TypeBitwidth) -> TypeAdr {
+    if mmio_uint_type(width_bytes).is_some() {
+        TypeAdr::from(width_bytes)
+    } else {
+        1
+    }
+}
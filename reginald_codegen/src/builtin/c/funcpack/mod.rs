@@ -1,6 +1,8 @@
 mod enums;
 mod layouts;
+mod mmio;
 mod registers;
+mod virtual_fields;
 
 use std::{collections::HashSet, fmt::Write, path::Path, rc::Rc};
 
@@ -10,7 +12,6 @@ use reginald_utils::str_pad_to_length;
 use clap::ValueEnum;
 
 use crate::{
-    bits::unpositioned_mask,
     error::Error,
     regmap::{Docs, FieldType, Layout, LayoutField, RegisterMap, TypeBitwidth, TypeValue},
     utils::{Endianess, ShiftDirection, packed_byte_to_field_transform},
@@ -18,7 +19,7 @@ use crate::{
 };
 
 use super::{
-    c_code, c_fitting_unsigned_type, c_generate_doxy_comment, c_generate_header_comment,
+    c_code, c_fitting_signed_type, c_fitting_unsigned_type, c_generate_doxy_comment, c_generate_header_comment,
     c_generate_section_header_comment, c_header_comment, c_layout_overview_comment, c_macro, c_section_header_comment,
 };
 
@@ -76,6 +77,14 @@ pub struct GeneratorOpts {
     /// Generate include guard
     pub include_guards: bool,
 
+    /// Generate memory-mapped register access.
+    ///
+    /// Generates `volatile` struct overlays for register blocks, and read/write
+    /// functions for each register that access it relative to a base pointer.
+    /// Overlays, instance pointers and functions are generated as part of the
+    /// `Structs`, `RegisterProperties` and `StructConversionFuncs` elements.
+    pub mmio_access: bool,
+
     /// Set of elements to generate.
     ///
     /// May be used to split generated content into multiple files.
@@ -97,6 +106,7 @@ impl Default for GeneratorOpts {
             funcs_as_prototypes: false,
             clang_format_guard: true,
             include_guards: true,
+            mmio_access: false,
             to_generate: HashSet::from([
                 Element::Enums,
                 Element::EnumValidationMacros,
@@ -180,6 +190,15 @@ pub fn generate(
         registers::generate_register_block(&mut out, &inp, block)?;
     }
 
+    // ===== Virtual fields: =====
+    out.push_section_with_header(&["\n", &c_section_header_comment("Virtual Fields"), "\n"]);
+    for field in map.virtual_fields.values() {
+        out.push_section_with_header(&["\n", &c_header_comment(&format!("{} Virtual Field", field.name)), "\n"]);
+        virtual_fields::generate_virtual_field(&mut out, &inp, field)?;
+        out.pop_section();
+    }
+    out.pop_section();
+
     generate_generic_macros(&mut out, &inp)?;
 
     generate_footer(&mut out, &inp)?;
@@ -243,7 +262,7 @@ fn generate_header(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
     writeln!(out)?;
     writeln!(out, "#include <stdint.h>")?;
     writeln!(out, "#include <stdbool.h>")?;
-    if inp.opts.defer_to_endian.is_some() {
+    if inp.opts.defer_to_endian.is_some() || inp.opts.mmio_access {
         writeln!(out, "#include <stddef.h>")?;
     }
     for include in &inp.opts.add_include {
@@ -386,6 +405,17 @@ fn func_prefix(inp: &Input) -> &'static str {
     }
 }
 
+/// C literal of a 64-bit signed value.
+fn c_int64(val: i64) -> String {
+    if val == i64::MIN {
+        "INT64_MIN".to_string()
+    } else if i32::try_from(val).is_ok() {
+        val.to_string()
+    } else {
+        format!("INT64_C({val})")
+    }
+}
+
 fn swap_loop(from: &str, to: &str, width_bytes: TypeBitwidth) -> String {
     format!("for(size_t i = 0; i < {width_bytes}; i++) {{ {to}[i] = {from}[{width_bytes}-(i+1)]; }}")
 }
@@ -418,44 +448,79 @@ fn assemble_numeric_field(layout: &Layout, field: &LayoutField, endian: Endianes
     };
 
     let mut unpacked_value: Vec<String> = vec![];
-    for byte in 0..layout_width_bytes {
-        let Some(transform) = packed_byte_to_field_transform(
-            endian,
-            unpositioned_mask(field.bits.mask()),
-            field.bits.lsb_pos(),
-            byte,
-            layout_width_bytes,
-        ) else {
-            continue;
-        };
 
-        let masked = if pre_cast.is_empty() {
-            format!("(val[{byte}] & 0x{:X}U)", transform.mask)
-        } else {
-            format!("({pre_cast}(val[{byte}] & 0x{:X}U))", transform.mask)
-        };
+    // Each range of a split field is assembled separately, and then shifted into position:
+    for (range, value_pos) in field.bits.pieces() {
+        let mut piece_value: Vec<String> = vec![];
+        for byte in 0..layout_width_bytes {
+            let Some(transform) = packed_byte_to_field_transform(
+                endian,
+                range.unpositioned_mask(),
+                range.lsb_pos(),
+                byte,
+                layout_width_bytes,
+            ) else {
+                continue;
+            };
+
+            let masked = if pre_cast.is_empty() {
+                format!("(val[{byte}] & 0x{:X}U)", transform.mask)
+            } else {
+                format!("({pre_cast}(val[{byte}] & 0x{:X}U))", transform.mask)
+            };
+
+            match &transform.shift {
+                Some((ShiftDirection::Left, amnt)) => piece_value.push(format!("({masked} << {amnt})")),
+                Some((ShiftDirection::Right, amnt)) => piece_value.push(format!("({masked} >> {amnt})")),
+                None => piece_value.push(masked),
+            };
+        }
+        assert!(!piece_value.is_empty());
 
-        match &transform.shift {
-            Some((ShiftDirection::Left, amnt)) => unpacked_value.push(format!("({masked} << {amnt})")),
-            Some((ShiftDirection::Right, amnt)) => unpacked_value.push(format!("({masked} >> {amnt})")),
-            None => unpacked_value.push(masked),
-        };
+        if value_pos == 0 {
+            unpacked_value.append(&mut piece_value);
+        } else if piece_value.len() == 1 {
+            unpacked_value.push(format!("({} << {value_pos})", piece_value[0]));
+        } else {
+            unpacked_value.push(format!("(({}) << {value_pos})", piece_value.join(" | ")));
+        }
     }
-    assert!(!unpacked_value.is_empty());
 
     let unpacked_value = unpacked_value.join(" | ");
 
     let post_cast = match &field.accepts {
-        FieldType::UInt => format!("({})", c_fitting_unsigned_type(field.bits.width())?),
+        FieldType::UInt | FieldType::SInt => format!("({})", c_fitting_unsigned_type(field.bits.width())?),
         FieldType::Bool => String::from("(bool)"),
         FieldType::Enum(_) => String::new(),
         FieldType::Fixed(_) => unreachable!(),
         FieldType::Layout(_) => unreachable!(),
     };
 
-    if post_cast.is_empty() {
-        Ok(unpacked_value.to_string())
+    let unpacked_value = if post_cast.is_empty() {
+        unpacked_value.to_string()
+    } else {
+        format!("{post_cast}({unpacked_value})")
+    };
+
+    if matches!(field.accepts, FieldType::SInt) {
+        Ok(sign_extend_numeric_field(&unpacked_value, field.bits.width())?)
+    } else {
+        Ok(unpacked_value)
+    }
+}
+
+/// Sign-extend an unsigned field value of the given width to a signed value.
+///
+/// Avoids implementation-defined conversions of out-of-range values to signed
+/// types where the field is narrower than the signed type.
+fn sign_extend_numeric_field(unsigned_value: &str, width: TypeBitwidth) -> Result<String, Error> {
+    let signed_type = c_fitting_signed_type(width)?;
+    if matches!(width, 8 | 16 | 32 | 64) {
+        Ok(format!("({signed_type})({unsigned_value})"))
     } else {
-        Ok(format!("{post_cast}({unpacked_value})"))
+        let sign_bit = 1_u64 << (width - 1);
+        Ok(format!(
+            "({signed_type})(({signed_type})({unsigned_value} ^ 0x{sign_bit:X}U) - ({signed_type})0x{sign_bit:X})"
+        ))
     }
 }
@@ -10,7 +10,7 @@ use crate::{
 
 use super::{
     Element, Input, c_code, c_generate_header_comment, c_generate_section_header_comment, c_layout_overview_comment,
-    c_macro, layouts, to_array_init,
+    c_macro, layouts, mmio, to_array_init,
 };
 
 /// Generate register section header comment
@@ -22,6 +22,7 @@ pub fn generate_register(out: &mut dyn Write, inp: &Input, register: &Register)
     out.push_section_with_header(&[&header]);
 
     generate_register_properties(&mut out, inp, register)?;
+    mmio::generate_register_mmio(&mut out, inp, register)?;
 
     // If the layout is local to this register, generate it:
     if register.layout.is_local {
@@ -99,6 +100,7 @@ pub fn generate_register_block(out: &mut dyn Write, inp: &Input, block: &Registe
     out.push_section_with_header(&[&header]);
 
     generate_register_block_properties(&mut out, inp, block)?;
+    mmio::generate_register_block_mmio(&mut out, inp, block)?;
 
     for member in block.members.values() {
         let mut header = String::new();
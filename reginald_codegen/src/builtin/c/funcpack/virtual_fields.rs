@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+use crate::{
+    error::Error,
+    regmap::{Docs, TypeValue, VirtualField, VirtualFieldPart},
+};
+
+use super::{
+    Element, Input, c_code, c_fitting_signed_type, c_fitting_unsigned_type, c_generate_doxy_comment, func_prefix,
+    sign_extend_numeric_field,
+};
+
+/// Generate functions to assemble a virtual field from, and split it into, register structs.
+pub fn generate_virtual_field(out: &mut dyn Write, inp: &Input, field: &VirtualField) -> Result<(), Error> {
+    if !inp.opts.is_enabled(Element::StructConversionFuncs) {
+        return Ok(());
+    }
+
+    // Strings:
+    let code_prefix = c_code(&inp.map.name);
+    let func_prefix = func_prefix(inp);
+    let field_name = c_code(&field.name);
+    let width = field.width();
+    let raw_type = c_fitting_unsigned_type(width)?;
+    let value_type = if field.signed {
+        c_fitting_signed_type(width)?
+    } else {
+        raw_type.clone()
+    };
+
+    // Parts listing for doxygen comments:
+    let mut parts_doc = String::from("Parts, most significant first:");
+    for (part, value_pos) in field.pieces() {
+        let part_width = part.field().bits.width();
+        if part_width == 1 {
+            write!(parts_doc, "\n - {}: Bit {value_pos}", part.name())?;
+        } else {
+            write!(parts_doc, "\n - {}: Bits {}-{value_pos}", part.name(), value_pos + part_width - 1)?;
+        }
+    }
+
+    // ==== Assemble ====:
+    let args: Vec<String> = field
+        .registers()
+        .iter()
+        .map(|x| format!("const struct {code_prefix}_{} *{}", c_code(&x.layout.name), c_code(&x.name)))
+        .collect();
+
+    writeln!(out)?;
+    let docs = Docs {
+        brief: Some(format!("Assemble {} value from register structs.", field.name)),
+        doc: Some(parts_doc.clone()),
+    };
+    c_generate_doxy_comment(out, &docs, "", vec![])?;
+    let func_sig = format!("{func_prefix}{value_type} {code_prefix}_{field_name}_assemble({})", args.join(", "));
+
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+    } else {
+        let mut parts: Vec<String> = vec![];
+        for (part, value_pos) in field.pieces() {
+            let part_mask = part.field().bits.unpositioned_mask();
+            let mut value = format!("(({raw_type})({} & {}))", part_access(part), c_mask(part_mask));
+            if value_pos != 0 {
+                value = format!("({value} << {value_pos})");
+            }
+            parts.push(value);
+        }
+        let raw_value = format!("({raw_type})({})", parts.join(" | "));
+
+        writeln!(out, "{func_sig} {{")?;
+        if field.signed {
+            writeln!(out, "  return {};", sign_extend_numeric_field(&raw_value, width)?)?;
+        } else {
+            writeln!(out, "  return {raw_value};")?;
+        }
+        writeln!(out, "}}")?;
+    }
+
+    // ==== Split ====:
+    let args: Vec<String> = field
+        .registers()
+        .iter()
+        .map(|x| format!("struct {code_prefix}_{} *{}", c_code(&x.layout.name), c_code(&x.name)))
+        .collect();
+
+    writeln!(out)?;
+    let docs = Docs {
+        brief: Some(format!("Split {} value into register structs.", field.name)),
+        doc: Some(format!("{parts_doc}\nAll other fields are left untouched.")),
+    };
+    c_generate_doxy_comment(out, &docs, "", vec![])?;
+    let func_sig = format!("{func_prefix}void {code_prefix}_{field_name}_split({value_type} val, {})", args.join(", "));
+
+    if inp.opts.funcs_as_prototypes {
+        writeln!(out, "{func_sig};")?;
+    } else {
+        writeln!(out, "{func_sig} {{")?;
+        let val = if field.signed {
+            writeln!(out, "  {raw_type} raw = ({raw_type})val;")?;
+            "raw"
+        } else {
+            "val"
+        };
+        for (part, value_pos) in field.pieces() {
+            let part_type = c_fitting_unsigned_type(part.field().bits.width())?;
+            let part_mask = part.field().bits.unpositioned_mask();
+            let shifted = if value_pos == 0 {
+                val.to_string()
+            } else {
+                format!("({val} >> {value_pos})")
+            };
+            writeln!(out, "  {} = ({part_type})({shifted} & {});", part_access(part), c_mask(part_mask))?;
+        }
+        writeln!(out, "}}")?;
+    }
+
+    Ok(())
+}
+
+/// Expression accessing the field holding a part, through the register struct pointer.
+fn part_access(part: &VirtualFieldPart) -> String {
+    format!("{}->{}", c_code(&part.register.name), c_code(&part.field))
+}
+
+/// Unsigned mask literal.
+fn c_mask(mask: TypeValue) -> String {
+    if mask > u32::MAX.into() {
+        format!("UINT64_C(0x{mask:X})")
+    } else {
+        format!("0x{mask:X}U")
+    }
+}
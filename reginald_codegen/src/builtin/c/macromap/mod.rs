@@ -45,6 +45,9 @@ impl Default for GeneratorOpts {
 // ====== Generator ============================================================
 
 pub fn generate(out: &mut dyn Write, map: &RegisterMap, output_file: &Path, opts: &GeneratorOpts) -> Result<(), Error> {
+    map.require_contiguous_fields("c-macromap")?;
+    map.require_max_layout_width(64, "c-macromap")?;
+
     generate_header(out, map, output_file, opts)?;
 
     for register in map.individual_registers() {
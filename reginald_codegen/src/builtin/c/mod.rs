@@ -7,6 +7,7 @@ use crate::{
 use regex::Regex;
 use reginald_utils::str_pad_to_length;
 
+pub mod driver;
 pub mod funcpack;
 pub mod macromap;
 
@@ -34,6 +35,16 @@ fn c_fitting_unsigned_type(width: TypeBitwidth) -> Result<String, Error> {
     }
 }
 
+fn c_fitting_signed_type(width: TypeBitwidth) -> Result<String, Error> {
+    match width {
+        1..=8 => Ok("int8_t".to_string()),
+        9..=16 => Ok("int16_t".to_string()),
+        17..=32 => Ok("int32_t".to_string()),
+        33..=64 => Ok("int64_t".to_string()),
+        _ => Err(Error::GeneratorError(format!("Cannot represent {width}-bit wide value as C type!"))),
+    }
+}
+
 fn c_section_header_comment(title: &str) -> String {
     let frame = "//===----------------------------------------------------------------------===//";
     format!("{frame}\n// {title}\n{frame}")
@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+use crate::{error::Error, regmap::Enum};
+
+use super::{cpp_fitting_unsigned_type, cpp_generate_doc_comment, cpp_pascalcase, cpp_uint_literal};
+
+/// Generate an enum class alongside its validation function.
+pub fn generate_enum(out: &mut dyn Write, e: &Enum) -> Result<(), Error> {
+    let name = cpp_pascalcase(&e.name);
+    let underlying_type = cpp_fitting_unsigned_type(e.bitwidth)?;
+
+    // Enum proper:
+    writeln!(out)?;
+    cpp_generate_doc_comment(out, &e.docs, "", &[])?;
+    writeln!(out, "enum class {name} : {underlying_type} {{")?;
+    for entry in e.entries.values() {
+        cpp_generate_doc_comment(out, &entry.docs, "  ", &[])?;
+        writeln!(out, "  {} = {},", cpp_pascalcase(&entry.name), cpp_uint_literal(entry.value))?;
+    }
+    writeln!(out, "}};")?;
+
+    // Validation:
+    writeln!(out)?;
+    writeln!(out, "/// Check if a `{name}` holds one of its defined values.")?;
+    writeln!(out, "[[nodiscard]] constexpr bool is_valid({name} v) {{")?;
+    writeln!(out, "  switch (v) {{")?;
+    for entry in e.entries.values() {
+        writeln!(out, "    case {name}::{}:", cpp_pascalcase(&entry.name))?;
+    }
+    writeln!(out, "      return true;")?;
+    writeln!(out, "    default:")?;
+    writeln!(out, "      return false;")?;
+    writeln!(out, "  }}")?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
@@ -0,0 +1,435 @@
+use std::fmt::Write;
+
+use reginald_utils::RangeStyle;
+
+use crate::{
+    error::Error,
+    regmap::{FieldType, Layout, LayoutField, TypeValue},
+    utils::{
+        Endianess, ShiftDirection, field_byte_to_packed_byte_transform, field_to_packed_byte_transform, grab_byte,
+        packed_byte_to_field_byte_transform, packed_byte_to_field_transform,
+    },
+};
+
+use super::{
+    Input, cpp_fitting_signed_type, cpp_fitting_unsigned_type, cpp_generate_doc_comment, cpp_layout_name,
+    cpp_pascalcase, cpp_snakecase, cpp_uint_literal,
+};
+
+/// Generate a layout struct, including all conversion functions.
+pub fn generate_layout(out: &mut dyn Write, inp: &Input, layout: &Layout) -> Result<(), Error> {
+    let name = cpp_layout_name(layout);
+    let width_bytes = layout.width_bytes();
+
+    // Struct proper:
+    writeln!(out)?;
+    cpp_generate_doc_comment(
+        out,
+        &layout.docs,
+        "",
+        &["Use the `pack`/`unpack` functions for conversion to/from packed binary values."],
+    )?;
+    writeln!(out, "struct {name} {{")?;
+    for field in layout.fields_with_content() {
+        cpp_generate_doc_comment(out, &field.docs, "  ", &[])?;
+        writeln!(out, "  {} {}{{}};", struct_field_type(field)?, cpp_snakecase(&field.name))?;
+    }
+    if layout.fields_with_content().count() > 0 {
+        writeln!(out)?;
+    }
+    writeln!(out, "  /// Width of the packed binary value in bytes.")?;
+    writeln!(out, "  static constexpr std::size_t width_bytes = {width_bytes};")?;
+
+    generate_pack_funcs(out, layout)?;
+    generate_unpack_funcs(out, layout)?;
+    generate_validation_func(out, inp, layout)?;
+    generate_try_unpack_funcs(out, layout)?;
+    generate_comparison_funcs(out, layout)?;
+
+    writeln!(out, "}};")?;
+
+    Ok(())
+}
+
+fn generate_pack_funcs(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let name = cpp_layout_name(layout);
+    let width_bytes = layout.width_bytes();
+    let array_type = format!("std::array<uint8_t, {width_bytes}>");
+
+    writeln!(out)?;
+    writeln!(out, "  /// Convert to packed {} binary value.", Endianess::Little)?;
+    writeln!(out, "  [[nodiscard]] constexpr {array_type} pack_le() const {{")?;
+    writeln!(out, "    {array_type} val{{}};")?;
+
+    for field in layout.fields.values() {
+        let field_name = cpp_snakecase(&field.name);
+        let bit_str = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
+        writeln!(out, "    // {} @ {name}[{bit_str}]:", field.name)?;
+
+        match &field.accepts {
+            FieldType::UInt | FieldType::SInt | FieldType::Bool | FieldType::Enum(_) => {
+                // Numeric field that can be directly converted:
+                let raw_value = match &field.accepts {
+                    FieldType::UInt => format!("this->{field_name}"),
+                    _ => format!("static_cast<{}>(this->{field_name})", cpp_fitting_unsigned_type(field.bits.width())?),
+                };
+
+                for byte in 0..width_bytes {
+                    let Some(transform) = field_to_packed_byte_transform(
+                        Endianess::Little,
+                        field.bits.unpositioned_mask(),
+                        field.bits.lsb_pos(),
+                        byte,
+                        width_bytes,
+                    ) else {
+                        continue;
+                    };
+
+                    let shifted = match &transform.shift {
+                        Some((ShiftDirection::Left, amnt)) => format!("({raw_value} << {amnt})"),
+                        Some((ShiftDirection::Right, amnt)) => format!("({raw_value} >> {amnt})"),
+                        None => raw_value.clone(),
+                    };
+
+                    writeln!(
+                        out,
+                        "    val[{byte}] = static_cast<uint8_t>(val[{byte}] | ({shifted} & 0x{:X}U));",
+                        transform.mask
+                    )?;
+                }
+            }
+
+            FieldType::Fixed(fixed) => {
+                // Fixed value:
+                for byte in 0..width_bytes {
+                    let mask_byte = grab_byte(Endianess::Little, field.bits.mask(), byte, width_bytes);
+                    let value_byte = grab_byte(Endianess::Little, *fixed << field.bits.lsb_pos(), byte, width_bytes);
+                    if mask_byte == 0 {
+                        continue;
+                    };
+
+                    writeln!(
+                        out,
+                        "    val[{byte}] = static_cast<uint8_t>(val[{byte}] | 0x{value_byte:X}U); // Fixed value."
+                    )?;
+                }
+            }
+
+            FieldType::Layout(sublayout) => {
+                // Sub-layout has to delegate to other pack function:
+                let sub_width_bytes = sublayout.width_bytes();
+                let packed_name = format!("{field_name}_packed");
+
+                let mut stmts = vec![];
+                for byte in 0..width_bytes {
+                    for field_byte in 0..sub_width_bytes {
+                        // Determine required transform to put byte 'field_byte' of field into 'byte' of
+                        // output:
+                        let Some(transform) = field_byte_to_packed_byte_transform(
+                            Endianess::Little,
+                            sublayout.occupied_mask(),
+                            field.bits.lsb_pos(),
+                            field_byte,
+                            sub_width_bytes,
+                            byte,
+                            width_bytes,
+                        ) else {
+                            continue;
+                        };
+
+                        let field_byte = format!("{packed_name}[{field_byte}]");
+                        let shifted = match &transform.shift {
+                            Some((ShiftDirection::Left, amnt)) => format!("({field_byte} << {amnt})"),
+                            Some((ShiftDirection::Right, amnt)) => format!("({field_byte} >> {amnt})"),
+                            None => field_byte,
+                        };
+
+                        stmts.push(format!(
+                            "val[{byte}] = static_cast<uint8_t>(val[{byte}] | ({shifted} & 0x{:X}U));",
+                            transform.mask
+                        ));
+                    }
+                }
+
+                if !stmts.is_empty() {
+                    writeln!(
+                        out,
+                        "    const std::array<uint8_t, {sub_width_bytes}> {packed_name} = this->{field_name}.pack_le();"
+                    )?;
+                    for stmt in stmts {
+                        writeln!(out, "    {stmt}")?;
+                    }
+                }
+            }
+        }
+    }
+
+    writeln!(out, "    return val;")?;
+    writeln!(out, "  }}")?;
+
+    writeln!(out)?;
+    writeln!(out, "  /// Convert to packed {} binary value.", Endianess::Big)?;
+    writeln!(out, "  [[nodiscard]] constexpr {array_type} pack_be() const {{")?;
+    writeln!(out, "    return detail::reverse_bytes(pack_le());")?;
+    writeln!(out, "  }}")?;
+
+    Ok(())
+}
+
+fn generate_unpack_funcs(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let name = cpp_layout_name(layout);
+    let width_bytes = layout.width_bytes();
+    let array_type = format!("std::array<uint8_t, {width_bytes}>");
+
+    let val_attr = if layout.fields_with_content().count() == 0 {
+        "[[maybe_unused]] "
+    } else {
+        ""
+    };
+
+    writeln!(out)?;
+    writeln!(out, "  /// Convert from packed {} binary value.", Endianess::Little)?;
+    writeln!(out, "  ///")?;
+    writeln!(out, "  /// Fixed bits are ignored, and enum fields may hold invalid values.")?;
+    writeln!(out, "  [[nodiscard]] static constexpr {name} unpack_le({val_attr}const {array_type} &val) {{")?;
+    writeln!(out, "    {name} r{{}};")?;
+
+    for field in layout.fields_with_content() {
+        let field_name = cpp_snakecase(&field.name);
+        let bit_str = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
+        writeln!(out, "    // {} @ {name}[{bit_str}]:", field.name)?;
+
+        match &field.accepts {
+            FieldType::UInt | FieldType::SInt | FieldType::Bool | FieldType::Enum(_) => {
+                // Numeric fields can be directly converted:
+                let raw_type = cpp_fitting_unsigned_type(field.bits.width())?;
+
+                match &field.accepts {
+                    FieldType::UInt => {
+                        let raw_value = assemble_numeric_field(layout, field, &raw_type);
+                        writeln!(out, "    r.{field_name} = {raw_value};")?;
+                    }
+                    FieldType::SInt => {
+                        // Sign-extend by flipping the sign bit and subtracting its weight:
+                        let raw_value = assemble_numeric_field(layout, field, &raw_type);
+                        let width = field.bits.width();
+                        let signed_type = cpp_fitting_signed_type(width)?;
+                        if matches!(width, 8 | 16 | 32 | 64) {
+                            writeln!(out, "    r.{field_name} = static_cast<{signed_type}>({raw_value});")?;
+                        } else {
+                            let sign_bit = cpp_uint_literal(1 << (width - 1));
+                            writeln!(
+                                out,
+                                "    r.{field_name} = static_cast<{signed_type}>(static_cast<{signed_type}>({raw_value} ^ {sign_bit}) - static_cast<{signed_type}>({sign_bit}));"
+                            )?;
+                        }
+                    }
+                    FieldType::Bool => {
+                        // Single bit, which is always contained in one byte:
+                        let byte = field.bits.lsb_pos() / 8;
+                        let mask = 1u8 << (field.bits.lsb_pos() % 8);
+                        writeln!(out, "    r.{field_name} = (val[{byte}] & 0x{mask:X}U) != 0U;")?;
+                    }
+                    FieldType::Enum(e) => {
+                        let raw_value = assemble_numeric_field(layout, field, &raw_type);
+                        writeln!(out, "    r.{field_name} = static_cast<{}>({raw_value});", cpp_pascalcase(&e.name))?;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            FieldType::Layout(sublayout) => {
+                // Sub-layout has to delegate to other unpack function:
+                let sub_width_bytes = sublayout.width_bytes();
+                let packed_name = format!("{field_name}_packed");
+
+                writeln!(out, "    std::array<uint8_t, {sub_width_bytes}> {packed_name}{{}};")?;
+                for byte in 0..width_bytes {
+                    for field_byte in 0..sub_width_bytes {
+                        // Determine required transform to put byte 'byte' of packed input into 'field_byte' of
+                        // field:
+                        let Some(transform) = packed_byte_to_field_byte_transform(
+                            Endianess::Little,
+                            sublayout.occupied_mask(),
+                            field.bits.lsb_pos(),
+                            field_byte,
+                            sub_width_bytes,
+                            byte,
+                            width_bytes,
+                        ) else {
+                            continue;
+                        };
+
+                        let masked = format!("(val[{byte}] & 0x{:X}U)", transform.mask);
+                        let shifted = match &transform.shift {
+                            Some((ShiftDirection::Left, amnt)) => format!("({masked} << {amnt})"),
+                            Some((ShiftDirection::Right, amnt)) => format!("({masked} >> {amnt})"),
+                            None => masked,
+                        };
+
+                        writeln!(
+                            out,
+                            "    {packed_name}[{field_byte}] = static_cast<uint8_t>({packed_name}[{field_byte}] | {shifted});"
+                        )?;
+                    }
+                }
+                writeln!(out, "    r.{field_name} = {}::unpack_le({packed_name});", cpp_layout_name(sublayout))?;
+            }
+            FieldType::Fixed(_) => unreachable!(),
+        }
+    }
+
+    writeln!(out, "    return r;")?;
+    writeln!(out, "  }}")?;
+
+    writeln!(out)?;
+    writeln!(out, "  /// Convert from packed {} binary value.", Endianess::Big)?;
+    writeln!(out, "  ///")?;
+    writeln!(out, "  /// Fixed bits are ignored, and enum fields may hold invalid values.")?;
+    writeln!(out, "  [[nodiscard]] static constexpr {name} unpack_be(const {array_type} &val) {{")?;
+    writeln!(out, "    return unpack_le(detail::reverse_bytes(val));")?;
+    writeln!(out, "  }}")?;
+
+    Ok(())
+}
+
+fn generate_validation_func(out: &mut dyn Write, inp: &Input, layout: &Layout) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "  /// Validate struct.")?;
+    writeln!(out, "  ///")?;
+    writeln!(out, "  /// Confirms that all enums are valid, and all values fit into respective fields.")?;
+    writeln!(out, "  [[nodiscard]] constexpr bool is_valid() const {{")?;
+
+    for field in layout.fields_with_content() {
+        let field_name = cpp_snakecase(&field.name);
+        let field_width = field.bits.width();
+
+        match &field.accepts {
+            FieldType::UInt => {
+                // Values can only be out of range if the field is narrower than its type:
+                if field_width.is_power_of_two() && field_width >= 8 {
+                    continue;
+                }
+                let max = cpp_uint_literal(field.bits.unpositioned_mask());
+                writeln!(out, "    if (this->{field_name} > {max}) {{ return false; }}")?;
+            }
+            FieldType::SInt => {
+                // Values can only be out of range if the field is narrower than its type:
+                if field_width.is_power_of_two() && field_width >= 8 {
+                    continue;
+                }
+                let sign_bit: TypeValue = 1 << (field_width - 1);
+                writeln!(
+                    out,
+                    "    if (this->{field_name} < -0x{sign_bit:X} || this->{field_name} > 0x{:X}) {{ return false; }}",
+                    sign_bit - 1
+                )?;
+            }
+            FieldType::Enum(_) => {
+                // Free function must be qualified, as it is hidden by the member function:
+                let namespace = &inp.namespace;
+                writeln!(out, "    if (!::{namespace}::is_valid(this->{field_name})) {{ return false; }}")?;
+            }
+            FieldType::Layout(_) => {
+                writeln!(out, "    if (!this->{field_name}.is_valid()) {{ return false; }}")?;
+            }
+            FieldType::Bool => continue,
+            FieldType::Fixed(_) => unreachable!(),
+        }
+    }
+
+    writeln!(out, "    return true;")?;
+    writeln!(out, "  }}")?;
+
+    Ok(())
+}
+
+fn generate_try_unpack_funcs(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let name = cpp_layout_name(layout);
+    let width_bytes = layout.width_bytes();
+    let array_type = format!("std::array<uint8_t, {width_bytes}>");
+
+    for endian in [Endianess::Little, Endianess::Big] {
+        let short = endian.short();
+        writeln!(out)?;
+        writeln!(out, "  /// Attempt to convert from packed {endian} binary value.")?;
+        writeln!(out, "  ///")?;
+        writeln!(out, "  /// Returns `std::nullopt` if the unpacked value is not valid.")?;
+        writeln!(
+            out,
+            "  [[nodiscard]] static constexpr std::optional<{name}> try_unpack_{short}(const {array_type} &val) {{"
+        )?;
+        writeln!(out, "    const {name} r = unpack_{short}(val);")?;
+        writeln!(out, "    if (!r.is_valid()) {{ return std::nullopt; }}")?;
+        writeln!(out, "    return r;")?;
+        writeln!(out, "  }}")?;
+    }
+
+    Ok(())
+}
+
+fn generate_comparison_funcs(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let name = cpp_layout_name(layout);
+
+    let (attr, comparison) = if layout.fields_with_content().count() == 0 {
+        ("[[maybe_unused]] ", "true".to_string())
+    } else {
+        let comparison: Vec<String> = layout
+            .fields_with_content()
+            .map(|x| {
+                let field_name = cpp_snakecase(&x.name);
+                format!("a.{field_name} == b.{field_name}")
+            })
+            .collect();
+        ("", comparison.join(" && "))
+    };
+
+    writeln!(out)?;
+    writeln!(out, "  friend constexpr bool operator==({attr}const {name} &a, {attr}const {name} &b) {{")?;
+    writeln!(out, "    return {comparison};")?;
+    writeln!(out, "  }}")?;
+    writeln!(out)?;
+    writeln!(out, "  friend constexpr bool operator!=(const {name} &a, const {name} &b) {{")?;
+    writeln!(out, "    return !(a == b);")?;
+    writeln!(out, "  }}")?;
+
+    Ok(())
+}
+
+/// Assemble a numeric field from the bytes of a packed little-endian value.
+fn assemble_numeric_field(layout: &Layout, field: &LayoutField, raw_type: &str) -> String {
+    let width_bytes = layout.width_bytes();
+
+    let mut parts: Vec<String> = vec![];
+    for byte in 0..width_bytes {
+        let Some(transform) = packed_byte_to_field_transform(
+            Endianess::Little,
+            field.bits.unpositioned_mask(),
+            field.bits.lsb_pos(),
+            byte,
+            width_bytes,
+        ) else {
+            continue;
+        };
+
+        let masked = format!("static_cast<{raw_type}>(val[{byte}] & 0x{:X}U)", transform.mask);
+        parts.push(match &transform.shift {
+            Some((ShiftDirection::Left, amnt)) => format!("({masked} << {amnt})"),
+            Some((ShiftDirection::Right, amnt)) => format!("({masked} >> {amnt})"),
+            None => masked,
+        });
+    }
+    assert!(!parts.is_empty());
+
+    format!("static_cast<{raw_type}>({})", parts.join(" | "))
+}
+
+fn struct_field_type(field: &LayoutField) -> Result<String, Error> {
+    Ok(match &field.accepts {
+        FieldType::UInt => cpp_fitting_unsigned_type(field.bits.width())?,
+        FieldType::SInt => cpp_fitting_signed_type(field.bits.width())?,
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Enum(e) => cpp_pascalcase(&e.name),
+        FieldType::Layout(layout) => cpp_layout_name(layout),
+        FieldType::Fixed(_) => panic!("Fixed field has no struct type"),
+    })
+}
@@ -0,0 +1,361 @@
+mod enums;
+mod layouts;
+mod registers;
+
+use std::{fmt::Write, path::Path, sync::LazyLock};
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+use regex::Regex;
+use reginald_utils::str_pad_to_length;
+
+use crate::{
+    bits::msb_pos,
+    error::Error,
+    regmap::{Docs, Layout, RegisterMap, TypeBitwidth, TypeValue},
+};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Namespace to place all generated content in.
+    ///
+    /// May be a nested namespace (`a::b`). If none is specified, the
+    /// snake-case name of the register map is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub namespace: Option<String>,
+
+    /// C++ type to use for register addresses.
+    ///
+    /// If none is specified, the smallest unsigned type capable of storing
+    /// the largest address will be used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub address_type: Option<String>,
+
+    /// Header file that should be included at the top of the generated header
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub add_include: Vec<String>,
+
+    /// Surround header with a clang-format off guard
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().clang_format_guard))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub clang_format_guard: bool,
+
+    /// Generate include guard
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().include_guards))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub include_guards: bool,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            address_type: None,
+            add_include: vec![],
+            clang_format_guard: true,
+            include_guards: true,
+        }
+    }
+}
+
+// ====== Generator ============================================================
+
+struct Input<'a> {
+    map: &'a RegisterMap,
+    namespace: String,
+    address_type: String,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, output_file: &Path, opts: &GeneratorOpts) -> Result<(), Error> {
+    map.require_contiguous_fields("cpp")?;
+
+    // Determine address type: Use option override, or smallest
+    // unsigned type that fits the largest address in the map.
+    let address_type = if let Some(address_type) = &opts.address_type {
+        address_type.clone()
+    } else {
+        let max_addr = map.registers.values().map(|x| x.adr).max().unwrap_or(0);
+        cpp_fitting_unsigned_type(msb_pos(max_addr.into()) + 1)?
+    };
+
+    let namespace = match &opts.namespace {
+        Some(namespace) => namespace.clone(),
+        None => cpp_snakecase(&map.name),
+    };
+
+    let inp = Input {
+        map,
+        namespace,
+        address_type,
+    };
+
+    let output_file_name = output_file
+        .file_name()
+        .ok_or(Error::GeneratorError("Failed to extract file name from output path!".to_string()))?
+        .to_string_lossy();
+
+    generate_header(out, &inp, opts, &output_file_name)?;
+
+    writeln!(out)?;
+    writeln!(out, "namespace {} {{", inp.namespace)?;
+
+    // ===== Traits: =====
+    generate_traits(out, &inp)?;
+
+    // ===== Enums: =====
+    writeln!(out)?;
+    cpp_generate_section_header_comment(out, "Enums")?;
+    for e in map.enums.values() {
+        enums::generate_enum(out, e)?;
+    }
+
+    // ===== Layouts: =====
+    writeln!(out)?;
+    cpp_generate_section_header_comment(out, "Layouts")?;
+    for layout in map.layouts_in_dependency_order() {
+        layouts::generate_layout(out, &inp, layout)?;
+    }
+
+    // ===== Registers: =====
+    writeln!(out)?;
+    cpp_generate_section_header_comment(out, "Registers")?;
+    let mut regs: Vec<_> = map.registers.values().collect();
+    regs.sort_by_key(|x| x.adr);
+    for register in regs {
+        registers::generate_register(out, &inp, register)?;
+    }
+
+    // ===== Register Blocks: =====
+    for block in map.register_blocks.values() {
+        registers::generate_register_block(out, &inp, block)?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "}} // namespace {}", inp.namespace)?;
+
+    generate_footer(out, opts, &output_file_name)?;
+
+    Ok(())
+}
+
+/// Generate header comment, include guard, includes.
+fn generate_header(
+    out: &mut dyn Write,
+    inp: &Input,
+    opts: &GeneratorOpts,
+    output_file_name: &str,
+) -> Result<(), Error> {
+    if opts.clang_format_guard {
+        writeln!(out, "// clang-format off")?;
+    }
+
+    writeln!(out, "/**")?;
+    writeln!(out, " * @file {output_file_name}")?;
+    writeln!(out, " * @brief {}", inp.map.name)?;
+    if let Some(input_file) = &inp.map.from_file {
+        writeln!(
+            out,
+            " * @note do not edit directly: generated using reginald from {}.",
+            input_file.to_string_lossy()
+        )?;
+    } else {
+        writeln!(out, " * @note do not edit directly: generated using reginald.",)?;
+    }
+    writeln!(out, " *")?;
+    writeln!(out, " * Generator: cpp")?;
+
+    if !inp.map.docs.is_empty() {
+        writeln!(out, " *")?;
+        write!(out, "{}", inp.map.docs.as_multiline(" * "))?;
+    }
+    if let Some(author) = &inp.map.author {
+        writeln!(out, " *")?;
+        writeln!(out, " * Listing file author: {author}")?;
+    }
+    if let Some(note) = &inp.map.notice {
+        writeln!(out, " *")?;
+        writeln!(out, " * Listing file notice:")?;
+        for line in note.lines() {
+            writeln!(out, " *   {line}")?;
+        }
+    }
+    writeln!(out, " */")?;
+
+    if opts.include_guards {
+        writeln!(out, "#ifndef REGINALD_{}", cpp_macro(output_file_name))?;
+        writeln!(out, "#define REGINALD_{}", cpp_macro(output_file_name))?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "#include <array>")?;
+    writeln!(out, "#include <cstddef>")?;
+    writeln!(out, "#include <cstdint>")?;
+    writeln!(out, "#include <optional>")?;
+    for include in &opts.add_include {
+        writeln!(out, "#include \"{include}\"")?;
+    }
+
+    Ok(())
+}
+
+/// Generate file footer
+fn generate_footer(out: &mut dyn Write, opts: &GeneratorOpts, output_file_name: &str) -> Result<(), Error> {
+    if opts.include_guards {
+        writeln!(out)?;
+        writeln!(out, "#endif /* REGINALD_{} */", cpp_macro(output_file_name))?;
+    }
+
+    if opts.clang_format_guard {
+        writeln!(out, "// clang-format on")?;
+    }
+
+    Ok(())
+}
+
+/// Generate utilities and register property templates
+fn generate_traits(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    let address_type = &inp.address_type;
+
+    writeln!(out)?;
+    cpp_generate_section_header_comment(out, "Utilities")?;
+    writeln!(out)?;
+    writeln!(out, "namespace detail {{")?;
+    writeln!(out, "/// Reverse byte order of a packed value.")?;
+    writeln!(out, "template <std::size_t N>")?;
+    writeln!(out, "constexpr std::array<uint8_t, N> reverse_bytes(const std::array<uint8_t, N> &val) {{")?;
+    writeln!(out, "  std::array<uint8_t, N> r{{}};")?;
+    writeln!(out, "  for (std::size_t i = 0; i < N; i++) {{ r[i] = val[N - i - 1]; }}")?;
+    writeln!(out, "  return r;")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "}} // namespace detail")?;
+
+    writeln!(out)?;
+    cpp_generate_section_header_comment(out, "Register Traits")?;
+    writeln!(out)?;
+    writeln!(out, "/// Register properties, specialised for every register type.")?;
+    writeln!(out, "template <typename R>")?;
+    writeln!(out, "struct RegisterTraits;")?;
+    writeln!(out)?;
+    writeln!(out, "/// Address of register `R`.")?;
+    writeln!(out, "template <typename R>")?;
+    writeln!(out, "[[nodiscard]] constexpr {address_type} address() {{")?;
+    writeln!(out, "  return RegisterTraits<R>::address;")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "/// Packed little-endian reset value of register `R`, if known.")?;
+    writeln!(out, "template <typename R>")?;
+    writeln!(out, "[[nodiscard]] constexpr std::optional<std::array<uint8_t, R::width_bytes>> reset_le() {{")?;
+    writeln!(out, "  return RegisterTraits<R>::reset_le;")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "/// Reset value of register `R`, if known.")?;
+    writeln!(out, "template <typename R>")?;
+    writeln!(out, "[[nodiscard]] constexpr std::optional<R> reset() {{")?;
+    writeln!(out, "  if (!RegisterTraits<R>::reset_le) {{ return std::nullopt; }}")?;
+    writeln!(out, "  return R::unpack_le(*RegisterTraits<R>::reset_le);")?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+// ====== Generator Utils ======================================================
+
+fn cpp_pascalcase(s: &str) -> String {
+    let mut result = String::new();
+    for part in cpp_sanitize(s).to_lowercase().split('_') {
+        let mut chars = part.chars();
+        if let Some(f) = chars.next() {
+            result.push_str(&(f.to_uppercase().collect::<String>() + chars.as_str()));
+        }
+    }
+    result
+}
+
+fn cpp_snakecase(s: &str) -> String {
+    cpp_sanitize(s).to_lowercase()
+}
+
+fn cpp_macro(s: &str) -> String {
+    cpp_sanitize(s).to_uppercase()
+}
+
+static CPP_SANITIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^_a-zA-Z0-9]").unwrap());
+
+fn cpp_sanitize(s: &str) -> String {
+    CPP_SANITIZE_REGEX.replace_all(s, "_").into()
+}
+
+fn cpp_fitting_unsigned_type(width: TypeBitwidth) -> Result<String, Error> {
+    match width {
+        1..=8 => Ok("uint8_t".to_string()),
+        9..=16 => Ok("uint16_t".to_string()),
+        17..=32 => Ok("uint32_t".to_string()),
+        33..=64 => Ok("uint64_t".to_string()),
+        _ => Err(Error::GeneratorError(format!("Cannot represent {width}-bit wide value as C++ type!"))),
+    }
+}
+
+fn cpp_fitting_signed_type(width: TypeBitwidth) -> Result<String, Error> {
+    match width {
+        1..=8 => Ok("int8_t".to_string()),
+        9..=16 => Ok("int16_t".to_string()),
+        17..=32 => Ok("int32_t".to_string()),
+        33..=64 => Ok("int64_t".to_string()),
+        _ => Err(Error::GeneratorError(format!("Cannot represent {width}-bit wide value as C++ type!"))),
+    }
+}
+
+/// Unsigned integer literal, suffixed so that it is never narrower than its value.
+fn cpp_uint_literal(val: TypeValue) -> String {
+    if val > TypeValue::from(u32::MAX) {
+        format!("0x{val:X}ULL")
+    } else {
+        format!("0x{val:X}U")
+    }
+}
+
+fn cpp_section_header_comment(title: &str) -> String {
+    str_pad_to_length(&format!("// ==== {title} "), '=', 80)
+}
+
+fn cpp_generate_section_header_comment(out: &mut dyn Write, title: &str) -> Result<(), Error> {
+    writeln!(out, "{}", cpp_section_header_comment(title))?;
+    Ok(())
+}
+
+fn cpp_generate_doc_comment(out: &mut dyn Write, docs: &Docs, prefix: &str, extra: &[&str]) -> Result<(), Error> {
+    let mut lines: Vec<String> = docs.as_multiline("").lines().map(String::from).collect();
+    if !lines.is_empty() && !extra.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(extra.iter().map(|x| x.to_string()));
+
+    for line in lines {
+        if line.is_empty() {
+            writeln!(out, "{prefix}///")?;
+        } else {
+            writeln!(out, "{prefix}/// {line}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Name of the struct representing a layout.
+fn cpp_layout_name(layout: &Layout) -> String {
+    cpp_pascalcase(&layout.name)
+}
@@ -0,0 +1,134 @@
+use std::fmt::Write;
+
+use crate::{
+    error::Error,
+    regmap::{Register, RegisterBlock},
+    utils::{Endianess, grab_byte},
+};
+
+use super::{Input, cpp_generate_doc_comment, cpp_layout_name, cpp_pascalcase, cpp_snakecase, cpp_uint_literal};
+
+/// Generate register type and property template specialisation.
+pub fn generate_register(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
+    // If the layout is local to this register, the layout struct is used as
+    // register type directly. Otherwise, a register type deriving from the
+    // layout struct is generated:
+    let reg_name = if register.layout.is_local && register.from_block.is_none() {
+        cpp_layout_name(&register.layout)
+    } else {
+        generate_register_struct(out, register)?;
+        cpp_pascalcase(&register.name)
+    };
+
+    let address_type = &inp.address_type;
+    let width_bytes = register.layout.width_bytes();
+    let array_type = format!("std::array<uint8_t, {width_bytes}>");
+
+    writeln!(out)?;
+    writeln!(out, "/// `{}` register properties.", register.name)?;
+    writeln!(out, "template <>")?;
+    writeln!(out, "struct RegisterTraits<{reg_name}> {{")?;
+    writeln!(out, "  static constexpr {address_type} address = {};", cpp_uint_literal(register.adr.into()))?;
+    if let Some(reset_val) = register.reset_val {
+        let bytes: Vec<String> = (0..width_bytes)
+            .map(|x| format!("0x{:X}U", grab_byte(Endianess::Little, reset_val, x, width_bytes)))
+            .collect();
+        writeln!(
+            out,
+            "  static constexpr std::optional<{array_type}> reset_le = {array_type}{{{}}};",
+            bytes.join(", ")
+        )?;
+    } else {
+        writeln!(out, "  static constexpr std::optional<{array_type}> reset_le = std::nullopt;")?;
+    }
+    writeln!(out, "}};")?;
+
+    Ok(())
+}
+
+/// Generate register type that derives from a non-local or block member layout.
+fn generate_register_struct(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    let reg_name = cpp_pascalcase(&register.name);
+    let layout_name = cpp_layout_name(&register.layout);
+    let array_type = format!("std::array<uint8_t, {}>", register.layout.width_bytes());
+
+    // Struct doc comment:
+    writeln!(out)?;
+    writeln!(out, "/// `{}` Register", register.name)?;
+    if !register.docs.is_empty() {
+        writeln!(out, "///")?;
+        cpp_generate_doc_comment(out, &register.docs, "", &[])?;
+    }
+    writeln!(out, "///")?;
+    writeln!(out, "/// Address: 0x{:X}", register.adr)?;
+    if let Some(reset_val) = register.reset_val {
+        writeln!(out, "///")?;
+        writeln!(out, "/// Reset Value: 0x{reset_val:X}")?;
+    }
+    writeln!(out, "///")?;
+    writeln!(out, "/// Uses `{layout_name}` layout.")?;
+    writeln!(out, "struct {reg_name} : {layout_name} {{")?;
+    writeln!(out, "  constexpr {reg_name}() = default;")?;
+    writeln!(out, "  constexpr explicit {reg_name}(const {layout_name} &layout) : {layout_name}(layout) {{}}")?;
+    for endian in [Endianess::Little, Endianess::Big] {
+        let short = endian.short();
+        writeln!(out)?;
+        writeln!(out, "  /// Convert from packed {endian} binary value.")?;
+        writeln!(out, "  [[nodiscard]] static constexpr {reg_name} unpack_{short}(const {array_type} &val) {{")?;
+        writeln!(out, "    return {reg_name}({layout_name}::unpack_{short}(val));")?;
+        writeln!(out, "  }}")?;
+    }
+    for endian in [Endianess::Little, Endianess::Big] {
+        let short = endian.short();
+        writeln!(out)?;
+        writeln!(out, "  /// Attempt to convert from packed {endian} binary value.")?;
+        writeln!(
+            out,
+            "  [[nodiscard]] static constexpr std::optional<{reg_name}> try_unpack_{short}(const {array_type} &val) {{"
+        )?;
+        writeln!(out, "    const std::optional<{layout_name}> r = {layout_name}::try_unpack_{short}(val);")?;
+        writeln!(out, "    if (!r) {{ return std::nullopt; }}")?;
+        writeln!(out, "    return {reg_name}(*r);")?;
+        writeln!(out, "  }}")?;
+    }
+    writeln!(out, "}};")?;
+
+    Ok(())
+}
+
+/// Generate register block offset and instance address constants.
+pub fn generate_register_block(out: &mut dyn Write, inp: &Input, block: &RegisterBlock) -> Result<(), Error> {
+    let address_type = &inp.address_type;
+
+    let mut members: Vec<_> = block.members.values().collect();
+    members.sort_by_key(|x| x.offset);
+
+    writeln!(out)?;
+    writeln!(out, "/// `{}` Register Block", block.name)?;
+    if !block.docs.is_empty() {
+        writeln!(out, "///")?;
+        cpp_generate_doc_comment(out, &block.docs, "", &[])?;
+    }
+    writeln!(out, "namespace {} {{", cpp_snakecase(&block.name))?;
+    for instance in block.instances.values() {
+        writeln!(
+            out,
+            "inline constexpr {address_type} {}_address = {}; ///< Start of `{}` instance.",
+            cpp_snakecase(&instance.name),
+            cpp_uint_literal(instance.adr.into()),
+            instance.name
+        )?;
+    }
+    for member in &members {
+        writeln!(
+            out,
+            "inline constexpr {address_type} {}_offset = {}; ///< Offset of `{}` register.",
+            cpp_snakecase(&member.name_raw),
+            cpp_uint_literal(member.offset.into()),
+            member.name
+        )?;
+    }
+    writeln!(out, "}} // namespace {}", cpp_snakecase(&block.name))?;
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use std::fmt::Write;
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+use reginald_utils::RangeStyle;
+
+use crate::{
+    error::Error,
+    import::csv::CsvColumns,
+    regmap::{Docs, FieldType, Register, RegisterMap, access_str},
+};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Field delimiter
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().delimiter))]
+    pub delimiter: char,
+
+    #[cfg_attr(feature = "clap", command(flatten))]
+    pub columns: CsvColumns,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            columns: CsvColumns::default(),
+        }
+    }
+}
+
+// ====== Generator ============================================================
+
+/// Generate a CSV table with one row per register, field and enum entry.
+///
+/// The table uses the same column layout that is understood by the CSV
+/// importer. Fields of nested layouts are flattened, with their names joined
+/// by underscores. Bit ranges are always numbered LSB-0, regardless of the bit
+/// order of the layout.
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    if !opts.delimiter.is_ascii() {
+        return Err(Error::GeneratorError(format!("Delimiter '{}' is not an ASCII character", opts.delimiter)));
+    }
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(opts.delimiter as u8)
+        .from_writer(vec![]);
+
+    writer.write_record(opts.columns.headers()).map_err(csv_error)?;
+
+    let mut registers: Vec<&Register> = map.registers.values().map(|x| x.as_ref()).collect();
+    registers.sort_by_key(|x| x.adr);
+
+    for register in registers {
+        for row in register_rows(register) {
+            writer.write_record(&row).map_err(csv_error)?;
+        }
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| Error::GeneratorError(err.to_string()))?;
+    let table = String::from_utf8(bytes).map_err(|err| Error::GeneratorError(err.to_string()))?;
+    write!(out, "{table}")?;
+    Ok(())
+}
+
+/// Rows describing a register, in the canonical column order of [`CsvColumns::headers`].
+fn register_rows(register: &Register) -> Vec<[String; 10]> {
+    let adr = format!("0x{:X}", register.adr);
+    let mut rows = vec![[
+        register.name.clone(),
+        adr.clone(),
+        register.layout.bitwidth.to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        register.reset_val.map(|x| format!("0x{x:X}")).unwrap_or_default(),
+        String::new(),
+        String::new(),
+        docs_text(&register.docs),
+    ]];
+
+    for field in register.layout.flattened_fields() {
+        let name = field.name.join("_");
+        let reset = register
+            .reset_val
+            .map(|x| format!("0x{:X}", field.bits.extract(x)))
+            .unwrap_or_default();
+        rows.push([
+            register.name.clone(),
+            adr.clone(),
+            String::new(),
+            name.clone(),
+            field.bits.to_string(RangeStyle::Verilog),
+            field.field.access.as_ref().map(access_str).unwrap_or_default(),
+            reset,
+            String::new(),
+            String::new(),
+            docs_text(&field.field.docs),
+        ]);
+
+        if let FieldType::Enum(e) = &field.field.accepts {
+            let mut entries: Vec<_> = e.entries.values().collect();
+            entries.sort_by_key(|x| x.value);
+            for entry in entries {
+                rows.push([
+                    register.name.clone(),
+                    adr.clone(),
+                    String::new(),
+                    name.clone(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    format!("0x{:X}", entry.value),
+                    entry.name.clone(),
+                    docs_text(&entry.docs),
+                ]);
+            }
+        }
+    }
+
+    rows
+}
+
+fn docs_text(docs: &Docs) -> String {
+    docs.as_multiline("").trim_end().to_string()
+}
+
+fn csv_error(err: ::csv::Error) -> Error {
+    Error::GeneratorError(format!("Failed to write CSV: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::csv::{ImportOpts, import};
+    use pretty_assertions::assert_eq;
+
+    const MAP: &str = r#"
+        name: test
+        registers:
+          CTRL: !Register
+            adr: 0x10
+            bitwidth: 8
+            reset_val: 0x8D
+            doc: Control register
+            layout: !Layout
+              EN:
+                bits: 0
+                access: [R, W]
+                doc: Enable
+              MODE:
+                bits: "2-1"
+                accepts: !Enum
+                  SLOW: { val: 0 }
+                  FAST: { val: 2, doc: "Fast, but loud" }
+              SEL:
+                bits: [7, "4-3"]
+          DATA: !Register
+            adr: 0x12
+            bitwidth: 16
+            layout: !Layout
+              VAL:
+                bits: "7-0"
+    "#;
+
+    #[test]
+    fn test_generate() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let mut out = String::new();
+        generate(&mut out, &map, &GeneratorOpts::default()).unwrap();
+
+        let should = "\
+Register,Address,Bitwidth,Field,Bits,Access,Reset,Enum Value,Enum Name,Description
+CTRL,0x10,8,,,,0x8D,,,Control register
+CTRL,0x10,,EN,0,R/W,0x1,,,Enable
+CTRL,0x10,,MODE,2:1,,0x2,,,
+CTRL,0x10,,MODE,,,,0x0,SLOW,
+CTRL,0x10,,MODE,,,,0x2,FAST,\"Fast, but loud\"
+CTRL,0x10,,SEL,\"7, 4:3\",,0x5,,,
+DATA,0x12,16,,,,,,,
+DATA,0x12,,VAL,7:0,,,,,
+";
+        assert_eq!(out, should);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let mut out = String::new();
+        generate(&mut out, &map, &GeneratorOpts::default()).unwrap();
+
+        let opts = ImportOpts {
+            name: Some("test".to_string()),
+            ..Default::default()
+        };
+        let imported = import(&out, &opts).unwrap();
+        assert!(imported.warnings.is_empty());
+        let reimported = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+
+        let (a, b) = (&map.registers["CTRL"], &reimported.registers["CTRL"]);
+        assert_eq!(a.adr, b.adr);
+        assert_eq!(a.reset_val, b.reset_val);
+        assert_eq!(a.docs, b.docs);
+        assert_eq!(a.layout.fields["EN"], b.layout.fields["EN"]);
+        assert_eq!(a.layout.fields["MODE"].bits, b.layout.fields["MODE"].bits);
+        assert_eq!(a.layout.fields["SEL"].bits, b.layout.fields["SEL"].bits);
+
+        // Registers wider than their fields keep their bitwidth:
+        let (a, b) = (&map.registers["DATA"], &reimported.registers["DATA"]);
+        assert_eq!(b.layout.bitwidth, 16);
+        assert_eq!(a.layout.fields["VAL"].bits, b.layout.fields["VAL"].bits);
+    }
+
+    #[test]
+    fn test_roundtrip_msb0() {
+        let map = RegisterMap::from_yaml_str(
+            r#"
+            name: test
+            registers:
+              CTRL: !Register
+                adr: 0x10
+                bitwidth: 8
+                bit_order: Msb0
+                reset_val: 0x81
+                layout: !Layout
+                  EN:
+                    bits: 0
+                  MODE:
+                    bits: "1-2"
+                  SEL:
+                    bits: [3, 7]
+            "#,
+        )
+        .unwrap();
+        let mut out = String::new();
+        generate(&mut out, &map, &GeneratorOpts::default()).unwrap();
+        assert!(out.contains("CTRL,0x10,,EN,7,"));
+
+        let opts = ImportOpts {
+            name: Some("test".to_string()),
+            ..Default::default()
+        };
+        let imported = import(&out, &opts).unwrap();
+        assert!(imported.warnings.is_empty());
+        let reimported = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+
+        let (a, b) = (&map.registers["CTRL"], &reimported.registers["CTRL"]);
+        assert_eq!(a.reset_val, b.reset_val);
+        for field in ["EN", "MODE", "SEL"] {
+            assert_eq!(a.layout.fields[field].bits, b.layout.fields[field].bits);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+#[cfg(feature = "clap")]
+use clap::{Parser, ValueEnum};
+use serde_json::{Map, Value};
+
+use crate::{error::Error, regmap::RegisterMap};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Diagram output format.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(value_enum, default_value_t = Self::default().format))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub format: DiagramFormat,
+
+    /// Only generate the diagram of the given register.
+    ///
+    /// Required for SVG output. If not given for WaveDrom output, a JSON
+    /// object containing the diagrams of all registers is generated.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub register: Option<String>,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            format: DiagramFormat::Wavedrom,
+            register: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum DiagramFormat {
+    /// WaveDrom `reg` JSON.
+    Wavedrom,
+    /// Standalone SVG image.
+    Svg,
+}
+
+// ====== Generator ============================================================
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    let register = match &opts.register {
+        Some(name) => Some(
+            map.registers
+                .get(name)
+                .ok_or(Error::GeneratorError(format!("Register '{name}' does not exist")))?,
+        ),
+        None => None,
+    };
+
+    match (opts.format, register) {
+        (DiagramFormat::Wavedrom, Some(register)) => {
+            writeln!(out, "{}", to_json(&register.layout.wavedrom_reg())?)?;
+        }
+        (DiagramFormat::Wavedrom, None) => {
+            let mut diagrams = Map::new();
+            for register in map.registers.values() {
+                diagrams.insert(register.name.clone(), register.layout.wavedrom_reg());
+            }
+            writeln!(out, "{}", to_json(&Value::Object(diagrams))?)?;
+        }
+        (DiagramFormat::Svg, Some(register)) => {
+            write!(out, "{}", register.layout.bitfield_svg())?;
+        }
+        (DiagramFormat::Svg, None) => {
+            return Err(Error::GeneratorError(
+                "SVG diagrams can only be generated for a single register (see --register)".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn to_json(value: &Value) -> Result<String, Error> {
+    serde_json::to_string_pretty(value)
+        .map_err(|err| Error::GeneratorError(format!("Failed to serialize diagram: {err}")))
+}
@@ -0,0 +1,466 @@
+use std::fmt::Write;
+
+use reginald_utils::RangeStyle;
+
+use crate::{
+    error::Error,
+    regmap::{
+        Enum, FieldType, Layout, Register, RegisterMap, ScalingConversion, TypeBitwidth, access_str, constraints_str,
+        diagram::DiagramSegmentKind, scaling_str,
+    },
+};
+
+use super::{html_docs, html_escape, html_id};
+
+/// Maximum number of bits shown in a single row of a bit diagram.
+const BITS_PER_ROW: TypeBitwidth = 16;
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 0; display: flex; }
+nav { position: sticky; top: 0; height: 100vh; overflow-y: auto; min-width: 22em; padding: 1em; box-sizing: border-box; border-right: 1px solid #ccc; background: #f8f8f8; }
+nav input { width: 100%; box-sizing: border-box; margin-bottom: 1em; padding: 0.3em; }
+main { padding: 1em 2em; flex: 1; min-width: 0; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #bbb; padding: 0.2em 0.5em; }
+table.bits td { text-align: center; font-family: monospace; }
+table.bits th { font-weight: normal; font-size: 0.8em; color: #555; min-width: 1.8em; }
+td.reserved { background: #e4e4e4; color: #888; }
+td.fixed { background: #fff2cc; }
+section { border-top: 1px solid #ddd; padding-top: 0.5em; }
+details { margin: 0.3em 0; }
+summary { cursor: pointer; }
+p.brief { font-weight: bold; }
+"#;
+
+const SCRIPT: &str = r#"
+document.getElementById("search").addEventListener("input", (e) => {
+  const query = e.target.value.trim().toLowerCase();
+  for (const el of document.querySelectorAll("[data-search]")) {
+    el.hidden = query !== "" && !el.dataset.search.includes(query);
+  }
+});
+"#;
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap) -> Result<(), Error> {
+    let mut registers = map.registers.values().map(|r| r.as_ref()).collect::<Vec<_>>();
+    registers.sort_by_key(|r| r.adr);
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", html_escape(&map.name))?;
+    writeln!(out, "<style>{STYLE}</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+
+    generate_index(out, &registers)?;
+
+    writeln!(out, "<main>")?;
+    generate_overview(out, map)?;
+
+    writeln!(out)?;
+    writeln!(out, "<h2>Registers</h2>")?;
+    for register in &registers {
+        generate_register(out, register)?;
+    }
+
+    let shared_enums: Vec<_> = map.shared_enums().collect();
+    if !shared_enums.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "<h2>Enums</h2>")?;
+        for e in shared_enums {
+            generate_shared_enum(out, map, e)?;
+        }
+    }
+
+    let shared_layouts: Vec<_> = map.shared_layouts().collect();
+    if !shared_layouts.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "<h2>Layouts</h2>")?;
+        for layout in shared_layouts {
+            generate_shared_layout(out, map, layout)?;
+        }
+    }
+
+    writeln!(out, "</main>")?;
+    writeln!(out, "<script>{SCRIPT}</script>")?;
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+
+    Ok(())
+}
+
+/// Generate searchable register index.
+fn generate_index(out: &mut dyn Write, registers: &[&Register]) -> Result<(), Error> {
+    writeln!(out, "<nav>")?;
+    writeln!(out, "<input id=\"search\" type=\"search\" placeholder=\"Search registers...\">")?;
+    writeln!(out, "<table>")?;
+    writeln!(out, "<tr><th>Address</th><th>Register</th><th>Reset</th></tr>")?;
+    for register in registers {
+        writeln!(
+            out,
+            "<tr data-search=\"{}\"><td>0x{:02X}</td><td><a href=\"#{}\">{}</a></td><td>{}</td></tr>",
+            search_key(register),
+            register.adr,
+            html_id("reg", &register.name),
+            html_escape(&register.name),
+            register.reset_val.map_or("-".to_string(), |x| format!("0x{x:02X}")),
+        )?;
+    }
+    writeln!(out, "</table>")?;
+    writeln!(out, "</nav>")?;
+    Ok(())
+}
+
+fn generate_overview(out: &mut dyn Write, map: &RegisterMap) -> Result<(), Error> {
+    writeln!(out, "<h1>{}</h1>", html_escape(&map.name))?;
+    html_docs(out, &map.docs, "")?;
+    if let Some(input_file) = &map.from_file {
+        writeln!(out, "<p>Generated from listing file: {}.</p>", html_escape(&input_file.to_string_lossy()))?;
+    }
+    if let Some(author) = &map.author {
+        writeln!(out, "<p>Listing file author: {}.</p>", html_escape(author))?;
+    }
+    if let Some(notice) = &map.notice {
+        writeln!(out, "<p>Listing file notice:</p>")?;
+        writeln!(out, "<pre>{}</pre>", html_escape(notice.trim_end()))?;
+    }
+    Ok(())
+}
+
+fn generate_register(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    let id = html_id("reg", &register.name);
+
+    writeln!(out)?;
+    writeln!(out, "<section id=\"{id}\" data-search=\"{}\">", search_key(register))?;
+    writeln!(out, "<h3>{}</h3>", html_escape(&register.name))?;
+    html_docs(out, &register.docs, "")?;
+
+    writeln!(out, "<ul>")?;
+    writeln!(out, "<li>Address: 0x{:02X}</li>", register.adr)?;
+    if let Some(reset_val) = register.reset_val {
+        writeln!(out, "<li>Reset: 0x{reset_val:02X}</li>")?;
+    }
+    writeln!(out, "<li>Width: {} bits</li>", register.layout.bitwidth)?;
+    if !register.layout.is_local {
+        writeln!(out, "<li>Layout: {}</li>", layout_link(&register.layout))?;
+    }
+    if let Some(from_block) = &register.from_block {
+        writeln!(
+            out,
+            "<li>In '{}' instance of '{}' block</li>",
+            html_escape(&from_block.instance),
+            html_escape(&from_block.block)
+        )?;
+    }
+    writeln!(out, "</ul>")?;
+
+    generate_bit_diagram(out, &register.layout)?;
+    generate_fields(out, &register.layout)?;
+
+    writeln!(out, "</section>")?;
+    Ok(())
+}
+
+fn generate_shared_enum(out: &mut dyn Write, map: &RegisterMap, e: &Enum) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "<section id=\"{}\">", html_id("enum", &e.name))?;
+    writeln!(out, "<h3>{}</h3>", html_escape(&e.name))?;
+    html_docs(out, &e.docs, "")?;
+    writeln!(out, "<ul>")?;
+    writeln!(out, "<li>Width: {} bits</li>", e.bitwidth)?;
+    writeln!(out, "</ul>")?;
+    generate_enum_table(out, e)?;
+
+    let users: Vec<_> = map
+        .registers
+        .values()
+        .filter(|r| {
+            r.layout
+                .nested_fields()
+                .iter()
+                .any(|x| matches!(&x.field.accepts, FieldType::Enum(used) if used.name == e.name))
+        })
+        .map(|r| r.as_ref())
+        .collect();
+    generate_used_by(out, &users)?;
+
+    writeln!(out, "</section>")?;
+    Ok(())
+}
+
+fn generate_shared_layout(out: &mut dyn Write, map: &RegisterMap, layout: &Layout) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "<section id=\"{}\">", html_id("layout", &layout.name))?;
+    writeln!(out, "<h3>{}</h3>", html_escape(&layout.name))?;
+    html_docs(out, &layout.docs, "")?;
+    writeln!(out, "<ul>")?;
+    writeln!(out, "<li>Width: {} bits</li>", layout.bitwidth)?;
+    writeln!(out, "</ul>")?;
+    generate_bit_diagram(out, layout)?;
+    generate_fields(out, layout)?;
+
+    let users: Vec<_> = map
+        .registers
+        .values()
+        .filter(|r| r.layout.name == layout.name)
+        .map(|r| r.as_ref())
+        .collect();
+    generate_used_by(out, &users)?;
+
+    writeln!(out, "</section>")?;
+    Ok(())
+}
+
+fn generate_used_by(out: &mut dyn Write, registers: &[&Register]) -> Result<(), Error> {
+    if registers.is_empty() {
+        return Ok(());
+    }
+    let links: Vec<String> = registers
+        .iter()
+        .map(|r| format!("<a href=\"#{}\">{}</a>", html_id("reg", &r.name), html_escape(&r.name)))
+        .collect();
+    writeln!(out, "<p>Used by: {}</p>", links.join(", "))?;
+    Ok(())
+}
+
+/// Generate bit diagram table, split into rows of at most [`BITS_PER_ROW`] bits.
+fn generate_bit_diagram(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    writeln!(out, "<table class=\"bits\">")?;
+    let mut row_lsb = (layout.bitwidth.max(1) - 1) / BITS_PER_ROW * BITS_PER_ROW;
+    loop {
+        let row_msb = TypeBitwidth::min(row_lsb + BITS_PER_ROW, layout.bitwidth.max(1)) - 1;
+
+        write!(out, "<tr>")?;
+        for bit in (row_lsb..=row_msb).rev() {
+            write!(out, "<th>{}</th>", layout.bit_number(bit))?;
+        }
+        writeln!(out, "</tr>")?;
+
+        write!(out, "<tr>")?;
+        for cell in bit_diagram_cells(layout, row_lsb, row_msb) {
+            write!(
+                out,
+                "<td colspan=\"{}\" class=\"{}\" title=\"{}\">{}</td>",
+                cell.width,
+                cell.class,
+                html_escape(&cell.title),
+                html_escape(&cell.label)
+            )?;
+        }
+        writeln!(out, "</tr>")?;
+
+        if row_lsb == 0 {
+            break;
+        }
+        row_lsb -= BITS_PER_ROW;
+    }
+    writeln!(out, "</table>")?;
+    Ok(())
+}
+
+/// Single cell of a bit diagram row.
+#[derive(Debug, PartialEq, Eq)]
+struct BitDiagramCell {
+    width: TypeBitwidth,
+    class: &'static str,
+    label: String,
+    title: String,
+}
+
+/// Split bits `lsb..=msb` of a layout into diagram cells, from MSB to LSB.
+fn bit_diagram_cells(layout: &Layout, lsb: TypeBitwidth, msb: TypeBitwidth) -> Vec<BitDiagramCell> {
+    let mut cells = vec![];
+    for segment in layout.diagram_segments().into_iter().rev() {
+        let cell_lsb = segment.bits.lsb_pos().max(lsb);
+        let cell_msb = segment.bits.msb_pos().min(msb);
+        if cell_lsb > cell_msb {
+            continue;
+        }
+        let width = cell_msb - cell_lsb + 1;
+
+        let name = segment.name.unwrap_or_default();
+        let title = format!("[{}] {name}", layout.range_to_string(&segment.bits, RangeStyle::Verilog));
+        cells.push(match segment.kind {
+            DiagramSegmentKind::Field => BitDiagramCell {
+                width,
+                class: "field",
+                label: name,
+                title,
+            },
+            DiagramSegmentKind::Fixed(fixed) => BitDiagramCell {
+                width,
+                class: "fixed",
+                label: format!("{name} = 0x{fixed:X}"),
+                title,
+            },
+            DiagramSegmentKind::Reserved => BitDiagramCell {
+                width,
+                class: "reserved",
+                label: "-".to_string(),
+                title: "Reserved".to_string(),
+            },
+        });
+    }
+    cells
+}
+
+/// Generate collapsible field descriptions.
+fn generate_fields(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let fields = layout.nested_fields();
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "<h4>Fields</h4>")?;
+    for field in fields {
+        let indent = field.name.len() - 1;
+        let bits = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
+        let name = field.name.join(".");
+
+        let type_str = match &field.field.accepts {
+            FieldType::UInt => "uint".to_string(),
+            FieldType::SInt => "sint".to_string(),
+            FieldType::Bool => "bool".to_string(),
+            FieldType::Fixed(fixed) => format!("fixed 0x{fixed:X}"),
+            FieldType::Enum(e) if !e.is_local => format!("enum {}", enum_link(e)),
+            FieldType::Enum(e) => format!("enum {}", html_escape(&e.name)),
+            FieldType::Layout(l) if !l.is_local => format!("layout {}", layout_link(l)),
+            FieldType::Layout(l) => format!("layout {}", html_escape(&l.name)),
+        };
+
+        let style = if indent > 0 {
+            format!(" style=\"margin-left: {}em\"", indent * 2)
+        } else {
+            String::new()
+        };
+
+        writeln!(out, "<details{style}>")?;
+        write!(out, "<summary><code>[{bits}]</code> <b>{}</b>", html_escape(&name))?;
+        if let Some(brief) = &field.field.docs.brief {
+            write!(out, ": {}", html_escape(brief))?;
+        }
+        writeln!(out, "</summary>")?;
+        html_docs(out, &field.field.docs, "")?;
+        writeln!(out, "<ul>")?;
+        if let Some(access) = &field.field.access {
+            writeln!(out, "<li>Access: {}</li>", access_str(access))?;
+        }
+        writeln!(out, "<li>Type: {type_str}</li>")?;
+        if let Some(constraints) = &field.field.constraints {
+            writeln!(out, "<li>Valid values: {}</li>", constraints_str(constraints))?;
+        }
+        if let Some(scaling) = &field.field.scaling {
+            writeln!(out, "<li>Unit: {} ({})</li>", html_escape(&scaling.unit), scaling_str(scaling))?;
+        }
+        writeln!(out, "</ul>")?;
+        if let FieldType::Enum(e) = &field.field.accepts
+            && e.is_local
+        {
+            generate_enum_table(out, e)?;
+        }
+        if let Some(scaling) = &field.field.scaling
+            && let ScalingConversion::Table(table) = &scaling.conversion
+        {
+            generate_scaling_table(out, &scaling.unit, table)?;
+        }
+        writeln!(out, "</details>")?;
+    }
+    Ok(())
+}
+
+fn generate_scaling_table(out: &mut dyn Write, unit: &str, table: &[i64]) -> Result<(), Error> {
+    writeln!(out, "<table>")?;
+    writeln!(out, "<tr><th>Value</th><th>Physical</th></tr>")?;
+    for (idx, physical) in table.iter().enumerate() {
+        writeln!(out, "<tr><td>0x{idx:X}</td><td>{physical} {}</td></tr>", html_escape(unit))?;
+    }
+    writeln!(out, "</table>")?;
+    Ok(())
+}
+
+fn generate_enum_table(out: &mut dyn Write, e: &Enum) -> Result<(), Error> {
+    let mut entries: Vec<_> = e.entries.values().collect();
+    entries.sort_by_key(|x| x.value);
+
+    writeln!(out, "<table>")?;
+    writeln!(out, "<tr><th>Value</th><th>Name</th><th>Description</th></tr>")?;
+    for entry in entries {
+        let mut docs = String::new();
+        html_docs(&mut docs, &entry.docs, "")?;
+        writeln!(
+            out,
+            "<tr><td>0x{:X}</td><td>{}</td><td>{}</td></tr>",
+            entry.value,
+            html_escape(&entry.name),
+            docs.trim_end().replace('\n', "")
+        )?;
+    }
+    writeln!(out, "</table>")?;
+    Ok(())
+}
+
+fn enum_link(e: &Enum) -> String {
+    format!("<a href=\"#{}\">{}</a>", html_id("enum", &e.name), html_escape(&e.name))
+}
+
+fn layout_link(layout: &Layout) -> String {
+    format!("<a href=\"#{}\">{}</a>", html_id("layout", &layout.name), html_escape(&layout.name))
+}
+
+fn search_key(register: &Register) -> String {
+    let mut key = register.name.to_lowercase();
+    if let Some(brief) = &register.docs.brief {
+        key.push(' ');
+        key.push_str(&brief.to_lowercase());
+    }
+    html_escape(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const MAP: &str = r#"
+        name: test
+        registers:
+          REG: !Register
+            adr: 0x00
+            bitwidth: 8
+            layout: !Layout
+              A:
+                bits: "1-0"
+              B:
+                bits: "5-4"
+                accepts: !Fixed 0x2
+              C:
+                bits: 7
+                accepts: !Layout
+                  D:
+                    bits: 0
+    "#;
+
+    #[test]
+    fn test_bit_diagram_cells() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let layout = &map.registers["REG"].layout;
+
+        let cells = bit_diagram_cells(layout, 0, 7);
+        let summary: Vec<_> = cells.iter().map(|x| (x.width, x.class, x.label.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "field", "C.D"),
+                (1, "reserved", "-"),
+                (2, "fixed", "B = 0x2"),
+                (2, "reserved", "-"),
+                (2, "field", "A"),
+            ]
+        );
+
+        let cells = bit_diagram_cells(layout, 0, 3);
+        assert_eq!(cells[1].title, "[1:0] A");
+    }
+}
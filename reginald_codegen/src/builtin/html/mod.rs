@@ -0,0 +1,76 @@
+use std::{fmt::Write, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{error::Error, regmap::Docs};
+
+pub mod datasheet;
+
+/// Escape text for use in HTML content and attribute values.
+pub fn html_escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+static HTML_ID_SANITIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^_a-zA-Z0-9-]").unwrap());
+
+/// Element id of a named item, used as link target.
+pub fn html_id(kind: &str, name: &str) -> String {
+    format!("{kind}-{}", HTML_ID_SANITIZE_REGEX.replace_all(name, "_").to_lowercase())
+}
+
+/// Documentation as paragraphs, with the brief emphasised.
+pub fn html_docs(out: &mut dyn Write, docs: &Docs, line_prefix: &str) -> Result<(), Error> {
+    if let Some(brief) = &docs.brief {
+        writeln!(out, "{line_prefix}<p class=\"brief\">{}</p>", html_escape(brief))?;
+    }
+    if let Some(doc) = &docs.doc {
+        for paragraph in doc.split("\n\n").map(str::trim).filter(|x| !x.is_empty()) {
+            let lines: Vec<String> = paragraph.lines().map(html_escape).collect();
+            writeln!(out, "{line_prefix}<p>{}</p>", lines.join("<br>"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_html_escape() {
+        assert_eq!(html_escape("a < b && c > \"d\""), "a &lt; b &amp;&amp; c &gt; &quot;d&quot;");
+        assert_eq!(html_escape("it's"), "it&#39;s");
+    }
+
+    #[test]
+    fn test_html_id() {
+        assert_eq!(html_id("reg", "REG_A"), "reg-reg_a");
+        assert_eq!(html_id("enum", "My Enum.X"), "enum-my_enum_x");
+    }
+
+    #[test]
+    fn test_html_docs() {
+        let docs = Docs {
+            brief: Some("Brief <b>".to_string()),
+            doc: Some("Line 1\nLine 2\n\nParagraph 2".to_string()),
+        };
+        let mut out = String::new();
+        html_docs(&mut out, &docs, "~").unwrap();
+        let should = "~<p class=\"brief\">Brief &lt;b&gt;</p>\n\
+                      ~<p>Line 1<br>Line 2</p>\n\
+                      ~<p>Paragraph 2</p>\n";
+        assert_eq!(out, should);
+    }
+}
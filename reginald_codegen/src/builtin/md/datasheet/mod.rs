@@ -1,21 +1,62 @@
 pub mod regdump;
 
-use std::fmt::Write;
+use std::{fmt::Write, path::PathBuf};
 
+#[cfg(feature = "clap")]
+use clap::Parser;
 use reginald_utils::RangeStyle;
 
 use crate::{
     bits::bitmask_from_range,
+    builtin::template,
     error::Error,
     regmap::{
-        DecodedField, FieldType, FlattenedLayoutField, Layout, LayoutField, Register, RegisterMap, TypeValue,
-        access_str,
+        DecodedField, Enum, FieldType, FlattenedLayoutField, Layout, LayoutField, Register, RegisterBlock, RegisterMap,
+        Scaling, ScalingConversion, TypeValue, access_str, constraints_str, scaling_str,
     },
 };
 
-use super::md_table;
+use super::{md_anchor, md_table};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Embed a WaveDrom bitfield diagram of every register.
+    ///
+    /// Diagrams are emitted as `wavedrom` code blocks, which must be
+    /// rendered by the markdown viewer (for example mdbook-wavedrom).
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().wavedrom))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub wavedrom: bool,
+
+    /// Render the datasheet using the given template instead.
+    ///
+    /// See the `template` generator for details.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub template: Option<PathBuf>,
+}
+
+// ====== Generator ============================================================
+
+/// Controls how register details are rendered.
+#[derive(Debug, Clone, Copy, Default)]
+struct RegisterInfoOpts {
+    /// Embed a WaveDrom diagram of the register.
+    wavedrom: bool,
+    /// Link register blocks, shared enums and shared layouts to their sections.
+    link_sections: bool,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    if let Some(template) = &opts.template {
+        return template::render_file(out, map, template);
+    }
 
-pub fn generate(out: &mut dyn Write, map: &RegisterMap) -> Result<(), Error> {
     writeln!(out, "# {}", map.name)?;
     writeln!(out)?;
     writeln!(out, "## Register Map")?;
@@ -27,8 +68,38 @@ pub fn generate(out: &mut dyn Write, map: &RegisterMap) -> Result<(), Error> {
     let mut registers = map.registers.values().collect::<Vec<_>>();
     registers.sort_by_key(|r| r.adr);
 
+    let info_opts = RegisterInfoOpts {
+        wavedrom: opts.wavedrom,
+        link_sections: true,
+    };
     for register in registers {
-        generate_register_infos(out, map, register, None)?;
+        generate_register_infos(out, map, register, None, info_opts)?;
+    }
+
+    if !map.register_blocks.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Register Blocks")?;
+        for block in map.register_blocks.values() {
+            generate_block_infos(out, block)?;
+        }
+    }
+
+    let shared_enums: Vec<_> = map.shared_enums().collect();
+    if !shared_enums.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Enums")?;
+        for e in shared_enums {
+            generate_enum_infos(out, map, e)?;
+        }
+    }
+
+    let shared_layouts: Vec<_> = map.shared_layouts().collect();
+    if !shared_layouts.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Layouts")?;
+        for layout in shared_layouts {
+            generate_layout_infos(out, map, layout)?;
+        }
     }
 
     Ok(())
@@ -81,6 +152,7 @@ fn generate_register_infos(
     map: &RegisterMap,
     register: &Register,
     value: Option<TypeValue>,
+    opts: RegisterInfoOpts,
 ) -> Result<(), Error> {
     // Header:
     writeln!(out)?;
@@ -96,6 +168,9 @@ fn generate_register_infos(
     if let Some(reset_val) = register.reset_val {
         writeln!(out, "  - Reset: 0x{reset_val:02X}")?;
     }
+    if opts.link_sections && !register.layout.is_local {
+        writeln!(out, "  - Layout: {}", layout_link(&register.layout))?;
+    }
 
     writeln!(out)?;
     writeln!(out, "#### Register:")?;
@@ -103,11 +178,23 @@ fn generate_register_infos(
 
     generate_layout_table(out, &register.layout, value)?;
 
+    if opts.wavedrom {
+        writeln!(out)?;
+        writeln!(out, "```wavedrom")?;
+        writeln!(out, "{}", register.layout.wavedrom_reg())?;
+        writeln!(out, "```")?;
+    }
+
     writeln!(out)?;
     write!(out, "{}", register.docs.as_twoline("  - "))?;
 
     if let Some(from_block) = &register.from_block {
-        writeln!(out, "  - In '{}' instance of '{}' block", from_block.instance, from_block.block)?;
+        let block_name = if opts.link_sections {
+            block_link(&from_block.block)
+        } else {
+            format!("'{}'", from_block.block)
+        };
+        writeln!(out, "  - In '{}' instance of {block_name} block", from_block.instance)?;
         let block = &map.register_blocks[&from_block.block];
         let member = &block.members[&from_block.block_member];
         let instance = &block.instances[&from_block.instance];
@@ -130,7 +217,7 @@ fn generate_register_infos(
 
         for sublayout in sublayouts {
             let name = sublayout.name.join(".");
-            let bits = sublayout.bits.to_string(RangeStyle::Verilog);
+            let bits = register.layout.bits_to_string(&sublayout.bits, RangeStyle::Verilog);
             let sublayout_value = value.map(|x| (x & sublayout.bits.mask()) >> sublayout.bits.lsb_pos());
 
             let FieldType::Layout(subfield_layout) = &sublayout.field.accepts else {
@@ -151,15 +238,139 @@ fn generate_register_infos(
     writeln!(out)?;
     writeln!(out, "#### Fields:")?;
     writeln!(out)?;
+    generate_field_list(out, &register.layout, value, opts.link_sections)?;
+
+    Ok(())
+}
+
+fn generate_block_infos(out: &mut dyn Write, block: &RegisterBlock) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "### {}", block_heading(&block.name))?;
+    if !block.docs.is_empty() {
+        writeln!(out)?;
+        write!(out, "{}", block.docs.as_twoline("  - "))?;
+    }
+
+    let mut members: Vec<_> = block.members.values().collect();
+    members.sort_by_key(|x| x.offset);
+    let mut instances: Vec<_> = block.instances.values().collect();
+    instances.sort_by_key(|x| x.adr);
+
+    // Instance/offset matrix:
+    let mut header = vec!["**Member**".to_string(), "**Offset**".to_string()];
+    for instance in &instances {
+        header.push(format!("**{} (0x{:02X})**", instance.name, instance.adr));
+    }
+    let mut rows = vec![header];
+    for member in &members {
+        let mut row = vec![member.name_raw.clone(), format!("0x{:02X}", member.offset)];
+        for instance in &instances {
+            let register = &instance.registers[&member.name];
+            row.push(format!("[0x{:02X}](#{})", register.adr, md_anchor(&register.name)));
+        }
+        rows.push(row);
+    }
+    writeln!(out)?;
+    md_table(out, &rows, "")?;
 
-    for field in register.layout.nested_fields() {
-        let value_field = value.map(|x| (x & field.bits.mask()) >> field.bits.lsb_pos());
+    Ok(())
+}
+
+fn generate_enum_infos(out: &mut dyn Write, map: &RegisterMap, e: &Enum) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "### {}", enum_heading(&e.name))?;
+    writeln!(out)?;
+    write!(out, "{}", e.docs.as_twoline("  - "))?;
+    writeln!(out, "  - Width: {} bits", e.bitwidth)?;
+
+    let mut entries: Vec<_> = e.entries.values().collect();
+    entries.sort_by_key(|x| x.value);
+    let mut rows = vec![vec![
+        "**Value**".to_string(),
+        "**Name**".to_string(),
+        "**Brief**".to_string(),
+    ]];
+    for entry in entries {
+        let brief = entry.docs.brief.clone().unwrap_or("-".to_string());
+        rows.push(vec![format!("0x{:02X}", entry.value), entry.name.clone(), brief]);
+    }
+    writeln!(out)?;
+    md_table(out, &rows, "")?;
+
+    let mut users: Vec<_> = map
+        .registers
+        .values()
+        .filter(|r| {
+            r.layout
+                .nested_fields()
+                .iter()
+                .any(|x| matches!(&x.field.accepts, FieldType::Enum(used) if used.name == e.name))
+        })
+        .collect();
+    users.sort_by_key(|r| r.adr);
+    generate_used_by(out, &users)?;
+
+    Ok(())
+}
+
+fn generate_layout_infos(out: &mut dyn Write, map: &RegisterMap, layout: &Layout) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "### {}", layout_heading(&layout.name))?;
+    writeln!(out)?;
+    write!(out, "{}", layout.docs.as_twoline("  - "))?;
+    writeln!(out, "  - Width: {} bits", layout.bitwidth)?;
+    writeln!(out)?;
+    generate_layout_table(out, layout, None)?;
+
+    writeln!(out)?;
+    writeln!(out, "#### Fields:")?;
+    writeln!(out)?;
+    generate_field_list(out, layout, None, true)?;
+
+    let mut users: Vec<_> = map
+        .registers
+        .values()
+        .filter(|r| r.layout.name == layout.name)
+        .collect();
+    users.sort_by_key(|r| r.adr);
+    generate_used_by(out, &users)?;
+
+    Ok(())
+}
+
+fn generate_used_by<R: AsRef<Register>>(out: &mut dyn Write, registers: &[R]) -> Result<(), Error> {
+    if registers.is_empty() {
+        return Ok(());
+    }
+    let links: Vec<String> = registers
+        .iter()
+        .map(|r| format!("[{}](#{})", r.as_ref().name, md_anchor(&r.as_ref().name)))
+        .collect();
+    writeln!(out)?;
+    writeln!(out, "Used by: {}", links.join(", "))?;
+    Ok(())
+}
+
+fn generate_field_list(
+    out: &mut dyn Write,
+    layout: &Layout,
+    value: Option<TypeValue>,
+    link_sections: bool,
+) -> Result<(), Error> {
+    for field in layout.nested_fields() {
+        let value_field = value.map(|x| field.bits.extract(x));
 
         let indent = field.name.len();
-        let indent = String::from_iter(std::iter::repeat("  ").take(indent));
+        let indent = String::from_iter(std::iter::repeat_n("  ", indent));
 
-        let value_string = value_field.map(|x| format!(": 0x{x:02X}")).unwrap_or_default();
-        let bits = field.bits.to_string(RangeStyle::Verilog);
+        let value_string = match value_field {
+            Some(x) => match (&field.field.scaling, field.field.decode_physical_value(x)) {
+                (Some(scaling), Some(physical)) => format!(": 0x{x:02X} ({physical} {})", scaling.unit),
+                _ => format!(": 0x{x:02X}"),
+            },
+            None => String::new(),
+        };
+        let bits = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
 
         let name = field.name.join(".");
 
@@ -174,15 +385,24 @@ fn generate_register_infos(
             FieldType::UInt => {
                 writeln!(out, "{indent} - Type: uint")?;
             }
+            FieldType::SInt => {
+                writeln!(out, "{indent} - Type: sint")?;
+            }
             FieldType::Bool => {
                 writeln!(out, "{indent} - Type: bool")?;
             }
             FieldType::Fixed(fix) => {
                 writeln!(out, "{indent} - Type: fixed 0x{fix:02X}")?;
             }
+            FieldType::Layout(l) if link_sections && !l.is_local => {
+                writeln!(out, "{indent} - Type: struct {}", layout_link(l))?;
+            }
             FieldType::Layout(l) => {
                 writeln!(out, "{indent} - Type: struct {}", l.name)?;
             }
+            FieldType::Enum(e) if link_sections && !e.is_local => {
+                writeln!(out, "{indent} - Type: enum {}", enum_link(e))?;
+            }
             FieldType::Enum(e) => {
                 writeln!(out, "{indent} - Type: enum {}", e.name)?;
                 for entry in e.entries.values() {
@@ -198,11 +418,42 @@ fn generate_register_infos(
                 }
             }
         }
+
+        if let Some(constraints) = &field.field.constraints {
+            writeln!(out, "{indent} - Valid values: {}", constraints_str(constraints))?;
+        }
+
+        if let Some(scaling) = &field.field.scaling {
+            generate_scaling(out, &indent, scaling, value_field)?;
+        }
     }
 
     Ok(())
 }
 
+fn generate_scaling(
+    out: &mut dyn Write,
+    indent: &str,
+    scaling: &Scaling,
+    value_field: Option<TypeValue>,
+) -> Result<(), Error> {
+    let unit = &scaling.unit;
+    writeln!(out, "{indent} - Unit: {unit} ({})", scaling_str(scaling))?;
+    if let ScalingConversion::Table(table) = &scaling.conversion {
+        for (idx, physical) in table.iter().enumerate() {
+            match value_field {
+                Some(val_field) if val_field == idx as TypeValue => {
+                    writeln!(out, "{indent}   - **0x{idx:02X}: {physical} {unit} (SELECTED)**")?;
+                }
+                _ => {
+                    writeln!(out, "{indent}   - 0x{idx:02X}: {physical} {unit}")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn generate_layout_table(out: &mut dyn Write, layout: &Layout, value: Option<TypeValue>) -> Result<(), Error> {
     // Overview table:
     let ranges = layout.split_to_bitranges();
@@ -214,7 +465,7 @@ fn generate_layout_table(out: &mut dyn Write, layout: &Layout, value: Option<Typ
     let mut row_decode: Vec<String> = vec!["**Decode:**".to_string()];
 
     for range in ranges.iter().rev() {
-        row_bits.push(range.bits.to_string(RangeStyle::Verilog));
+        row_bits.push(layout.range_to_string(&range.bits, RangeStyle::Verilog));
 
         if let Some(content) = &range.content {
             if let Some(access) = &content.field.access {
@@ -233,7 +484,7 @@ fn generate_layout_table(out: &mut dyn Write, layout: &Layout, value: Option<Typ
             let value_range = (value & bitmask_from_range(&range.bits)) >> range.bits.msb_pos();
             row_state.push(format!("**0b{value_range:b}**"));
             if let Some(content) = &range.content {
-                let value_field = (value & content.field.bits.mask()) >> content.field.bits.lsb_pos();
+                let value_field = content.field.bits.extract(value);
                 row_decode.push(decode_field(value_field, content.field));
             } else {
                 row_decode.push(String::new());
@@ -248,9 +499,41 @@ fn generate_layout_table(out: &mut dyn Write, layout: &Layout, value: Option<Typ
     }
 }
 
+fn block_heading(name: &str) -> String {
+    format!("Block {name}")
+}
+
+fn enum_heading(name: &str) -> String {
+    format!("Enum {name}")
+}
+
+fn layout_heading(name: &str) -> String {
+    format!("Layout {name}")
+}
+
+fn block_link(name: &str) -> String {
+    format!("[{name}](#{})", md_anchor(&block_heading(name)))
+}
+
+fn enum_link(e: &Enum) -> String {
+    format!("[{}](#{})", e.name, md_anchor(&enum_heading(&e.name)))
+}
+
+fn layout_link(layout: &Layout) -> String {
+    format!("[{}](#{})", layout.name, md_anchor(&layout_heading(&layout.name)))
+}
+
 fn decode_field(field_value: TypeValue, field: &LayoutField) -> String {
+    if let Some(scaling) = &field.scaling {
+        return match field.decode_physical_value(field_value) {
+            Some(physical) => format!("**{physical} {}**", scaling.unit),
+            None => "**ERROR**".to_string(),
+        };
+    }
+
     match field.decode_value(field_value) {
         Ok(DecodedField::UInt(_)) => String::new(),
+        Ok(DecodedField::SInt(val)) => format!("**{val}**"),
         Ok(DecodedField::Bool(b)) => if b { "**true**" } else { "**false**" }.to_string(),
         Ok(DecodedField::EnumEntry(e)) => format!("**{e}**"),
         Ok(DecodedField::Fixed { val: _, is_correct }) => {
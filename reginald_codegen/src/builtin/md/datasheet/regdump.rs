@@ -1,6 +1,6 @@
 use std::{
     collections::{BTreeMap, HashSet},
-    fmt::Write,
+    fmt::{self, Write},
     ops::Deref,
     path::{Path, PathBuf},
 };
@@ -8,26 +8,73 @@ use std::{
 use crate::{
     builtin::md::md_table,
     error::Error,
-    regmap::{Register, RegisterMap, TypeAdr, TypeValue},
+    regmap::{
+        DecodedField, Register, RegisterMap, TypeAdr, TypeValue,
+        listing::value::{Value, ValueVisitor},
+    },
 };
 
-use super::generate_register_infos;
+use super::{RegisterInfoOpts, generate_register_infos};
 
 #[cfg(feature = "clap")]
 use clap::Parser;
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, SeqAccess, Visitor},
+};
 
 // ====== Register Dump ========================================================
 
 pub type RegDump = BTreeMap<TypeAdr, TypeValue>;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
-#[serde(untagged, deny_unknown_fields)]
+#[derive(Debug, PartialEq, Eq)]
 enum RegDumpListingEntry {
     One(TypeValue),
     Multiple(Vec<TypeValue>),
 }
 
+// Deserialized by hand instead of as an untagged enum, which cannot buffer
+// 128-bit values:
+impl<'de> Deserialize<'de> for RegDumpListingEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RegDumpListingEntryVisitor)
+    }
+}
+
+struct RegDumpListingEntryVisitor;
+
+impl<'de> Visitor<'de> for RegDumpListingEntryVisitor {
+    type Value = RegDumpListingEntry;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a register value, or a list of consecutive register values")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(RegDumpListingEntry::One(ValueVisitor.visit_u64::<E>(v)?.0))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(RegDumpListingEntry::One(ValueVisitor.visit_u128::<E>(v)?.0))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(RegDumpListingEntry::One(ValueVisitor.visit_i64::<E>(v)?.0))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(RegDumpListingEntry::One(ValueVisitor.visit_str::<E>(v)?.0))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut vals = vec![];
+        while let Some(val) = seq.next_element::<Value>()? {
+            vals.push(val.0);
+        }
+        Ok(RegDumpListingEntry::Multiple(vals))
+    }
+}
+
 fn read_regdump(path: &Path) -> Result<RegDump, Error> {
     let reader = std::fs::File::open(path)?;
     let regdump_listing: BTreeMap<TypeAdr, RegDumpListingEntry> = serde_yaml::from_reader(reader)?;
@@ -84,10 +131,12 @@ pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) ->
     for adr in adrs {
         let (regs, val) = lookup_adr(map, &regdump, adr);
         for reg in regs {
-            generate_register_infos(out, map, reg, val)?;
+            generate_register_infos(out, map, reg, val, RegisterInfoOpts::default())?;
         }
     }
 
+    generate_virtual_fields(out, map, &regdump)?;
+
     Ok(())
 }
 
@@ -146,6 +195,47 @@ fn generate_overview(
     Ok(())
 }
 
+fn generate_virtual_fields(out: &mut dyn Write, map: &RegisterMap, regdump: &RegDump) -> Result<(), Error> {
+    if map.virtual_fields.is_empty() {
+        return Ok(());
+    }
+
+    let mut rows = vec![];
+    rows.push(vec![
+        "**Virtual Field**".to_string(),
+        "**Parts**".to_string(),
+        "**Value**".to_string(),
+        "**Decoded**".to_string(),
+        "**Brief**".to_string(),
+    ]);
+    for field in map.virtual_fields.values() {
+        let parts: Vec<String> = field.parts.iter().map(|x| x.name()).collect();
+        let (value_str, decoded_str) = match field.assemble(|reg| regdump.get(&reg.adr).copied()) {
+            Some(val) => {
+                let decoded = match field.decode_value(val) {
+                    DecodedField::SInt(signed) => format!("**{signed}**"),
+                    _ => format!("**{val}**"),
+                };
+                (format!("0x{val:X}"), decoded)
+            }
+            None => ("?".to_string(), "?".to_string()),
+        };
+        rows.push(vec![
+            field.name.clone(),
+            parts.join(", "),
+            value_str,
+            decoded_str,
+            field.docs.brief.clone().unwrap_or_default(),
+        ]);
+    }
+
+    writeln!(out)?;
+    writeln!(out, "## Virtual Fields")?;
+    writeln!(out)?;
+    md_table(out, &rows, "")?;
+    Ok(())
+}
+
 fn adrs_of_interest(map: &RegisterMap, regdump: &RegDump) -> Vec<TypeAdr> {
     let mut adrs: HashSet<TypeAdr> = HashSet::new();
 
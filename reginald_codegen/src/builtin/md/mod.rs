@@ -52,6 +52,20 @@ fn md_sep_row(out: &mut dyn Write, widths: &[usize], line_prefix: &str) -> Resul
     Ok(())
 }
 
+/// GitHub-style anchor of a markdown heading.
+pub fn md_anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '_' || c == '-' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +91,11 @@ mod tests {
                       ~| :) | !     |      |\n";
         assert_eq!(is, should);
     }
+
+    #[test]
+    fn test_md_anchor() {
+        assert_eq!(md_anchor("REG_A"), "reg_a");
+        assert_eq!(md_anchor("Enum MY_ENUM"), "enum-my_enum");
+        assert_eq!(md_anchor("A.B (c)"), "ab-c");
+    }
 }
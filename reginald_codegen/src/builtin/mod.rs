@@ -1,3 +1,10 @@
 pub mod c;
+pub mod cpp;
+pub mod csv;
+pub mod diagram;
+pub mod html;
 pub mod md;
+pub mod py;
 pub mod rs;
+pub mod sv;
+pub mod template;
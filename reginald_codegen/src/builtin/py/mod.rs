@@ -0,0 +1,571 @@
+use std::{fmt::Write, sync::LazyLock};
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+use regex::Regex;
+use reginald_utils::{RangeStyle, str_pad_to_length};
+
+use crate::{
+    error::Error,
+    regmap::{Docs, Enum, FieldType, Layout, LayoutField, Register, RegisterBlock, RegisterMap, TypeValue},
+    utils::Endianess,
+};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Generate packing/unpacking functions with the given endianess.
+    ///
+    /// May be given multiple times. If not specified, both endianess
+    /// versions will be generated.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(value_enum))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub endian: Vec<Endianess>,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            endian: vec![Endianess::Little, Endianess::Big],
+        }
+    }
+}
+
+// ====== Generator ============================================================
+
+struct Input<'a> {
+    map: &'a RegisterMap,
+    endian: Vec<Endianess>,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    map.require_contiguous_fields("py")?;
+
+    let endian = if opts.endian.is_empty() {
+        vec![Endianess::Little, Endianess::Big]
+    } else {
+        opts.endian.clone()
+    };
+
+    let inp = Input { map, endian };
+
+    generate_header(out, &inp)?;
+
+    // ===== Enums: =====
+    writeln!(out)?;
+    writeln!(out)?;
+    py_generate_section_header_comment(out, "Enums")?;
+    for e in map.enums.values() {
+        generate_enum(out, e)?;
+    }
+
+    // ===== Layouts: =====
+    writeln!(out)?;
+    writeln!(out)?;
+    py_generate_section_header_comment(out, "Layouts")?;
+    for layout in map.layouts_in_dependency_order() {
+        // Registers with a local layout are represented by the layout class directly:
+        let register = map
+            .individual_registers()
+            .find(|x| x.layout.is_local && x.layout.name == layout.name);
+        generate_layout(out, &inp, layout, register)?;
+    }
+
+    // ===== Registers: =====
+    writeln!(out)?;
+    writeln!(out)?;
+    py_generate_section_header_comment(out, "Registers")?;
+    for register in map.registers.values() {
+        if register.layout.is_local && register.from_block.is_none() {
+            continue;
+        }
+        generate_register_class(out, register)?;
+    }
+
+    // ===== Register Blocks: =====
+    if !map.register_blocks.is_empty() {
+        writeln!(out)?;
+        writeln!(out)?;
+        py_generate_section_header_comment(out, "Register Blocks")?;
+        for block in map.register_blocks.values() {
+            generate_register_block(out, block)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_header(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    writeln!(out, "\"\"\"{}", py_escape_docstring(&inp.map.name))?;
+    writeln!(out)?;
+    if let Some(input_file) = &inp.map.from_file {
+        writeln!(out, "Do not edit directly: generated using reginald from {}.", input_file.to_string_lossy())?;
+    } else {
+        writeln!(out, "Do not edit directly: generated using reginald.")?;
+    }
+    writeln!(out)?;
+    writeln!(out, "Generator: py")?;
+    if !inp.map.docs.is_empty() {
+        writeln!(out)?;
+        write!(out, "{}", py_escape_docstring(&inp.map.docs.as_multiline("")))?;
+    }
+    if let Some(author) = &inp.map.author {
+        writeln!(out)?;
+        writeln!(out, "Listing file author: {}", py_escape_docstring(author))?;
+    }
+    if let Some(notice) = &inp.map.notice {
+        writeln!(out)?;
+        writeln!(out, "Listing file notice:")?;
+        for line in notice.lines() {
+            writeln!(out, "  {}", py_escape_docstring(line))?;
+        }
+    }
+    writeln!(out, "\"\"\"")?;
+    writeln!(out)?;
+    writeln!(out, "from __future__ import annotations")?;
+    writeln!(out)?;
+    writeln!(out, "import dataclasses")?;
+    writeln!(out, "import enum")?;
+    writeln!(out, "from typing import ClassVar, Optional")?;
+    Ok(())
+}
+
+fn generate_enum(out: &mut dyn Write, e: &Enum) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out)?;
+    writeln!(out, "class {}(enum.IntEnum):", py_pascalcase(&e.name))?;
+    py_generate_docstring(out, &e.docs, "    ", &[format!("{}-bit wide enum.", e.bitwidth)])?;
+    writeln!(out)?;
+
+    let mut entries: Vec<_> = e.entries.values().collect();
+    entries.sort_by_key(|x| x.value);
+    for entry in entries {
+        if !entry.docs.is_empty() {
+            for line in entry.docs.as_multiline("").lines() {
+                writeln!(out, "    #: {line}")?;
+            }
+        }
+        writeln!(out, "    {} = 0x{:X}", py_const(&entry.name), entry.value)?;
+    }
+
+    Ok(())
+}
+
+fn generate_layout(
+    out: &mut dyn Write,
+    inp: &Input,
+    layout: &Layout,
+    register: Option<&Register>,
+) -> Result<(), Error> {
+    let class_name = py_pascalcase(&layout.name);
+    let width_bytes = layout.width_bytes();
+
+    let mut extra = vec![];
+    if let Some(register) = register {
+        extra.push(format!("`{}` Register", register.name));
+        extra.push(String::new());
+        extra.push(format!("Address: 0x{:X}", register.adr));
+        if let Some(reset_val) = register.reset_val {
+            extra.push(String::new());
+            extra.push(format!("Reset Value: 0x{reset_val:X}"));
+        }
+        extra.push(String::new());
+    }
+    extra.push("Fields:".to_string());
+    extra.extend(layout.overview_text(false).lines().map(|x| format!("  {x}")));
+
+    // Class proper:
+    writeln!(out)?;
+    writeln!(out)?;
+    writeln!(out, "@dataclasses.dataclass")?;
+    writeln!(out, "class {class_name}:")?;
+    py_generate_docstring(out, &layout.docs, "    ", &extra)?;
+    writeln!(out)?;
+    writeln!(out, "    WIDTH_BYTES: ClassVar[int] = {width_bytes}")?;
+    if let Some(register) = register {
+        generate_register_properties(out, register)?;
+    }
+
+    // Fields:
+    for field in layout.fields_with_content() {
+        if !field.docs.is_empty() {
+            for line in field.docs.as_multiline("").lines() {
+                writeln!(out, "    #: {line}")?;
+            }
+        }
+        writeln!(out, "    {}: {} = {}", py_snakecase(&field.name), field_type(field), field_default(field))?;
+    }
+
+    generate_to_int(out, layout)?;
+    generate_from_int(out, layout)?;
+    generate_validate(out, layout)?;
+
+    for endian in &inp.endian {
+        generate_pack(out, *endian)?;
+    }
+    for endian in &inp.endian {
+        generate_unpack(out, layout, *endian)?;
+    }
+
+    if let Some(register) = register {
+        generate_register_reset(out, register)?;
+    }
+
+    Ok(())
+}
+
+fn generate_to_int(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let class_name = py_pascalcase(&layout.name);
+
+    writeln!(out)?;
+    writeln!(out, "    def to_int(self) -> int:")?;
+    writeln!(out, "        \"\"\"Convert to packed integer value.")?;
+    writeln!(out)?;
+    writeln!(out, "        Raises `ValueError` if a field holds a value that is not valid.")?;
+    writeln!(out, "        \"\"\"")?;
+    writeln!(out, "        self.validate()")?;
+    writeln!(out, "        val = 0")?;
+    for field in layout.fields.values() {
+        let field_name = py_snakecase(&field.name);
+        let lsb = field.bits.lsb_pos();
+        let mask = field.bits.unpositioned_mask();
+        let bit_str = layout.bits_to_string(&field.bits, RangeStyle::Verilog);
+        let value = match &field.accepts {
+            FieldType::UInt | FieldType::SInt => format!("self.{field_name}"),
+            FieldType::Bool | FieldType::Enum(_) => format!("int(self.{field_name})"),
+            FieldType::Layout(_) => format!("self.{field_name}.to_int()"),
+            FieldType::Fixed(fixed) => {
+                let fixed = fixed << lsb;
+                writeln!(out, "        val |= 0x{fixed:X}  # {} @ {class_name}[{bit_str}] (fixed value)", field.name)?;
+                continue;
+            }
+        };
+        writeln!(out, "        val |= ({value} & 0x{mask:X}) << {lsb}  # {} @ {class_name}[{bit_str}]", field.name)?;
+    }
+    writeln!(out, "        return val")?;
+
+    Ok(())
+}
+
+fn generate_from_int(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let class_name = py_pascalcase(&layout.name);
+
+    writeln!(out)?;
+    writeln!(out, "    @classmethod")?;
+    writeln!(out, "    def from_int(cls, val: int) -> {class_name}:")?;
+    writeln!(out, "        \"\"\"Convert from packed integer value.")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "        Fixed bits are ignored. Raises `ValueError` if an enum field holds a value that is not valid."
+    )?;
+    writeln!(out, "        \"\"\"")?;
+
+    if layout.fields_with_content().count() == 0 {
+        writeln!(out, "        del val  # Layout contains no variable fields.")?;
+        writeln!(out, "        return cls()")?;
+        return Ok(());
+    }
+
+    writeln!(out, "        return cls(")?;
+    for field in layout.fields_with_content() {
+        let field_name = py_snakecase(&field.name);
+        let raw = format!("(val >> {}) & 0x{:X}", field.bits.lsb_pos(), field.bits.unpositioned_mask());
+        let value = match &field.accepts {
+            FieldType::UInt => raw,
+            FieldType::SInt => {
+                let sign_bit: TypeValue = 1 << (field.bits.width() - 1);
+                format!("(({raw}) ^ 0x{sign_bit:X}) - 0x{sign_bit:X}")
+            }
+            FieldType::Bool => format!("bool({raw})"),
+            FieldType::Enum(e) => format!("{}({raw})", py_pascalcase(&e.name)),
+            FieldType::Layout(l) => format!("{}.from_int({raw})", py_pascalcase(&l.name)),
+            FieldType::Fixed(_) => unreachable!(),
+        };
+        writeln!(out, "            {field_name}={value},")?;
+    }
+    writeln!(out, "        )")?;
+
+    Ok(())
+}
+
+fn generate_validate(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "    def validate(self) -> None:")?;
+    writeln!(out, "        \"\"\"Confirm that all enums are valid, and all values fit into respective fields.")?;
+    writeln!(out)?;
+    writeln!(out, "        Raises `ValueError` if not.")?;
+    writeln!(out, "        \"\"\"")?;
+
+    let mut stmt_cnt = 0;
+    for field in layout.fields_with_content() {
+        let field_name = py_snakecase(&field.name);
+        match &field.accepts {
+            FieldType::UInt => {
+                let max = field.bits.unpositioned_mask();
+                writeln!(out, "        if not 0 <= self.{field_name} <= 0x{max:X}:")?;
+                writeln!(
+                    out,
+                    "            raise ValueError(f\"{} value 0x{{self.{field_name}:X}} does not fit into field.\")",
+                    field.name
+                )?;
+            }
+            FieldType::SInt => {
+                let sign_bit: TypeValue = 1 << (field.bits.width() - 1);
+                writeln!(out, "        if not -0x{sign_bit:X} <= self.{field_name} <= 0x{:X}:", sign_bit - 1)?;
+                writeln!(
+                    out,
+                    "            raise ValueError(f\"{} value {{self.{field_name}}} does not fit into field.\")",
+                    field.name
+                )?;
+            }
+            FieldType::Enum(e) => {
+                // Constructing the enum raises a ValueError if the value is not a member:
+                writeln!(out, "        {}(self.{field_name})", py_pascalcase(&e.name))?;
+            }
+            FieldType::Layout(_) => {
+                writeln!(out, "        self.{field_name}.validate()")?;
+            }
+            FieldType::Bool => continue,
+            FieldType::Fixed(_) => unreachable!(),
+        }
+        stmt_cnt += 1;
+    }
+
+    if stmt_cnt == 0 {
+        writeln!(out, "        return")?;
+    }
+
+    Ok(())
+}
+
+fn generate_pack(out: &mut dyn Write, endian: Endianess) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "    def pack_{}(self) -> bytes:", endian.short())?;
+    writeln!(out, "        \"\"\"Convert to packed {endian} binary value.\"\"\"")?;
+    writeln!(out, "        return self.to_int().to_bytes(self.WIDTH_BYTES, \"{}\")", py_byteorder(endian))?;
+    Ok(())
+}
+
+fn generate_unpack(out: &mut dyn Write, layout: &Layout, endian: Endianess) -> Result<(), Error> {
+    let class_name = py_pascalcase(&layout.name);
+
+    writeln!(out)?;
+    writeln!(out, "    @classmethod")?;
+    writeln!(out, "    def unpack_{}(cls, data: bytes) -> {class_name}:", endian.short())?;
+    writeln!(out, "        \"\"\"Convert from packed {endian} binary value.\"\"\"")?;
+    writeln!(out, "        if len(data) != cls.WIDTH_BYTES:")?;
+    writeln!(out, "            raise ValueError(f\"Expected {{cls.WIDTH_BYTES}} bytes, got {{len(data)}}.\")")?;
+    writeln!(out, "        return cls.from_int(int.from_bytes(data, \"{}\"))", py_byteorder(endian))?;
+    Ok(())
+}
+
+fn generate_register_properties(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    writeln!(out, "    ADDRESS: ClassVar[int] = 0x{:X}", register.adr)?;
+    match register.reset_val {
+        Some(reset_val) => writeln!(out, "    RESET_VAL: ClassVar[Optional[int]] = 0x{reset_val:X}")?,
+        None => writeln!(out, "    RESET_VAL: ClassVar[Optional[int]] = None")?,
+    }
+    Ok(())
+}
+
+fn generate_register_reset(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    let class_name = py_pascalcase(&register.name);
+
+    if register.reset_val.is_none() {
+        return Ok(());
+    }
+
+    writeln!(out)?;
+    writeln!(out, "    @classmethod")?;
+    writeln!(out, "    def reset(cls) -> {class_name}:")?;
+    writeln!(out, "        \"\"\"Register reset value.\"\"\"")?;
+    writeln!(out, "        assert cls.RESET_VAL is not None")?;
+    writeln!(out, "        return cls.from_int(cls.RESET_VAL)")?;
+    Ok(())
+}
+
+/// Generate register class that derives from a non-local or block member layout.
+fn generate_register_class(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    let class_name = py_pascalcase(&register.name);
+    let layout_name = py_pascalcase(&register.layout.name);
+
+    let mut extra = vec![format!("`{}` Register", register.name), String::new()];
+    let docs_lines = py_docs_lines(&register.docs);
+    if !docs_lines.is_empty() {
+        extra.extend(docs_lines);
+        extra.push(String::new());
+    }
+    extra.push(format!("Address: 0x{:X}", register.adr));
+    if let Some(reset_val) = register.reset_val {
+        extra.push(String::new());
+        extra.push(format!("Reset Value: 0x{reset_val:X}"));
+    }
+    extra.push(String::new());
+    extra.push(format!("Uses `{layout_name}` layout."));
+
+    writeln!(out)?;
+    writeln!(out)?;
+    writeln!(out, "@dataclasses.dataclass")?;
+    writeln!(out, "class {class_name}({layout_name}):")?;
+    py_generate_docstring(out, &Docs::default(), "    ", &extra)?;
+    writeln!(out)?;
+    generate_register_properties(out, register)?;
+    generate_register_reset(out, register)?;
+
+    Ok(())
+}
+
+fn generate_register_block(out: &mut dyn Write, block: &RegisterBlock) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "# {} register block:", block.name)?;
+    for line in block.docs.as_multiline("# ").lines() {
+        writeln!(out, "{line}")?;
+    }
+    for instance in block.instances.values() {
+        writeln!(
+            out,
+            "{}_INSTANCE_{} = 0x{:X}  # Start of {} instance {}",
+            py_const(&block.name),
+            py_const(&instance.name),
+            instance.adr,
+            block.name,
+            instance.name
+        )?;
+    }
+    let mut members: Vec<_> = block.members.values().collect();
+    members.sort_by_key(|x| x.offset);
+    for member in members {
+        writeln!(
+            out,
+            "{}_OFFSET = 0x{:X}  # Offset of {} register from {} block start",
+            py_const(&member.name),
+            member.offset,
+            member.name,
+            block.name
+        )?;
+    }
+    Ok(())
+}
+
+fn field_type(field: &LayoutField) -> String {
+    match &field.accepts {
+        FieldType::UInt | FieldType::SInt => "int".to_string(),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Enum(e) => py_pascalcase(&e.name),
+        FieldType::Layout(l) => py_pascalcase(&l.name),
+        FieldType::Fixed(_) => panic!("Fixed field has no type"),
+    }
+}
+
+fn field_default(field: &LayoutField) -> String {
+    match &field.accepts {
+        FieldType::UInt | FieldType::SInt => "0".to_string(),
+        FieldType::Bool => "False".to_string(),
+        FieldType::Enum(e) => {
+            // Default to entry with the lowest value:
+            let entry = e.entries.values().min_by_key(|x| x.value).expect("Enum has no entries");
+            format!("{}.{}", py_pascalcase(&e.name), py_const(&entry.name))
+        }
+        FieldType::Layout(l) => format!("dataclasses.field(default_factory={})", py_pascalcase(&l.name)),
+        FieldType::Fixed(_) => panic!("Fixed field has no type"),
+    }
+}
+
+// ====== Generator Utils ======================================================
+
+fn py_section_header_comment(title: &str) -> String {
+    str_pad_to_length(&format!("# ==== {title} "), '=', 80)
+}
+
+fn py_generate_section_header_comment(out: &mut dyn Write, title: &str) -> Result<(), Error> {
+    writeln!(out, "{}", py_section_header_comment(title))?;
+    Ok(())
+}
+
+fn py_byteorder(endian: Endianess) -> &'static str {
+    match endian {
+        Endianess::Little => "little",
+        Endianess::Big => "big",
+    }
+}
+
+fn py_pascalcase(s: &str) -> String {
+    let mut result = String::new();
+    for part in py_sanitize(s).to_lowercase().split('_') {
+        let mut chars = part.chars();
+        if let Some(f) = chars.next() {
+            result.push_str(&(f.to_uppercase().collect::<String>() + chars.as_str()));
+        }
+    }
+    result
+}
+
+const PY_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except",
+    "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+    "return", "try", "while", "with", "yield",
+];
+
+fn py_snakecase(s: &str) -> String {
+    let s = py_sanitize(s).to_lowercase();
+    if PY_KEYWORDS.contains(&s.as_str()) { s + "_" } else { s }
+}
+
+fn py_const(s: &str) -> String {
+    py_sanitize(s).to_uppercase()
+}
+
+static PY_SANITIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^_a-zA-Z0-9]").unwrap());
+
+fn py_sanitize(s: &str) -> String {
+    PY_SANITIZE_REGEX.replace_all(s, "_").into()
+}
+
+fn py_escape_docstring(s: &str) -> String {
+    s.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")
+}
+
+/// Lines of a docs entry, with brief and main documentation separated by an empty line.
+fn py_docs_lines(docs: &Docs) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    if let Some(brief) = &docs.brief {
+        lines.push(brief.clone());
+    }
+    if let Some(doc) = &docs.doc {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(doc.lines().map(String::from));
+    }
+    lines
+}
+
+fn py_generate_docstring(out: &mut dyn Write, docs: &Docs, prefix: &str, extra: &[String]) -> Result<(), Error> {
+    let mut lines = py_docs_lines(docs);
+    if !lines.is_empty() && !extra.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(extra.iter().cloned());
+
+    match lines.as_slice() {
+        [] => (),
+        [line] => writeln!(out, "{prefix}\"\"\"{}\"\"\"", py_escape_docstring(line))?,
+        [first, rest @ ..] => {
+            writeln!(out, "{prefix}\"\"\"{}", py_escape_docstring(first))?;
+            for line in rest {
+                if line.is_empty() {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, "{prefix}{}", py_escape_docstring(line))?;
+                }
+            }
+            writeln!(out, "{prefix}\"\"\"")?;
+        }
+    }
+    Ok(())
+}
@@ -81,6 +81,17 @@ fn rs_fitting_unsigned_type(width: TypeBitwidth) -> Result<String, Error> {
     }
 }
 
+fn rs_fitting_signed_type(width: TypeBitwidth) -> Result<String, Error> {
+    match width {
+        1..=8 => Ok("i8".to_string()),
+        9..=16 => Ok("i16".to_string()),
+        17..=32 => Ok("i32".to_string()),
+        33..=64 => Ok("i64".to_string()),
+        65..=128 => Ok("i128".to_string()),
+        _ => Err(Error::GeneratorError(format!("Cannot represent {width}-bit wide value as a rust type!"))),
+    }
+}
+
 fn rs_header_comment(title: &str) -> String {
     str_pad_to_length(&format!("// ==== {title} "), '=', 80)
 }
@@ -130,3 +141,4 @@ fn masked_array_literal(endian: Endianess, val_name: &str, mask: TypeValue, widt
 }
 
 pub const CONVERSION_TRAITS: &str = include_str!("../../../../reginald/src/traits.rs");
+pub const MMIO_TYPES: &str = include_str!("../../../../reginald/src/mmio.rs");
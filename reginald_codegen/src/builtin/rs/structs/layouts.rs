@@ -6,7 +6,7 @@ use crate::{
     bits::{bitwidth_to_width_bytes, mask_to_bit_ranges},
     builtin::rs::generate_extended_doc_comment,
     error::Error,
-    regmap::{BitRange, FieldType, Layout, RegisterBlockMember},
+    regmap::{BitRange, FieldConstraints, FieldType, Layout, RegisterBlockMember, ScalingConversion, scaling_str},
     utils::{
         Endianess, ShiftDirection, field_byte_to_packed_byte_transform, field_to_packed_byte_transform, grab_byte,
         packed_byte_to_field_byte_transform,
@@ -93,8 +93,8 @@ fn generate_layout_struct(
         writeln!(out, "/// Fixed bits:")?;
         for range in mask_to_bit_ranges(layout.fixed_bits_mask()) {
             let range = BitRange(range);
-            let range_str = range.to_string(RangeStyle::Verilog);
-            let value = (layout.fixed_bits_val() >> range.lsb_pos()) & range.mask();
+            let range_str = layout.range_to_string(&range, RangeStyle::Verilog);
+            let value = (layout.fixed_bits_val() >> range.lsb_pos()) & range.unpositioned_mask();
             writeln!(out, "/// - `[{range_str}]` = 0b{value:b}")?;
         }
     }
@@ -115,7 +115,10 @@ fn generate_layout_struct(
             out,
             &field.docs,
             "    ",
-            &[&format!("Bits: `[{}]`", field.bits.to_string(RangeStyle::Verilog))],
+            &[&format!(
+                "Bits: `[{}]`",
+                layout.bits_to_string(&field.bits, RangeStyle::Verilog)
+            )],
         )?;
         writeln!(out, "    pub {field_name}: {field_type},")?;
     }
@@ -129,6 +132,7 @@ fn generate_layout_struct(
 fn register_layout_member_type(field: &LayoutField) -> Result<String, Error> {
     match &field.accepts {
         FieldType::UInt => rs_fitting_unsigned_type(field.bits.width()),
+        FieldType::SInt => rs_fitting_signed_type(field.bits.width()),
         FieldType::Bool => Ok("bool".to_string()),
         FieldType::Enum(e) => Ok(rs_pascalcase(&e.name)),
         FieldType::Layout(l) => Ok(rs_pascalcase(&l.name)),
@@ -142,6 +146,7 @@ pub fn generate_layout_impls(out: &mut dyn Write, inp: &Input, layout: &Layout)
     if inp.opts.generate_uint_conversion {
         generate_layout_impl_uint_conv(inp, out, layout)?;
     }
+    generate_layout_impl_scaling(out, layout)?;
     Ok(())
 }
 
@@ -174,45 +179,62 @@ fn generate_layout_impl_to_bytes(inp: &Input, out: &mut dyn Write, layout: &Layo
     for field in layout.fields.values() {
         let field_name = rs_snakecase(&field.name);
 
-        writeln!(out, "// {} @ {struct_name}[{}]:", field.name, field.bits.to_string(RangeStyle::Verilog))?;
+        writeln!(
+            out,
+            "// {} @ {struct_name}[{}]:",
+            field.name,
+            layout.bits_to_string(&field.bits, RangeStyle::Verilog)
+        )?;
 
         match &field.accepts {
-            FieldType::UInt | FieldType::Bool => {
-                // Numeric field that can be directly converted:
-                for byte in 0..width_bytes {
-                    let Some(transform) = field_to_packed_byte_transform(
-                        Endianess::Little,
-                        field.bits.unpositioned_mask(),
-                        field.bits.lsb_pos(),
-                        byte,
-                        width_bytes,
-                    ) else {
-                        continue;
-                    };
+            FieldType::UInt | FieldType::SInt | FieldType::Bool => {
+                // Convert the field to some unsigned integer that can be shifted:
+                let field_value = match &field.accepts {
+                    FieldType::UInt => format!("self.{field_name}"),
+                    FieldType::SInt => {
+                        format!("(self.{field_name} as {})", rs_fitting_unsigned_type(field.bits.width())?)
+                    }
+                    FieldType::Bool => format!("u8::from(self.{field_name})"),
+                    FieldType::Enum(_) => unreachable!(),
+                    FieldType::Fixed(_) => unreachable!(),
+                    FieldType::Layout(_) => unreachable!(),
+                };
 
-                    // Convert the field to some unsigned integer that can be shifted:
-                    let field_value = match &field.accepts {
-                        FieldType::UInt => format!("self.{field_name}"),
-                        FieldType::Bool => format!("u8::from(self.{field_name})"),
-                        FieldType::Enum(_) => unreachable!(),
-                        FieldType::Fixed(_) => unreachable!(),
-                        FieldType::Layout(_) => unreachable!(),
+                // Numeric field that can be directly converted. Each range of a split field holds
+                // the bits of the value starting at 'value_pos':
+                for (range, value_pos) in field.bits.pieces() {
+                    let piece_value = if value_pos == 0 {
+                        field_value.clone()
+                    } else {
+                        format!("({field_value} >> {value_pos})")
                     };
 
-                    // The byte of interest:
-                    let field_byte = match &transform.shift {
-                        Some((ShiftDirection::Left, amnt)) => format!("({field_value} << {amnt})"),
-                        Some((ShiftDirection::Right, amnt)) => format!("({field_value} >> {amnt})"),
-                        None => field_value,
-                    };
+                    for byte in 0..width_bytes {
+                        let Some(transform) = field_to_packed_byte_transform(
+                            Endianess::Little,
+                            range.unpositioned_mask(),
+                            range.lsb_pos(),
+                            byte,
+                            width_bytes,
+                        ) else {
+                            continue;
+                        };
 
-                    let masked_field_byte = if transform.mask == 0xFF {
-                        field_byte
-                    } else {
-                        format!("({field_byte} & 0x{:X})", transform.mask)
-                    };
+                        // The byte of interest:
+                        let field_byte = match &transform.shift {
+                            Some((ShiftDirection::Left, amnt)) => format!("({piece_value} << {amnt})"),
+                            Some((ShiftDirection::Right, amnt)) => format!("({piece_value} >> {amnt})"),
+                            None => piece_value.clone(),
+                        };
+
+                        let masked_field_byte = if transform.mask == 0xFF {
+                            field_byte
+                        } else {
+                            format!("({field_byte} & 0x{:X})", transform.mask)
+                        };
 
-                    writeln!(out, "val[{byte}] |= {masked_field_byte} as u8;")?;
+                        writeln!(out, "val[{byte}] |= {masked_field_byte} as u8;")?;
+                    }
                 }
             }
 
@@ -220,7 +242,7 @@ fn generate_layout_impl_to_bytes(inp: &Input, out: &mut dyn Write, layout: &Layo
                 // Fixed value:
                 for byte in 0..width_bytes {
                     let mask_byte = grab_byte(Endianess::Little, field.bits.mask(), byte, width_bytes);
-                    let value_byte = grab_byte(Endianess::Little, *fixed << field.bits.lsb_pos(), byte, width_bytes);
+                    let value_byte = grab_byte(Endianess::Little, field.bits.insert(*fixed), byte, width_bytes);
                     if mask_byte == 0 {
                         continue;
                     };
@@ -243,11 +265,11 @@ fn generate_layout_impl_to_bytes(inp: &Input, out: &mut dyn Write, layout: &Layo
                     _ => unreachable!(),
                 };
 
-                if let FieldType::Layout(l) = &field.accepts {
-                    if l.fields.is_empty() {
-                        writeln!(out, "// No fields.")?;
-                        continue;
-                    }
+                if let FieldType::Layout(l) = &field.accepts
+                    && l.fields.is_empty()
+                {
+                    writeln!(out, "// No fields.")?;
+                    continue;
                 }
 
                 writeln!(out, "let {array_name}: [u8; {array_len}] = self.{field_name}.to_le_bytes();")?;
@@ -337,6 +359,27 @@ fn generate_layout_impl_from_bytes(inp: &Input, out: &mut dyn Write, layout: &La
 
     out.increase_indent(2);
 
+    // Constrained numeric fields are checked before the struct initialiser:
+    for field in layout.fields_with_content() {
+        let Some(constraints) = &field.constraints else {
+            continue;
+        };
+        let field_name = rs_snakecase(&field.name);
+        let numeric_value = assemble_numeric_field(layout, field)?;
+        let checks = constraint_checks(&field_name, constraints);
+
+        writeln!(
+            out,
+            "// {} @ {struct_name}[{}]:",
+            field.name,
+            layout.bits_to_string(&field.bits, RangeStyle::Verilog)
+        )?;
+        writeln!(out, "let {field_name} = {numeric_value};")?;
+        writeln!(out, "if {} {{", checks.join(" || "))?;
+        writeln!(out, "    return Err(Self::Error {{ pos: {} }});", field.bits.lsb_pos())?;
+        writeln!(out, "}}")?;
+    }
+
     // Sublayouts and enums require a bunch of array wrangling, which is done before the struct initialiser:
     for field in layout.fields_with_content() {
         let array_len = match &field.accepts {
@@ -351,15 +394,20 @@ fn generate_layout_impl_from_bytes(inp: &Input, out: &mut dyn Write, layout: &La
             _ => unreachable!(),
         };
 
-        writeln!(out, "// {} @ {struct_name}[{}]:", field.name, field.bits.to_string(RangeStyle::Verilog))?;
+        writeln!(
+            out,
+            "// {} @ {struct_name}[{}]:",
+            field.name,
+            layout.bits_to_string(&field.bits, RangeStyle::Verilog)
+        )?;
 
         // Assemble field bytes into array:
 
-        if let FieldType::Layout(l) = &field.accepts {
-            if l.fields.is_empty() {
-                writeln!(out, "let {array_name}: [u8; {array_len}] = [0; {array_len}];")?;
-                continue;
-            }
+        if let FieldType::Layout(l) = &field.accepts
+            && l.fields.is_empty()
+        {
+            writeln!(out, "let {array_name}: [u8; {array_len}] = [0; {array_len}];")?;
+            continue;
         }
 
         writeln!(out, "let mut {array_name}: [u8; {array_len}] = [0; {array_len}];")?;
@@ -408,14 +456,35 @@ fn generate_layout_impl_from_bytes(inp: &Input, out: &mut dyn Write, layout: &La
     for field in layout.fields_with_content() {
         let field_name = rs_snakecase(&field.name);
         let field_pos = field.bits.lsb_pos();
-        writeln!(out, "  // {} @ {struct_name}[{}]:", field.name, field.bits.to_string(RangeStyle::Verilog))?;
+        writeln!(
+            out,
+            "  // {} @ {struct_name}[{}]:",
+            field.name,
+            layout.bits_to_string(&field.bits, RangeStyle::Verilog)
+        )?;
 
         match &field.accepts {
+            FieldType::UInt if field.constraints.is_some() => {
+                // Constrained fields have already been converted and checked:
+                writeln!(out, "  {field_name},")?;
+            }
             FieldType::UInt => {
                 // Numeric fields can be directly converted:
                 let numeric_value = assemble_numeric_field(layout, field)?;
                 writeln!(out, "  {field_name}: {numeric_value},")?;
             }
+            FieldType::SInt => {
+                // Signed fields are sign-extended by shifting the sign bit into the MSB and back:
+                let numeric_value = assemble_numeric_field(layout, field)?;
+                let width = field.bits.width();
+                let signed_type = rs_fitting_signed_type(width)?;
+                let shift = width.next_power_of_two().max(8) - width;
+                if shift == 0 {
+                    writeln!(out, "  {field_name}: ({numeric_value}) as {signed_type},")?;
+                } else {
+                    writeln!(out, "  {field_name}: ((({numeric_value}) as {signed_type}) << {shift}) >> {shift},")?;
+                }
+            }
             FieldType::Bool => {
                 // Bools require a simple conversion:
                 let numeric_value = assemble_numeric_field(layout, field)?;
@@ -526,7 +595,7 @@ fn generate_layout_impl_uint_conv(inp: &Input, out: &mut dyn Write, layout: &Lay
         } else {
             writeln!(out, "        let mut bytes = [0; {}];", layout.width_bytes())?;
             writeln!(out, "        bytes.copy_from_slice(&(value.to_le_bytes()[0..{}]));", layout.width_bytes())?;
-            writeln!(out, "        Self::from_le_bytes(bytes)")?;
+            writeln!(out, "        Self::from_le_bytes(&bytes)")?;
         }
         writeln!(out, "    }}")?;
         writeln!(out, "}}")?;
@@ -551,9 +620,170 @@ fn generate_layout_impl_uint_conv(inp: &Input, out: &mut dyn Write, layout: &Lay
     Ok(())
 }
 
+/// Conditions that are true if the given field value violates the constraints.
+fn constraint_checks(val: &str, constraints: &FieldConstraints) -> Vec<String> {
+    let mut checks = vec![];
+    match (constraints.min, constraints.max) {
+        (Some(min), Some(max)) => checks.push(format!("!(0x{min:X}..=0x{max:X}).contains(&{val})")),
+        (Some(min), None) => checks.push(format!("{val} < 0x{min:X}")),
+        (None, Some(max)) => checks.push(format!("{val} > 0x{max:X}")),
+        (None, None) => (),
+    }
+    if let Some(step) = constraints.step {
+        match constraints.min {
+            Some(min) => checks.push(format!("!({val} - 0x{min:X}).is_multiple_of(0x{step:X})")),
+            None => checks.push(format!("!{val}.is_multiple_of(0x{step:X})")),
+        }
+    }
+    match constraints.reserved.as_slice() {
+        [] => (),
+        [reserved] => checks.push(format!("{val} == 0x{reserved:X}")),
+        reserved => {
+            let reserved = reserved
+                .iter()
+                .map(|x| format!("0x{x:X}"))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            checks.push(format!("matches!({val}, {reserved})"));
+        }
+    }
+    checks
+}
+
+/// Generate physical unit conversion functions for all scaled fields.
+fn generate_layout_impl_scaling(out: &mut dyn Write, layout: &Layout) -> Result<(), Error> {
+    let scaled_fields: Vec<_> = layout.fields_with_content().filter(|x| x.scaling.is_some()).collect();
+    if scaled_fields.is_empty() {
+        return Ok(());
+    }
+
+    let struct_name = rs_pascalcase(&layout.name);
+    let mut out = IndentWriter::new(out, "    ");
+
+    writeln!(out)?;
+    writeln!(out, "impl {struct_name} {{")?;
+    out.increase_indent(1);
+
+    for (idx, field) in scaled_fields.iter().enumerate() {
+        let Some(scaling) = &field.scaling else {
+            continue;
+        };
+        let Some(range) = field.numeric_range() else {
+            continue;
+        };
+
+        let field_name = rs_snakecase(&field.name);
+        let field_type = register_layout_member_type(field)?;
+        let unit = &scaling.unit;
+        let unit_name = scaling.unit_name();
+        let conversion = scaling_str(scaling);
+
+        if idx != 0 {
+            writeln!(out)?;
+        }
+
+        // Field -> Physical:
+        match &scaling.conversion {
+            ScalingConversion::Linear { .. } => {
+                writeln!(out, "/// Convert `{field_name}` to {unit} (`{conversion}`).")?
+            }
+            ScalingConversion::Table(_) => writeln!(out, "/// Convert `{field_name}` to {unit} ({conversion}).")?,
+        }
+        match &scaling.conversion {
+            ScalingConversion::Linear { scale, offset } => {
+                // Unsigned members wider than 32 bits have no lossless conversion to i64. Validation ensures
+                // that the physical range fits, so a cast is safe:
+                let lossless = match field.accepts {
+                    FieldType::SInt => field.bits.width() <= 64,
+                    _ => field.bits.width() <= 32,
+                };
+                let (value, constness) = if lossless {
+                    (format!("i64::from(self.{field_name})"), "")
+                } else {
+                    (format!("self.{field_name} as i64"), "const ")
+                };
+                let scaled = match scale {
+                    1 => value,
+                    -1 => format!("-{value}"),
+                    scale => format!("{scale} * {value}"),
+                };
+                let physical = match offset {
+                    0 => scaled,
+                    offset => format!("{offset} + {scaled}"),
+                };
+                writeln!(out, "pub {constness}fn {field_name}_to_{unit_name}(&self) -> i64 {{")?;
+                writeln!(out, "    {physical}")?;
+                writeln!(out, "}}")?;
+            }
+            ScalingConversion::Table(table) => {
+                let table = table.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                if scaling.can_always_convert(field.bits.width()) {
+                    writeln!(out, "pub fn {field_name}_to_{unit_name}(&self) -> i64 {{")?;
+                    // Mask, since the member type may hold values that are too large for the field:
+                    let mask = field.bits.unpositioned_mask();
+                    if matches!(field.bits.width(), 8 | 16) {
+                        writeln!(out, "    [{table}][usize::from(self.{field_name})]")?;
+                    } else {
+                        writeln!(out, "    [{table}][usize::from(self.{field_name} & 0x{mask:X})]")?;
+                    }
+                } else {
+                    writeln!(out, "///")?;
+                    writeln!(out, "/// Returns `None` if the value has no physical equivalent.")?;
+                    writeln!(out, "pub fn {field_name}_to_{unit_name}(&self) -> Option<i64> {{")?;
+                    writeln!(out, "    [{table}].get(usize::from(self.{field_name})).copied()")?;
+                }
+                writeln!(out, "}}")?;
+            }
+        }
+
+        writeln!(out)?;
+
+        // Physical -> Field:
+        writeln!(out, "/// Convert {unit} to a `{field_name}` value.")?;
+        writeln!(out, "///")?;
+        writeln!(out, "/// Returns `None` if the given value cannot be represented exactly.")?;
+        writeln!(out, "pub fn {field_name}_from_{unit_name}({unit_name}: i64) -> Option<{field_type}> {{")?;
+        match &scaling.conversion {
+            ScalingConversion::Linear { scale, offset } => {
+                if *offset == 0 {
+                    writeln!(out, "    let val = {unit_name};")?;
+                } else {
+                    writeln!(out, "    let val = {unit_name}.checked_sub({offset})?;")?;
+                }
+                if *scale != 1 {
+                    writeln!(out, "    if val.checked_rem({scale})? != 0 {{")?;
+                    writeln!(out, "        return None;")?;
+                    writeln!(out, "    }}")?;
+                    writeln!(out, "    let val = val.checked_div({scale})?;")?;
+                }
+                // Values that fit the member type may still be too large for the field:
+                if !matches!(field.bits.width(), 8 | 16 | 32 | 64) {
+                    writeln!(out, "    if !({}..={}).contains(&val) {{", range.start(), range.end())?;
+                    writeln!(out, "        return None;")?;
+                    writeln!(out, "    }}")?;
+                }
+                writeln!(out, "    {field_type}::try_from(val).ok()")?;
+            }
+            ScalingConversion::Table(table) if table.len() == 1 => {
+                writeln!(out, "    ({unit_name} == {}).then_some(0)", table[0])?;
+            }
+            ScalingConversion::Table(table) => {
+                let table = table.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                writeln!(out, "    let idx = [{table}].iter().position(|x| *x == {unit_name})?;")?;
+                writeln!(out, "    {field_type}::try_from(idx).ok()")?;
+            }
+        }
+        writeln!(out, "}}")?;
+    }
+
+    out.decrease_indent(1);
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
 fn assemble_numeric_field(layout: &Layout, field: &LayoutField) -> Result<String, Error> {
     let field_raw_type = match &field.accepts {
-        FieldType::UInt => rs_fitting_unsigned_type(field.bits.width())?,
+        FieldType::UInt | FieldType::SInt => rs_fitting_unsigned_type(field.bits.width())?,
         FieldType::Bool => "u8".to_string(),
         FieldType::Enum(e) => rs_fitting_unsigned_type(e.bitwidth)?,
         FieldType::Fixed(_) => unreachable!(),
@@ -562,36 +792,48 @@ fn assemble_numeric_field(layout: &Layout, field: &LayoutField) -> Result<String
 
     let mut unpacked_value_parts: Vec<String> = vec![];
 
-    for byte in 0..layout.width_bytes() {
-        let Some(transform) = packed_byte_to_field_transform(
-            Endianess::Little,
-            field.bits.unpositioned_mask(),
-            field.bits.lsb_pos(),
-            byte,
-            layout.width_bytes(),
-        ) else {
-            continue;
-        };
-
-        let casted_value = if field_raw_type == "u8" {
-            format!("val[{byte}]")
-        } else {
-            format!("{field_raw_type}::from(val[{byte}])")
-        };
+    // Each range of a split field is assembled separately, and then shifted into position:
+    for (range, value_pos) in field.bits.pieces() {
+        let mut piece_parts: Vec<String> = vec![];
+
+        for byte in 0..layout.width_bytes() {
+            let Some(transform) = packed_byte_to_field_transform(
+                Endianess::Little,
+                range.unpositioned_mask(),
+                range.lsb_pos(),
+                byte,
+                layout.width_bytes(),
+            ) else {
+                continue;
+            };
+
+            let casted_value = if field_raw_type == "u8" {
+                format!("val[{byte}]")
+            } else {
+                format!("{field_raw_type}::from(val[{byte}])")
+            };
+
+            let masked = if transform.mask == 0xFF {
+                casted_value
+            } else {
+                format!("({casted_value} & 0x{:X})", transform.mask)
+            };
+
+            match &transform.shift {
+                Some((ShiftDirection::Left, amnt)) => piece_parts.push(format!("{masked} << {amnt}")),
+                Some((ShiftDirection::Right, amnt)) => piece_parts.push(format!("{masked} >> {amnt}")),
+                None => piece_parts.push(masked),
+            };
+        }
+        assert!(!piece_parts.is_empty());
 
-        let masked = if transform.mask == 0xFF {
-            casted_value
+        if value_pos == 0 {
+            unpacked_value_parts.append(&mut piece_parts);
         } else {
-            format!("({casted_value} & 0x{:X})", transform.mask)
-        };
-
-        match &transform.shift {
-            Some((ShiftDirection::Left, amnt)) => unpacked_value_parts.push(format!("{masked} << {amnt}")),
-            Some((ShiftDirection::Right, amnt)) => unpacked_value_parts.push(format!("{masked} >> {amnt}")),
-            None => unpacked_value_parts.push(masked),
-        };
+            let piece_value = remove_wrapping_parens(&piece_parts.join(" | "));
+            unpacked_value_parts.push(format!("({piece_value}) << {value_pos}"));
+        }
     }
-    assert!(!unpacked_value_parts.is_empty());
 
     Ok(remove_wrapping_parens(&unpacked_value_parts.join(" | ")))
 }
@@ -1,6 +1,7 @@
 mod enums;
 mod layouts;
 mod registers;
+mod virtual_fields;
 
 use std::fmt::Write;
 
@@ -19,8 +20,8 @@ use clap::Parser;
 use self::layouts::LayoutStructKind;
 
 use super::{
-    CONVERSION_TRAITS, generate_doc_comment, rs_fitting_unsigned_type, rs_generate_header_comment, rs_header_comment,
-    rs_pascalcase, rs_snakecase,
+    CONVERSION_TRAITS, MMIO_TYPES, generate_doc_comment, rs_fitting_signed_type, rs_fitting_unsigned_type,
+    rs_generate_header_comment, rs_header_comment, rs_pascalcase, rs_snakecase,
 };
 
 // ====== Generator Opts =======================================================
@@ -92,6 +93,17 @@ pub struct GeneratorOpts {
     #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().generate_uint_conversion))]
     #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
     pub generate_uint_conversion: bool,
+
+    /// Generate memory-mapped register block structs.
+    ///
+    /// For every register block, a `#[repr(C)]` struct with a volatile cell
+    /// for each member at its offset is generated, alongside a pointer
+    /// constant for every instance of the block.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().register_block_mmio))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub register_block_mmio: bool,
 }
 
 impl Default for GeneratorOpts {
@@ -104,6 +116,7 @@ impl Default for GeneratorOpts {
             add_attribute: vec![],
             external_traits: None,
             generate_uint_conversion: true,
+            register_block_mmio: false,
         }
     }
 }
@@ -124,7 +137,7 @@ pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) ->
         address_type.clone()
     } else {
         let max_addr = map.registers.values().map(|x| x.adr).max().unwrap_or(0);
-        rs_fitting_unsigned_type(msb_pos(max_addr) + 1)?
+        rs_fitting_unsigned_type(msb_pos(max_addr.into()) + 1)?
     };
 
     // Gather derives to be applied to all enums.
@@ -147,6 +160,9 @@ pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) ->
     // ===== Traits: =====
     if inp.opts.external_traits.is_none() {
         generate_traits(&mut out)?;
+        if inp.opts.register_block_mmio {
+            generate_mmio_types(&mut out)?;
+        }
     }
 
     // ===== Registers: =====
@@ -187,6 +203,13 @@ pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) ->
         layouts::generate_layout_impls(&mut out, &inp, layout)?;
     }
 
+    // ===== Virtual fields: =====
+    for field in inp.map.virtual_fields.values() {
+        writeln!(&mut out)?;
+        writeln!(&mut out, "{}", &rs_header_comment(&format!("`{}` Virtual Field", field.name)))?;
+        virtual_fields::generate_virtual_field(&mut out, field)?;
+    }
+
     Ok(())
 }
 
@@ -195,6 +218,10 @@ fn generate_header(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
     writeln!(out, "#![allow(clippy::unnecessary_cast)]")?;
     writeln!(out, "#![allow(clippy::module_name_repetitions)]")?;
     writeln!(out, "#![allow(clippy::precedence)]")?;
+    writeln!(out, "#![allow(clippy::cast_sign_loss)]")?;
+    writeln!(out, "#![allow(clippy::cast_possible_wrap)]")?;
+    writeln!(out, "#![allow(clippy::similar_names)]")?;
+    writeln!(out, "#![allow(clippy::unreadable_literal)]")?;
     writeln!(out, "#![allow(unused_imports)]")?;
     for attr in &inp.opts.add_attribute {
         writeln!(out, "#![{}]", attr)?;
@@ -267,6 +294,15 @@ fn generate_traits(out: &mut dyn Write) -> Result<(), Error> {
     Ok(())
 }
 
+/// Memory-mapped register types section
+fn generate_mmio_types(out: &mut dyn Write) -> Result<(), Error> {
+    writeln!(out)?;
+    rs_generate_header_comment(out, "Memory-mapped Register Types")?;
+    writeln!(out)?;
+    write!(out, "{}", MMIO_TYPES)?;
+    Ok(())
+}
+
 /// Decide trait prefix. If an external override is given, use that.
 /// Otherwise, use the local definition (Which may be in the parent
 /// module)
@@ -2,7 +2,13 @@ use std::fmt::Write;
 
 use super::*;
 
-use crate::{builtin::rs::array_literal, error::Error, utils::Endianess, writer::indent_writer::IndentWriter};
+use crate::{
+    builtin::rs::{array_literal, generate_extended_doc_comment},
+    error::Error,
+    regmap::{TypeAdr, TypeBitwidth},
+    utils::Endianess,
+    writer::indent_writer::IndentWriter,
+};
 
 use super::rs_pascalcase;
 
@@ -11,19 +17,24 @@ pub fn generate_register(out: &mut dyn Write, inp: &Input, register: &Register)
 
     rs_generate_header_comment(out, &format!("`{}` Register", register.name))?;
 
-    if register.layout.is_local && register.from_block.is_none() {
+    if is_newtype(register) {
+        // Generate a newtype to contain the register properties:
+        generate_register_newtype(out, inp, register)?;
+        generate_register_impl(out, inp, register, true)?;
+    } else {
         // If the layout is local to this register, generate it and associate all properties to it:
         layouts::generate_layout(out, inp, &register.layout, &LayoutStructKind::RegisterLayout(register))?;
         generate_register_impl(out, inp, register, false)?;
-    } else {
-        // Otherwise generate a newtype to contain the register properties:
-        generate_register_newtype(out, inp, register)?;
-        generate_register_impl(out, inp, register, true)?;
     }
 
     Ok(())
 }
 
+/// Registers without a layout of their own are represented by a newtype wrapping their layout.
+pub fn is_newtype(register: &Register) -> bool {
+    !(register.layout.is_local && register.from_block.is_none())
+}
+
 pub fn generate_register_newtype(out: &mut dyn Write, inp: &Input, register: &Register) -> Result<(), Error> {
     // Struct doc comment:
     writeln!(out)?;
@@ -122,6 +133,11 @@ pub fn generate_register_block(out: &mut dyn Write, inp: &Input, block: &Registe
     // Shared register block properties
     generate_register_block_properties(out, inp, block)?;
 
+    // Memory-mapped register block struct:
+    if inp.opts.register_block_mmio {
+        generate_register_block_mmio(out, inp, block)?;
+    }
+
     // Members:
     for member in block.members.values() {
         writeln!(out)?;
@@ -213,6 +229,104 @@ pub fn generate_register_block_properties(
     Ok(())
 }
 
+pub fn generate_register_block_mmio(out: &mut dyn Write, inp: &Input, block: &RegisterBlock) -> Result<(), Error> {
+    let block_name = &block.name;
+    let struct_name = rs_pascalcase(&format!("{block_name}_registers"));
+    let const_block_name = rs_const(block_name);
+    let trait_prefix = trait_prefix(inp);
+
+    let mut members: Vec<_> = block.members.values().collect();
+    members.sort_by_key(|x| x.offset);
+
+    // Struct proper:
+    writeln!(out)?;
+    writeln!(out, "/// `{block_name}` memory-mapped register block")?;
+    writeln!(out, "///")?;
+    writeln!(out, "/// Volatile overlay of a `{block_name}` instance's memory region.")?;
+    writeln!(out, "#[repr(C)]")?;
+    writeln!(out, "pub struct {struct_name} {{")?;
+
+    let mut pos: TypeAdr = 0;
+    let mut reserved_cnt = 0;
+    for member in &members {
+        let width_bytes = member.layout.width_bytes();
+        let (cell_type, align) = mmio_cell_type(width_bytes);
+
+        if member.offset < pos {
+            return Err(Error::GeneratorError(format!(
+                "Register block {block_name} member {} at offset 0x{:x} overlaps with previous member.",
+                member.name_raw, member.offset
+            )));
+        }
+
+        if member.offset % align != 0 {
+            return Err(Error::GeneratorError(format!(
+                "Register block {block_name} member {} at offset 0x{:x} is not aligned to its {width_bytes}-byte access width.",
+                member.name_raw, member.offset
+            )));
+        }
+
+        if member.offset > pos {
+            writeln!(out, "    _reserved{reserved_cnt}: [u8; 0x{:x}],", member.offset - pos)?;
+            reserved_cnt += 1;
+        }
+
+        generate_extended_doc_comment(
+            out,
+            &member.docs,
+            "    ",
+            &[
+                &format!("Offset: 0x{:X}", member.offset),
+                &format!("Uses [`{}`] layout.", rs_pascalcase(&member.layout.name)),
+            ],
+        )?;
+        writeln!(out, "    pub {}: {trait_prefix}VolatileCell<{cell_type}>,", rs_snakecase(&member.name_raw))?;
+
+        pos = member.offset + TypeAdr::from(width_bytes);
+    }
+
+    writeln!(out, "}}")?;
+
+    // Offset checks:
+    if !members.is_empty() {
+        writeln!(out)?;
+        for member in &members {
+            writeln!(
+                out,
+                "const _: () = assert!(core::mem::offset_of!({struct_name}, {}) == 0x{:x});",
+                rs_snakecase(&member.name_raw),
+                member.offset
+            )?;
+        }
+    }
+
+    // Instance pointers:
+    for instance in block.instances.values() {
+        let instance_name = &instance.name;
+        let const_instance_name = rs_const(instance_name);
+        writeln!(out)?;
+        writeln!(out, "/// Memory-mapped registers of `{block_name}` instance `{instance_name}`")?;
+        writeln!(
+            out,
+            "pub const {const_block_name}_INSTANCE_{const_instance_name}_PTR: *const {struct_name} = 0x{:x} as *const {struct_name};",
+            instance.adr
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Volatile cell content type and alignment for a register of given width.
+fn mmio_cell_type(width_bytes: TypeBitwidth) -> (String, TypeAdr) {
+    match width_bytes {
+        1 => ("u8".to_string(), 1),
+        2 => ("u16".to_string(), 2),
+        4 => ("u32".to_string(), 4),
+        8 => ("u64".to_string(), 8),
+        _ => (format!("[u8; {width_bytes}]"), 1),
+    }
+}
+
 fn generate_reset_val(
     out: &mut IndentWriter,
     layout: &Layout,
@@ -229,7 +343,7 @@ fn generate_reset_val(
     out.push_indent();
 
     for field in layout.fields_with_content() {
-        let field_val = (val & field.bits.mask()) >> (field.bits.lsb_pos());
+        let field_val = field.bits.extract(val);
 
         write!(out, "{}: ", rs_snakecase(&field.name))?;
 
@@ -240,6 +354,9 @@ fn generate_reset_val(
                 crate::regmap::DecodedField::UInt(val) => {
                     write!(out, "0x{:X}", val)?;
                 }
+                crate::regmap::DecodedField::SInt(val) => {
+                    write!(out, "{}", val)?;
+                }
                 crate::regmap::DecodedField::Bool(b) => {
                     write!(out, "{}", b)?;
                 }
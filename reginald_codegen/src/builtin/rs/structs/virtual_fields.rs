@@ -0,0 +1,140 @@
+use std::fmt::Write;
+
+use crate::{
+    builtin::rs::generate_extended_doc_comment,
+    error::Error,
+    regmap::{VirtualField, VirtualFieldPart},
+};
+
+use super::{registers::is_newtype, rs_fitting_signed_type, rs_fitting_unsigned_type, rs_pascalcase, rs_snakecase};
+
+pub fn generate_virtual_field(out: &mut dyn Write, field: &VirtualField) -> Result<(), Error> {
+    let func_prefix = rs_snakecase(&field.name);
+    let width = field.width();
+    let raw_type = rs_fitting_unsigned_type(width)?;
+    let value_type = if field.signed {
+        rs_fitting_signed_type(width)?
+    } else {
+        raw_type.clone()
+    };
+
+    // Parts listing for doc comments:
+    let mut parts_doc = vec![String::from("Parts, most significant first:")];
+    for (part, value_pos) in field.pieces() {
+        let part_width = part.field().bits.width();
+        let value_bits = if part_width == 1 {
+            format!("{value_pos}")
+        } else {
+            format!("{}:{value_pos}", value_pos + part_width - 1)
+        };
+        parts_doc.push(format!("- `{}`: Bits `[{value_bits}]`", part.name()));
+    }
+    let parts_doc: Vec<&str> = parts_doc.iter().map(String::as_str).collect();
+
+    // ==== Assemble ====:
+    let args: Vec<String> = field
+        .registers()
+        .iter()
+        .map(|x| format!("{}: &{}", rs_snakecase(&x.name), rs_pascalcase(&x.name)))
+        .collect();
+
+    let mut parts: Vec<String> = vec![];
+    for (part, value_pos) in field.pieces() {
+        let part_width = part.field().bits.width();
+        let part_type = rs_fitting_unsigned_type(part_width)?;
+
+        let mut value = part_access(part);
+        if !matches!(part_width, 8 | 16 | 32 | 64) {
+            value = format!("{value} & 0x{:X}", part.field().bits.unpositioned_mask());
+        }
+        if part_type != raw_type {
+            if value.contains(' ') {
+                value = format!("({value})");
+            }
+            value = format!("{value} as {raw_type}");
+        }
+        if (value_pos != 0 || field.parts.len() > 1) && value.contains(' ') {
+            value = format!("({value})");
+        }
+        if value_pos != 0 {
+            value = format!("{value} << {value_pos}");
+        }
+        parts.push(value);
+    }
+    let raw_value = parts.join(" | ");
+
+    let value = if field.signed {
+        // Sign-extend by shifting the sign bit into the MSB and back:
+        let shift = width.next_power_of_two().max(8) - width;
+        if shift == 0 {
+            format!("({raw_value}) as {value_type}")
+        } else {
+            format!("((({raw_value}) as {value_type}) << {shift}) >> {shift}")
+        }
+    } else {
+        raw_value
+    };
+
+    writeln!(out)?;
+    generate_extended_doc_comment(out, &field.docs, "", &parts_doc)?;
+    writeln!(out, "///")?;
+    writeln!(out, "/// Assembles the value from the given registers.")?;
+    writeln!(out, "#[allow(clippy::cast_lossless)]")?;
+    writeln!(out, "pub const fn {func_prefix}_assemble({}) -> {value_type} {{", args.join(", "))?;
+    writeln!(out, "    {value}")?;
+    writeln!(out, "}}")?;
+
+    // ==== Split ====:
+    let args: Vec<String> = field
+        .registers()
+        .iter()
+        .map(|x| format!("{}: &mut {}", rs_snakecase(&x.name), rs_pascalcase(&x.name)))
+        .collect();
+
+    writeln!(out)?;
+    generate_extended_doc_comment(out, &field.docs, "", &parts_doc)?;
+    writeln!(out, "///")?;
+    writeln!(out, "/// Splits the value into the given registers. All other fields are left untouched.")?;
+    writeln!(out, "#[allow(clippy::cast_possible_truncation)]")?;
+    writeln!(out, "pub const fn {func_prefix}_split(value: {value_type}, {}) {{", args.join(", "))?;
+    if field.signed {
+        writeln!(out, "    let value = value as {raw_type};")?;
+    }
+    for (part, value_pos) in field.pieces() {
+        let part_width = part.field().bits.width();
+        let part_type = rs_fitting_unsigned_type(part_width)?;
+
+        let mut value = if value_pos == 0 {
+            String::from("value")
+        } else {
+            format!("value >> {value_pos}")
+        };
+        if !matches!(part_width, 8 | 16 | 32 | 64) {
+            if value_pos != 0 {
+                value = format!("({value})");
+            }
+            value = format!("{value} & 0x{:X}", part.field().bits.unpositioned_mask());
+        }
+        if part_type != raw_type {
+            if value != "value" {
+                value = format!("({value})");
+            }
+            value = format!("{value} as {part_type}");
+        }
+        writeln!(out, "    {} = {value};", part_access(part))?;
+    }
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+/// Expression accessing the field holding a part, through the register argument.
+fn part_access(part: &VirtualFieldPart) -> String {
+    let register = rs_snakecase(&part.register.name);
+    let field = rs_snakecase(&part.field);
+    if is_newtype(&part.register) {
+        format!("{register}.0.{field}")
+    } else {
+        format!("{register}.{field}")
+    }
+}
@@ -0,0 +1,175 @@
+pub mod regfile;
+pub mod uvm_ral;
+
+use std::{fmt::Write, sync::LazyLock};
+
+use regex::Regex;
+use reginald_utils::str_pad_to_length;
+
+use crate::{
+    error::Error,
+    regmap::{Docs, RegisterMap, TypeBitwidth, TypeValue},
+};
+
+// ====== Generator Utils ======================================================
+
+/// Generate file header comment.
+fn sv_generate_header_comment(out: &mut dyn Write, map: &RegisterMap, generator: &str) -> Result<(), Error> {
+    writeln!(out, "// {}", map.name)?;
+    if let Some(input_file) = &map.from_file {
+        writeln!(out, "// Do not edit directly: generated using reginald from {}.", input_file.to_string_lossy())?;
+    } else {
+        writeln!(out, "// Do not edit directly: generated using reginald.")?;
+    }
+    writeln!(out, "//")?;
+    writeln!(out, "// Generator: {generator}")?;
+    if !map.docs.is_empty() {
+        writeln!(out, "//")?;
+        write!(out, "{}", map.docs.as_multiline("// "))?;
+    }
+    if let Some(author) = &map.author {
+        writeln!(out, "//")?;
+        writeln!(out, "// Listing file author: {author}")?;
+    }
+    if let Some(notice) = &map.notice {
+        writeln!(out, "//")?;
+        writeln!(out, "// Listing file notice:")?;
+        for line in notice.lines() {
+            writeln!(out, "//   {line}")?;
+        }
+    }
+    Ok(())
+}
+
+fn sv_section_header_comment(title: &str) -> String {
+    str_pad_to_length(&format!("// ==== {title} "), '=', 80)
+}
+
+fn sv_generate_section_header_comment(out: &mut dyn Write, prefix: &str, title: &str) -> Result<(), Error> {
+    writeln!(out, "{prefix}{}", sv_section_header_comment(title))?;
+    Ok(())
+}
+
+fn sv_generate_doc_comment(out: &mut dyn Write, docs: &Docs, prefix: &str, extra: &[&str]) -> Result<(), Error> {
+    let mut lines: Vec<String> = docs.as_multiline("").lines().map(String::from).collect();
+    if !lines.is_empty() && !extra.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(extra.iter().map(|x| x.to_string()));
+
+    for line in lines {
+        if line.is_empty() {
+            writeln!(out, "{prefix}//")?;
+        } else {
+            writeln!(out, "{prefix}// {line}")?;
+        }
+    }
+    Ok(())
+}
+
+const SV_KEYWORDS: &[&str] = &[
+    "alias",
+    "always",
+    "and",
+    "assert",
+    "assign",
+    "begin",
+    "bit",
+    "break",
+    "buf",
+    "byte",
+    "case",
+    "class",
+    "const",
+    "constraint",
+    "continue",
+    "cover",
+    "default",
+    "disable",
+    "do",
+    "edge",
+    "else",
+    "end",
+    "enum",
+    "event",
+    "extends",
+    "extern",
+    "final",
+    "for",
+    "force",
+    "foreach",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "if",
+    "initial",
+    "inout",
+    "input",
+    "int",
+    "integer",
+    "interface",
+    "local",
+    "logic",
+    "longint",
+    "module",
+    "new",
+    "not",
+    "null",
+    "or",
+    "output",
+    "package",
+    "parameter",
+    "posedge",
+    "negedge",
+    "rand",
+    "randc",
+    "real",
+    "reg",
+    "release",
+    "repeat",
+    "return",
+    "shortint",
+    "signed",
+    "static",
+    "string",
+    "struct",
+    "super",
+    "task",
+    "this",
+    "time",
+    "type",
+    "typedef",
+    "union",
+    "unique",
+    "unsigned",
+    "var",
+    "virtual",
+    "void",
+    "wait",
+    "while",
+    "wire",
+    "with",
+    "xor",
+];
+
+/// Lower-case SystemVerilog identifier, with keywords escaped by a trailing underscore.
+fn sv_ident(s: &str) -> String {
+    let s = sv_sanitize(s).to_lowercase();
+    if SV_KEYWORDS.contains(&s.as_str()) { s + "_" } else { s }
+}
+
+fn sv_macro(s: &str) -> String {
+    sv_sanitize(s).to_uppercase()
+}
+
+static SV_SANITIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^_a-zA-Z0-9]").unwrap());
+
+fn sv_sanitize(s: &str) -> String {
+    SV_SANITIZE_REGEX.replace_all(s, "_").into()
+}
+
+/// Sized hexadecimal literal.
+fn sv_hex_literal(width: TypeBitwidth, val: TypeValue) -> String {
+    format!("{width}'h{val:X}")
+}
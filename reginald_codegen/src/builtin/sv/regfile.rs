@@ -0,0 +1,436 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+#[cfg(feature = "clap")]
+use clap::{Parser, ValueEnum};
+
+use crate::{
+    bits::msb_pos,
+    error::Error,
+    regmap::{AccessMode, FieldType, FlattenedLayoutField, Register, RegisterMap, TypeAdr, TypeBitwidth, TypeValue},
+};
+
+use super::{sv_generate_header_comment, sv_generate_section_header_comment, sv_hex_literal, sv_ident, sv_macro};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Name of the generated module.
+    ///
+    /// If none is specified, `<map name>_regfile` is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub module_name: Option<String>,
+
+    /// Bus interface through which the register file is accessed.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(value_enum, default_value_t = Self::default().bus))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub bus: BusInterface,
+
+    /// Width of the bus address, in bits.
+    ///
+    /// If none is specified, the smallest width capable of representing
+    /// the largest address will be used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub addr_width: Option<TypeBitwidth>,
+
+    /// Width of the bus data, in bits.
+    ///
+    /// If none is specified, the width of the widest register (rounded
+    /// up to a full byte) will be used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub data_width: Option<TypeBitwidth>,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            module_name: None,
+            bus: BusInterface::Apb,
+            addr_width: None,
+            data_width: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum BusInterface {
+    /// AMBA APB slave with zero wait states.
+    Apb,
+    /// Generic valid/ready request and response channels.
+    ValidReady,
+}
+
+// ====== Generator ============================================================
+
+struct Input<'a> {
+    opts: &'a GeneratorOpts,
+    module_name: String,
+    addr_width: TypeBitwidth,
+    data_width: TypeBitwidth,
+    registers: Vec<&'a Register>,
+}
+
+/// Port in the module header.
+struct Port {
+    comment: Option<String>,
+    decl: String,
+}
+
+/// How a single field is implemented in the register file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum FieldImpl {
+    /// Constant value, read back through the bus.
+    Fixed(TypeValue),
+    /// Stored in the register file and driven to hardware.
+    Stored { readable: bool },
+    /// Driven by hardware and read back through the bus.
+    Input,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    map.require_contiguous_fields("sv-regfile")?;
+
+    // Every address decodes to exactly one register:
+    let mut registers: BTreeMap<TypeAdr, &Register> = BTreeMap::new();
+    for register in map.registers.values() {
+        if let Some(other) = registers.insert(register.adr, register) {
+            return Err(Error::GeneratorError(format!(
+                "Registers {} and {} are both located at address 0x{:X}. Register files require unique addresses",
+                other.name, register.name, register.adr
+            )));
+        }
+    }
+    let registers: Vec<&Register> = registers.into_values().collect();
+
+    let max_width = map.max_register_width();
+    let data_width = match opts.data_width {
+        Some(data_width) => data_width,
+        None => max_width.div_ceil(8).max(1) * 8,
+    };
+    if let Some(register) = registers.iter().find(|x| x.layout.bitwidth > data_width) {
+        return Err(Error::GeneratorError(format!(
+            "Register {} is {} bits wide, which exceeds the {data_width}-bit data bus",
+            register.name, register.layout.bitwidth
+        )));
+    }
+
+    let max_addr = registers.iter().map(|x| x.adr).max().unwrap_or(0);
+    let addr_width = match opts.addr_width {
+        Some(addr_width) => addr_width,
+        None => msb_pos(max_addr.into()) + 1,
+    };
+    if addr_width < msb_pos(max_addr.into()) + 1 {
+        return Err(Error::GeneratorError(format!(
+            "Address 0x{max_addr:X} cannot be represented with a {addr_width}-bit address bus"
+        )));
+    }
+
+    let module_name = match &opts.module_name {
+        Some(module_name) => module_name.clone(),
+        None => format!("{}_regfile", sv_ident(&map.name)),
+    };
+
+    let inp = Input {
+        opts,
+        module_name,
+        addr_width,
+        data_width,
+        registers,
+    };
+
+    sv_generate_header_comment(out, map, "sv-regfile")?;
+    generate_module_header(out, &inp)?;
+
+    // ===== Address Map: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Address Map")?;
+    writeln!(out)?;
+    for register in &inp.registers {
+        writeln!(
+            out,
+            "  localparam {} {} = {};",
+            sv_logic(inp.addr_width),
+            addr_param_name(register),
+            sv_hex_literal(inp.addr_width, register.adr.into())
+        )?;
+    }
+
+    // ===== Bus Interface: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Bus Interface")?;
+    generate_bus_interface(out, &inp)?;
+
+    // ===== Registers: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Registers")?;
+    for register in &inp.registers {
+        generate_register(out, register)?;
+    }
+
+    // ===== Read Decode: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Read Decode")?;
+    generate_read_decode(out, &inp)?;
+
+    writeln!(out)?;
+    writeln!(out, "endmodule : {}", inp.module_name)?;
+
+    Ok(())
+}
+
+fn generate_module_header(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    let addr = sv_logic(inp.addr_width);
+    let data = sv_logic(inp.data_width);
+
+    let mut ports = vec![];
+    let mut port = |comment: Option<String>, decl: String| ports.push(Port { comment, decl });
+
+    port(None, "input  logic clk".to_string());
+    port(None, "input  logic rst_n".to_string());
+
+    match inp.opts.bus {
+        BusInterface::Apb => {
+            port(Some("APB interface:".to_string()), "input  logic psel".to_string());
+            port(None, "input  logic penable".to_string());
+            port(None, "input  logic pwrite".to_string());
+            port(None, format!("input  {addr} paddr"));
+            port(None, format!("input  {data} pwdata"));
+            port(None, format!("output {data} prdata"));
+            port(None, "output logic pready".to_string());
+            port(None, "output logic pslverr".to_string());
+        }
+        BusInterface::ValidReady => {
+            port(Some("Request channel:".to_string()), "input  logic req_valid".to_string());
+            port(None, "output logic req_ready".to_string());
+            port(None, "input  logic req_write".to_string());
+            port(None, format!("input  {addr} req_addr"));
+            port(None, format!("input  {data} req_wdata"));
+            port(Some("Response channel:".to_string()), "output logic rsp_valid".to_string());
+            port(None, "input  logic rsp_ready".to_string());
+            port(None, format!("output {data} rsp_rdata"));
+            port(None, "output logic rsp_error".to_string());
+        }
+    }
+
+    for register in &inp.registers {
+        let mut comment = Some(format!("`{}` register:", register.name));
+        for field in register.layout.flattened_fields() {
+            let name = field_signal_name(register, &field);
+            let logic = sv_logic(field.bits.width());
+            let decl = match field_impl(&field) {
+                FieldImpl::Fixed(_) => continue,
+                FieldImpl::Stored { .. } => format!("output {logic} {name}_o"),
+                FieldImpl::Input => format!("input  {logic} {name}_i"),
+            };
+            port(comment.take(), decl);
+        }
+        if register_is_writable(register) {
+            port(comment.take(), format!("output logic {}_wr_o", sv_ident(&register.name)));
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(out, "module {} (", inp.module_name)?;
+    let port_cnt = ports.len();
+    for (i, port) in ports.into_iter().enumerate() {
+        if let Some(comment) = port.comment {
+            writeln!(out)?;
+            writeln!(out, "  // {comment}")?;
+        }
+        let sep = if i + 1 == port_cnt { "" } else { "," };
+        writeln!(out, "  {}{sep}", port.decl)?;
+    }
+    writeln!(out, ");")?;
+
+    Ok(())
+}
+
+fn generate_bus_interface(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "  {} addr;", sv_logic(inp.addr_width))?;
+    writeln!(out, "  {} wdata;", sv_logic(inp.data_width))?;
+    writeln!(out, "  {} rdata;", sv_logic(inp.data_width))?;
+    writeln!(out, "  logic wr_en;")?;
+    writeln!(out, "  logic hit;")?;
+    writeln!(out)?;
+
+    match inp.opts.bus {
+        BusInterface::Apb => {
+            writeln!(out, "  assign addr    = paddr;")?;
+            writeln!(out, "  assign wdata   = pwdata;")?;
+            writeln!(out, "  assign wr_en   = psel && penable && pwrite;")?;
+            writeln!(out, "  assign prdata  = rdata;")?;
+            writeln!(out, "  assign pready  = 1'b1;")?;
+            writeln!(out, "  assign pslverr = psel && penable && !hit;")?;
+        }
+        BusInterface::ValidReady => {
+            writeln!(out, "  assign addr      = req_addr;")?;
+            writeln!(out, "  assign wdata     = req_wdata;")?;
+            writeln!(out, "  assign req_ready = !rsp_valid || rsp_ready;")?;
+            writeln!(out, "  assign wr_en     = req_valid && req_ready && req_write;")?;
+            writeln!(out)?;
+            writeln!(out, "  always_ff @(posedge clk or negedge rst_n) begin")?;
+            writeln!(out, "    if (!rst_n) begin")?;
+            writeln!(out, "      rsp_valid <= 1'b0;")?;
+            writeln!(out, "      rsp_rdata <= '0;")?;
+            writeln!(out, "      rsp_error <= 1'b0;")?;
+            writeln!(out, "    end else if (req_valid && req_ready) begin")?;
+            writeln!(out, "      rsp_valid <= 1'b1;")?;
+            writeln!(out, "      rsp_rdata <= req_write ? '0 : rdata;")?;
+            writeln!(out, "      rsp_error <= !hit;")?;
+            writeln!(out, "    end else if (rsp_ready) begin")?;
+            writeln!(out, "      rsp_valid <= 1'b0;")?;
+            writeln!(out, "    end")?;
+            writeln!(out, "  end")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_register(out: &mut dyn Write, register: &Register) -> Result<(), Error> {
+    let reg_name = sv_ident(&register.name);
+    let fields = register.layout.flattened_fields();
+    let stored: Vec<_> = fields
+        .iter()
+        .filter(|x| matches!(field_impl(x), FieldImpl::Stored { .. }))
+        .collect();
+
+    writeln!(out)?;
+    writeln!(out, "  // `{}` register (0x{:X}):", register.name, register.adr)?;
+    if stored.is_empty() {
+        writeln!(out, "  // No storage.")?;
+        return Ok(());
+    }
+
+    // Storage and write strobe:
+    writeln!(out, "  logic {reg_name}_wr;")?;
+    for field in &stored {
+        writeln!(out, "  {} {}_q;", sv_logic(field.bits.width()), field_signal_name(register, field))?;
+    }
+    writeln!(out)?;
+    writeln!(out, "  assign {reg_name}_wr   = wr_en && (addr == {});", addr_param_name(register))?;
+    writeln!(out, "  assign {reg_name}_wr_o = {reg_name}_wr;")?;
+    for field in &stored {
+        let name = field_signal_name(register, field);
+        writeln!(out, "  assign {name}_o = {name}_q;")?;
+    }
+
+    // Update logic:
+    writeln!(out)?;
+    writeln!(out, "  always_ff @(posedge clk or negedge rst_n) begin")?;
+    writeln!(out, "    if (!rst_n) begin")?;
+    for field in &stored {
+        let reset = register
+            .reset_val
+            .map(|x| (x >> field.bits.lsb_pos()) & field.bits.unpositioned_mask())
+            .unwrap_or(0);
+        writeln!(
+            out,
+            "      {}_q <= {};",
+            field_signal_name(register, field),
+            sv_hex_literal(field.bits.width(), reset)
+        )?;
+    }
+    writeln!(out, "    end else if ({reg_name}_wr) begin")?;
+    for field in &stored {
+        writeln!(out, "      {}_q <= wdata{};", field_signal_name(register, field), sv_select(field))?;
+    }
+    writeln!(out, "    end")?;
+    writeln!(out, "  end")?;
+
+    Ok(())
+}
+
+fn generate_read_decode(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "  always_comb begin")?;
+    writeln!(out, "    rdata = '0;")?;
+    writeln!(out, "    hit   = 1'b1;")?;
+    writeln!(out, "    case (addr)")?;
+    for register in &inp.registers {
+        let mut lines = vec![];
+        for field in register.layout.flattened_fields() {
+            let name = field_signal_name(register, &field);
+            let value = match field_impl(&field) {
+                FieldImpl::Fixed(fixed) => sv_hex_literal(field.bits.width(), fixed),
+                FieldImpl::Stored { readable: true } => format!("{name}_q"),
+                FieldImpl::Stored { readable: false } => continue,
+                FieldImpl::Input => format!("{name}_i"),
+            };
+            lines.push(format!("rdata{} = {value};", sv_select(&field)));
+        }
+
+        writeln!(out, "      {}: begin", addr_param_name(register))?;
+        for line in lines {
+            writeln!(out, "        {line}")?;
+        }
+        writeln!(out, "      end")?;
+    }
+    writeln!(out, "      default: hit = 1'b0;")?;
+    writeln!(out, "    endcase")?;
+    writeln!(out, "  end")?;
+
+    Ok(())
+}
+
+// ====== Generator Utils ======================================================
+
+fn field_impl(field: &FlattenedLayoutField) -> FieldImpl {
+    if let FieldType::Fixed(fixed) = &field.field.accepts {
+        return FieldImpl::Fixed(*fixed);
+    }
+
+    let readable = field.field.access.as_ref().is_none_or(|x| x.contains(&AccessMode::R));
+    let writable = field.field.access.as_ref().is_none_or(|x| x.contains(&AccessMode::W));
+
+    if writable || !readable {
+        FieldImpl::Stored { readable }
+    } else {
+        FieldImpl::Input
+    }
+}
+
+fn register_is_writable(register: &Register) -> bool {
+    register
+        .layout
+        .flattened_fields()
+        .iter()
+        .any(|x| matches!(field_impl(x), FieldImpl::Stored { .. }))
+}
+
+fn field_signal_name(register: &Register, field: &FlattenedLayoutField) -> String {
+    sv_ident(&format!("{}_{}", register.name, field.name.join("_")))
+}
+
+fn addr_param_name(register: &Register) -> String {
+    format!("{}_ADDR", sv_macro(&register.name))
+}
+
+/// `logic` type of a signal with the given width.
+fn sv_logic(width: TypeBitwidth) -> String {
+    if width == 1 {
+        "logic".to_string()
+    } else {
+        format!("logic [{}:0]", width - 1)
+    }
+}
+
+/// Bit-select of a field within the register.
+fn sv_select(field: &FlattenedLayoutField) -> String {
+    if field.bits.width() == 1 {
+        format!("[{}]", field.bits.lsb_pos())
+    } else {
+        format!("[{}:{}]", field.bits.msb_pos(), field.bits.lsb_pos())
+    }
+}
@@ -0,0 +1,453 @@
+use std::fmt::Write;
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+
+use crate::{
+    bits::bitwidth_to_width_bytes,
+    error::Error,
+    regmap::{
+        AccessMode, Docs, FieldType, FlattenedLayoutField, Layout, RegisterBlock, RegisterBlockMember, RegisterMap,
+        TypeAdr, TypeBitwidth, TypeValue,
+    },
+    utils::Endianess,
+};
+
+use super::{
+    sv_generate_doc_comment, sv_generate_header_comment, sv_generate_section_header_comment, sv_hex_literal, sv_ident,
+    sv_macro,
+};
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Name of the generated package.
+    ///
+    /// If none is specified, `<map name>_ral_pkg` is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub package_name: Option<String>,
+
+    /// Endianess of the register maps.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(value_enum, default_value_t = Self::default().endian))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub endian: Endianess,
+
+    /// Width of the bus the register maps are accessed through, in bytes.
+    ///
+    /// If none is specified, the width of the widest register is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Set))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub bus_width_bytes: Option<TypeBitwidth>,
+}
+
+impl Default for GeneratorOpts {
+    fn default() -> Self {
+        Self {
+            package_name: None,
+            endian: Endianess::Little,
+            bus_width_bytes: None,
+        }
+    }
+}
+
+// ====== Generator ============================================================
+
+struct Input<'a> {
+    map: &'a RegisterMap,
+    opts: &'a GeneratorOpts,
+}
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    map.require_contiguous_fields("sv-uvm-ral")?;
+
+    let inp = Input { map, opts };
+
+    let package_name = match &opts.package_name {
+        Some(package_name) => package_name.clone(),
+        None => format!("{}_ral_pkg", sv_ident(&map.name)),
+    };
+
+    sv_generate_header_comment(out, map, "sv-uvm-ral")?;
+    writeln!(out)?;
+    writeln!(out, "`ifndef {}_SV", sv_macro(&package_name))?;
+    writeln!(out, "`define {}_SV", sv_macro(&package_name))?;
+    writeln!(out)?;
+    writeln!(out, "package {package_name};")?;
+    writeln!(out)?;
+    writeln!(out, "  import uvm_pkg::*;")?;
+    writeln!(out, "  `include \"uvm_macros.svh\"")?;
+    let max_width = map.max_register_width();
+    if max_width > 64 {
+        writeln!(out)?;
+        writeln!(out, "  // Note: Registers wider than 64 bits require UVM_REG_DATA_WIDTH to be")?;
+        writeln!(out, "  // defined as at least {max_width}.")?;
+    }
+
+    // ===== Registers: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Registers")?;
+    let mut regs: Vec<_> = map.individual_registers().collect();
+    regs.sort_by_key(|x| x.adr);
+    for register in regs {
+        let mut extra = vec![format!("Address: 0x{:X}", register.adr)];
+        if let Some(reset_val) = register.reset_val {
+            extra.push(format!("Reset Value: 0x{reset_val:X}"));
+        }
+        let extra: Vec<&str> = extra.iter().map(|x| x.as_str()).collect();
+        generate_register_class(out, &register.name, &register.docs, &register.layout, register.reset_val, &extra)?;
+    }
+    for block in map.register_blocks.values() {
+        for member in block_members(block) {
+            let extra = [format!("Offset: 0x{:X}", member.offset)];
+            let extra: Vec<&str> = extra.iter().map(|x| x.as_str()).collect();
+            let reset_val = block_member_common_reset_val(block, member);
+            generate_register_class(out, &member.name, &member.docs, &member.layout, reset_val, &extra)?;
+        }
+    }
+
+    // ===== Register Blocks: =====
+    if !map.register_blocks.is_empty() {
+        writeln!(out)?;
+        sv_generate_section_header_comment(out, "  ", "Register Blocks")?;
+        for block in map.register_blocks.values() {
+            generate_block_class(out, &inp, block)?;
+        }
+    }
+
+    // ===== Register Model: =====
+    writeln!(out)?;
+    sv_generate_section_header_comment(out, "  ", "Register Model")?;
+    generate_model_class(out, &inp)?;
+
+    writeln!(out)?;
+    writeln!(out, "endpackage : {package_name}")?;
+    writeln!(out)?;
+    writeln!(out, "`endif // {}_SV", sv_macro(&package_name))?;
+
+    Ok(())
+}
+
+fn generate_register_class(
+    out: &mut dyn Write,
+    name: &str,
+    docs: &Docs,
+    layout: &Layout,
+    reset_val: Option<TypeValue>,
+    extra: &[&str],
+) -> Result<(), Error> {
+    let class_name = register_class_name(name);
+    let fields = layout.flattened_fields();
+
+    // Class doc comment:
+    writeln!(out)?;
+    writeln!(out, "  // `{name}` Register")?;
+    writeln!(out, "  //")?;
+    sv_generate_doc_comment(out, docs, "  ", extra)?;
+    writeln!(out, "  class {class_name} extends uvm_reg;")?;
+    writeln!(out, "    `uvm_object_utils({class_name})")?;
+
+    // Field handles:
+    if !fields.is_empty() {
+        writeln!(out)?;
+    }
+    for field in &fields {
+        sv_generate_doc_comment(out, &field.field.docs, "    ", &[])?;
+        let rand = if field_props(field, reset_val).is_rand {
+            "rand "
+        } else {
+            ""
+        };
+        writeln!(out, "    {rand}uvm_reg_field {};", field_name(field))?;
+    }
+
+    // Constructor:
+    writeln!(out)?;
+    writeln!(out, "    function new(string name = \"{class_name}\");")?;
+    writeln!(out, "      super.new(name, {}, UVM_NO_COVERAGE);", layout.bitwidth)?;
+    writeln!(out, "    endfunction")?;
+
+    // Build:
+    writeln!(out)?;
+    writeln!(out, "    virtual function void build();")?;
+    for field in &fields {
+        let name = field_name(field);
+        let props = field_props(field, reset_val);
+        let width = field.bits.width();
+        writeln!(out, "      {name} = uvm_reg_field::type_id::create(\"{name}\");")?;
+        writeln!(
+            out,
+            "      {name}.configure(this, {width}, {}, \"{}\", {}, {}, {}, {}, 0);",
+            field.bits.lsb_pos(),
+            props.access,
+            props.volatile as u8,
+            sv_hex_literal(width, props.reset.unwrap_or(0)),
+            props.reset.is_some() as u8,
+            props.is_rand as u8,
+        )?;
+    }
+    writeln!(out, "    endfunction")?;
+
+    writeln!(out, "  endclass : {class_name}")?;
+
+    Ok(())
+}
+
+fn generate_block_class(out: &mut dyn Write, inp: &Input, block: &RegisterBlock) -> Result<(), Error> {
+    let class_name = block_class_name(&block.name);
+    let members = block_members(block);
+    let bus_width = match inp.opts.bus_width_bytes {
+        Some(bus_width) => bus_width,
+        None => members.iter().map(|x| x.layout.width_bytes()).max().unwrap_or(1),
+    };
+
+    // Class doc comment:
+    writeln!(out)?;
+    writeln!(out, "  // `{}` Register Block", block.name)?;
+    if !block.docs.is_empty() {
+        writeln!(out, "  //")?;
+        sv_generate_doc_comment(out, &block.docs, "  ", &[])?;
+    }
+    writeln!(out, "  class {class_name} extends uvm_reg_block;")?;
+    writeln!(out, "    `uvm_object_utils({class_name})")?;
+
+    // Register handles:
+    writeln!(out)?;
+    for member in &members {
+        writeln!(out, "    rand {} {};", register_class_name(&member.name), sv_ident(&member.name_raw))?;
+    }
+
+    // Constructor:
+    writeln!(out)?;
+    writeln!(out, "    function new(string name = \"{class_name}\");")?;
+    writeln!(out, "      super.new(name, UVM_NO_COVERAGE);")?;
+    writeln!(out, "    endfunction")?;
+
+    // Build:
+    writeln!(out)?;
+    writeln!(out, "    virtual function void build();")?;
+    generate_create_map(out, inp, bus_width)?;
+    for member in &members {
+        generate_add_reg(
+            out,
+            &register_class_name(&member.name),
+            &sv_ident(&member.name_raw),
+            &member.layout,
+            member.offset,
+        )?;
+    }
+    writeln!(out, "    endfunction")?;
+
+    writeln!(out, "  endclass : {class_name}")?;
+
+    Ok(())
+}
+
+fn generate_model_class(out: &mut dyn Write, inp: &Input) -> Result<(), Error> {
+    let class_name = format!("{}_reg_block", sv_ident(&inp.map.name));
+    let bus_width = match inp.opts.bus_width_bytes {
+        Some(bus_width) => bus_width,
+        None => bitwidth_to_width_bytes(inp.map.max_register_width()).max(1),
+    };
+
+    let mut regs: Vec<_> = inp.map.individual_registers().collect();
+    regs.sort_by_key(|x| x.adr);
+    let mut instances: Vec<_> = inp
+        .map
+        .register_blocks
+        .values()
+        .flat_map(|block| block.instances.values().map(move |instance| (block, instance)))
+        .collect();
+    instances.sort_by_key(|(_, instance)| instance.adr);
+
+    // Class doc comment:
+    writeln!(out)?;
+    writeln!(out, "  // `{}` Register Model", inp.map.name)?;
+    writeln!(out, "  class {class_name} extends uvm_reg_block;")?;
+    writeln!(out, "    `uvm_object_utils({class_name})")?;
+
+    // Register and block handles:
+    writeln!(out)?;
+    for register in &regs {
+        writeln!(out, "    rand {} {};", register_class_name(&register.name), sv_ident(&register.name))?;
+    }
+    for (block, instance) in &instances {
+        sv_generate_doc_comment(out, &instance.docs, "    ", &[])?;
+        writeln!(out, "    rand {} {};", block_class_name(&block.name), sv_ident(&instance.name))?;
+    }
+
+    // Constructor:
+    writeln!(out)?;
+    writeln!(out, "    function new(string name = \"{class_name}\");")?;
+    writeln!(out, "      super.new(name, UVM_NO_COVERAGE);")?;
+    writeln!(out, "    endfunction")?;
+
+    // Build:
+    writeln!(out)?;
+    writeln!(out, "    virtual function void build();")?;
+    generate_create_map(out, inp, bus_width)?;
+    for register in &regs {
+        generate_add_reg(
+            out,
+            &register_class_name(&register.name),
+            &sv_ident(&register.name),
+            &register.layout,
+            register.adr,
+        )?;
+    }
+    for (block, instance) in &instances {
+        let name = sv_ident(&instance.name);
+        writeln!(out)?;
+        writeln!(out, "      {name} = {}::type_id::create(\"{name}\");", block_class_name(&block.name))?;
+        writeln!(out, "      {name}.configure(this);")?;
+        writeln!(out, "      {name}.build();")?;
+        writeln!(out, "      default_map.add_submap({name}.default_map, 'h{:X});", instance.adr)?;
+
+        // Reset values that differ between instances are applied to each instance:
+        for member in block_members(block) {
+            if block_member_common_reset_val(block, member).is_some() {
+                continue;
+            }
+            let Some(reset_val) = instance.registers.get(&member.name).and_then(|x| x.reset_val) else {
+                continue;
+            };
+            writeln!(
+                out,
+                "      {name}.{}.set_reset({});",
+                sv_ident(&member.name_raw),
+                sv_hex_literal(member.layout.bitwidth, reset_val)
+            )?;
+        }
+    }
+    writeln!(out)?;
+    writeln!(out, "      lock_model();")?;
+    writeln!(out, "    endfunction")?;
+
+    writeln!(out, "  endclass : {class_name}")?;
+
+    Ok(())
+}
+
+fn generate_create_map(out: &mut dyn Write, inp: &Input, bus_width: TypeBitwidth) -> Result<(), Error> {
+    let endian = match inp.opts.endian {
+        Endianess::Little => "UVM_LITTLE_ENDIAN",
+        Endianess::Big => "UVM_BIG_ENDIAN",
+    };
+    writeln!(out, "      default_map = create_map(\"default_map\", 0, {bus_width}, {endian}, 1);")?;
+    Ok(())
+}
+
+fn generate_add_reg(
+    out: &mut dyn Write,
+    class_name: &str,
+    name: &str,
+    layout: &Layout,
+    offset: TypeAdr,
+) -> Result<(), Error> {
+    writeln!(out)?;
+    writeln!(out, "      {name} = {class_name}::type_id::create(\"{name}\");")?;
+    writeln!(out, "      {name}.configure(this);")?;
+    writeln!(out, "      {name}.build();")?;
+    writeln!(out, "      default_map.add_reg({name}, 'h{offset:X}, \"{}\");", register_rights(layout))?;
+    Ok(())
+}
+
+// ====== Generator Utils ======================================================
+
+/// UVM properties of a single register field.
+struct FieldProps {
+    access: &'static str,
+    volatile: bool,
+    reset: Option<TypeValue>,
+    is_rand: bool,
+}
+
+fn field_props(field: &FlattenedLayoutField, reset_val: Option<TypeValue>) -> FieldProps {
+    let reset = reset_val.map(|x| (x >> field.bits.lsb_pos()) & field.bits.unpositioned_mask());
+
+    // Fixed fields always read back their fixed value:
+    if let FieldType::Fixed(fixed) = &field.field.accepts {
+        return FieldProps {
+            access: "RO",
+            volatile: false,
+            reset: Some(*fixed),
+            is_rand: false,
+        };
+    }
+
+    let readable = field.field.access.as_ref().is_none_or(|x| x.contains(&AccessMode::R));
+    let writable = field.field.access.as_ref().is_none_or(|x| x.contains(&AccessMode::W));
+
+    match (readable, writable) {
+        (true, true) | (false, false) => FieldProps {
+            access: "RW",
+            volatile: false,
+            reset,
+            is_rand: true,
+        },
+        // Read-only fields are assumed to be updated by hardware:
+        (true, false) => FieldProps {
+            access: "RO",
+            volatile: true,
+            reset,
+            is_rand: false,
+        },
+        (false, true) => FieldProps {
+            access: "WO",
+            volatile: false,
+            reset,
+            is_rand: true,
+        },
+    }
+}
+
+/// Access rights of a register in a map, derived from its fields.
+fn register_rights(layout: &Layout) -> &'static str {
+    let fields = layout.flattened_fields();
+    let readable = fields.iter().any(|x| field_props(x, None).access != "WO");
+    let writable = fields.iter().any(|x| field_props(x, None).access != "RO");
+
+    match (readable, writable) {
+        (true, false) => "RO",
+        (false, true) => "WO",
+        _ => "RW",
+    }
+}
+
+/// Reset value of a block member, if it is the same across all instances.
+fn block_member_common_reset_val(block: &RegisterBlock, member: &RegisterBlockMember) -> Option<TypeValue> {
+    let mut reset_vals = block
+        .instances
+        .values()
+        .map(|x| x.registers.get(&member.name).and_then(|x| x.reset_val));
+    let first = reset_vals.next()??;
+    if reset_vals.all(|x| x == Some(first)) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+fn block_members(block: &RegisterBlock) -> Vec<&RegisterBlockMember> {
+    let mut members: Vec<_> = block.members.values().map(|x| x.as_ref()).collect();
+    members.sort_by_key(|x| x.offset);
+    members
+}
+
+fn field_name(field: &FlattenedLayoutField) -> String {
+    sv_ident(&field.name.join("_"))
+}
+
+fn register_class_name(name: &str) -> String {
+    sv_ident(name) + "_reg"
+}
+
+fn block_class_name(name: &str) -> String {
+    sv_ident(name) + "_block"
+}
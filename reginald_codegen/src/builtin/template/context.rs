@@ -0,0 +1,341 @@
+use serde::Serialize;
+
+use crate::regmap::{
+    Docs, Enum, FieldType, Layout, LayoutField, Register, RegisterBlock, RegisterMap, Scaling, ScalingConversion,
+    TypeAdr, TypeBitwidth, TypeValue, access_str,
+};
+
+// ==== Context Types ==========================================================
+
+/// Serialisable view of a register map, as exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct MapContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub author: Option<String>,
+    pub notice: Option<String>,
+    pub input_file: Option<String>,
+    /// All registers, including those generated from register blocks, sorted by address.
+    pub registers: Vec<RegisterContext>,
+    /// Register blocks, sorted by name.
+    pub blocks: Vec<BlockContext>,
+    /// Shared (non-local) enums, sorted by name.
+    pub enums: Vec<EnumContext>,
+    /// Shared (non-local) layouts, sorted by name.
+    pub layouts: Vec<LayoutContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocsContext {
+    pub brief: Option<String>,
+    pub doc: Option<String>,
+    /// Brief and doc joined into a single block of text.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub adr: TypeAdr,
+    pub reset_val: Option<TypeValue>,
+    pub bitwidth: TypeBitwidth,
+    pub layout: LayoutContext,
+    pub block: Option<String>,
+    pub block_instance: Option<String>,
+    pub block_member: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub is_local: bool,
+    pub bitwidth: TypeBitwidth,
+    /// Fields, sorted from LSB to MSB.
+    pub fields: Vec<FieldContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub msb: TypeBitwidth,
+    pub lsb: TypeBitwidth,
+    pub width: TypeBitwidth,
+    pub mask: TypeValue,
+    /// Bit ranges occupied by the field, with the most significant bits of the value first.
+    /// Contiguous fields have a single range.
+    pub ranges: Vec<FieldRangeContext>,
+    /// Access modes joined by '/', if specified.
+    pub access: Option<String>,
+    /// One of `uint`, `sint`, `bool`, `fixed`, `enum` or `layout`.
+    pub kind: &'static str,
+    /// Value of a fixed field.
+    pub fixed: Option<TypeValue>,
+    #[serde(rename = "enum")]
+    pub enum_: Option<EnumContext>,
+    pub layout: Option<LayoutContext>,
+    /// Physical unit conversion of a scaled field.
+    pub scaling: Option<ScalingContext>,
+    /// Value constraints of an unsigned field.
+    pub constraints: Option<ConstraintsContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldRangeContext {
+    pub msb: TypeBitwidth,
+    pub lsb: TypeBitwidth,
+    /// Position of this range's least significant bit within the field's value.
+    pub value_lsb: TypeBitwidth,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstraintsContext {
+    pub min: Option<TypeValue>,
+    pub max: Option<TypeValue>,
+    pub step: Option<TypeValue>,
+    pub reserved: Vec<TypeValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScalingContext {
+    pub unit: String,
+    /// Spelled-out unit name, such as `millivolts`.
+    pub unit_name: String,
+    /// Scale and offset of a linear conversion.
+    pub scale: Option<i64>,
+    pub offset: Option<i64>,
+    /// Physical values of a lookup table conversion, indexed by field value.
+    pub table: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub is_local: bool,
+    pub bitwidth: TypeBitwidth,
+    /// Entries, sorted by value.
+    pub entries: Vec<EnumEntryContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumEntryContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub value: TypeValue,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockContext {
+    pub name: String,
+    pub docs: DocsContext,
+    /// Members, sorted by offset.
+    pub members: Vec<BlockMemberContext>,
+    /// Instances, sorted by address.
+    pub instances: Vec<BlockInstanceContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockMemberContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub offset: TypeAdr,
+    pub layout: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockInstanceContext {
+    pub name: String,
+    pub docs: DocsContext,
+    pub adr: TypeAdr,
+    /// Names of the registers of this instance, sorted by address.
+    pub registers: Vec<String>,
+}
+
+// ==== Conversion =============================================================
+
+impl From<&RegisterMap> for MapContext {
+    fn from(map: &RegisterMap) -> Self {
+        let mut registers: Vec<&Register> = map.registers.values().map(|x| x.as_ref()).collect();
+        registers.sort_by_key(|x| x.adr);
+
+        Self {
+            name: map.name.clone(),
+            docs: (&map.docs).into(),
+            author: map.author.clone(),
+            notice: map.notice.clone(),
+            input_file: map.from_file.as_ref().map(|x| x.to_string_lossy().to_string()),
+            registers: registers.into_iter().map(RegisterContext::from).collect(),
+            blocks: map.register_blocks.values().map(BlockContext::from).collect(),
+            enums: map.shared_enums().map(EnumContext::from).collect(),
+            layouts: map.shared_layouts().map(LayoutContext::from).collect(),
+        }
+    }
+}
+
+impl From<&Docs> for DocsContext {
+    fn from(docs: &Docs) -> Self {
+        Self {
+            brief: docs.brief.clone(),
+            doc: docs.doc.clone(),
+            text: docs.as_multiline("").trim_end().to_string(),
+        }
+    }
+}
+
+impl From<&Register> for RegisterContext {
+    fn from(register: &Register) -> Self {
+        let origin = register.from_block.as_ref();
+        Self {
+            name: register.name.clone(),
+            docs: (&register.docs).into(),
+            adr: register.adr,
+            reset_val: register.reset_val,
+            bitwidth: register.layout.bitwidth,
+            layout: register.layout.as_ref().into(),
+            block: origin.map(|x| x.block.clone()),
+            block_instance: origin.map(|x| x.instance.clone()),
+            block_member: origin.map(|x| x.block_member.clone()),
+        }
+    }
+}
+
+impl From<&Layout> for LayoutContext {
+    fn from(layout: &Layout) -> Self {
+        let mut fields: Vec<&LayoutField> = layout.fields.values().collect();
+        fields.sort_by_key(|x| x.bits.lsb_pos());
+
+        Self {
+            name: layout.name.clone(),
+            docs: (&layout.docs).into(),
+            is_local: layout.is_local,
+            bitwidth: layout.bitwidth,
+            fields: fields.into_iter().map(FieldContext::from).collect(),
+        }
+    }
+}
+
+impl From<&LayoutField> for FieldContext {
+    fn from(field: &LayoutField) -> Self {
+        let (kind, fixed, enum_, layout) = match &field.accepts {
+            FieldType::UInt => ("uint", None, None, None),
+            FieldType::SInt => ("sint", None, None, None),
+            FieldType::Bool => ("bool", None, None, None),
+            FieldType::Fixed(val) => ("fixed", Some(*val), None, None),
+            FieldType::Enum(e) => ("enum", None, Some(e.as_ref().into()), None),
+            FieldType::Layout(l) => ("layout", None, None, Some(l.as_ref().into())),
+        };
+
+        Self {
+            name: field.name.clone(),
+            docs: (&field.docs).into(),
+            msb: field.bits.msb_pos(),
+            lsb: field.bits.lsb_pos(),
+            width: field.bits.width(),
+            mask: field.bits.mask(),
+            ranges: field
+                .bits
+                .pieces()
+                .into_iter()
+                .map(|(range, value_lsb)| FieldRangeContext {
+                    msb: range.msb_pos(),
+                    lsb: range.lsb_pos(),
+                    value_lsb,
+                })
+                .collect(),
+            access: field.access.as_ref().map(access_str),
+            kind,
+            fixed,
+            enum_,
+            layout,
+            scaling: field.scaling.as_ref().map(Into::into),
+            constraints: field.constraints.as_ref().map(|x| ConstraintsContext {
+                min: x.min,
+                max: x.max,
+                step: x.step,
+                reserved: x.reserved.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&Scaling> for ScalingContext {
+    fn from(scaling: &Scaling) -> Self {
+        let (scale, offset, table) = match &scaling.conversion {
+            ScalingConversion::Linear { scale, offset } => (Some(*scale), Some(*offset), None),
+            ScalingConversion::Table(table) => (None, None, Some(table.clone())),
+        };
+
+        Self {
+            unit: scaling.unit.clone(),
+            unit_name: scaling.unit_name(),
+            scale,
+            offset,
+            table,
+        }
+    }
+}
+
+impl From<&Enum> for EnumContext {
+    fn from(e: &Enum) -> Self {
+        let mut entries: Vec<EnumEntryContext> = e
+            .entries
+            .values()
+            .map(|entry| EnumEntryContext {
+                name: entry.name.clone(),
+                docs: (&entry.docs).into(),
+                value: entry.value,
+            })
+            .collect();
+        entries.sort_by_key(|x| x.value);
+
+        Self {
+            name: e.name.clone(),
+            docs: (&e.docs).into(),
+            is_local: e.is_local,
+            bitwidth: e.bitwidth,
+            entries,
+        }
+    }
+}
+
+impl From<&RegisterBlock> for BlockContext {
+    fn from(block: &RegisterBlock) -> Self {
+        let mut members: Vec<BlockMemberContext> = block
+            .members
+            .values()
+            .map(|member| BlockMemberContext {
+                name: member.name.clone(),
+                docs: (&member.docs).into(),
+                offset: member.offset,
+                layout: member.layout.name.clone(),
+            })
+            .collect();
+        members.sort_by_key(|x| x.offset);
+
+        let mut instances: Vec<BlockInstanceContext> = block
+            .instances
+            .values()
+            .map(|instance| {
+                let mut registers: Vec<&Register> = instance.registers.values().map(|x| x.as_ref()).collect();
+                registers.sort_by_key(|x| x.adr);
+                BlockInstanceContext {
+                    name: instance.name.clone(),
+                    docs: (&instance.docs).into(),
+                    adr: instance.adr,
+                    registers: registers.into_iter().map(|x| x.name.clone()).collect(),
+                }
+            })
+            .collect();
+        instances.sort_by_key(|x| x.adr);
+
+        Self {
+            name: block.name.clone(),
+            docs: (&block.docs).into(),
+            members,
+            instances,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+pub mod context;
+
+use std::{fmt::Write, path::Path, path::PathBuf};
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+use minijinja::{Environment, context, path_loader};
+
+use crate::{error::Error, regmap::RegisterMap};
+
+use self::context::MapContext;
+
+// ====== Generator Opts =======================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct GeneratorOpts {
+    /// Template file.
+    ///
+    /// Rendered with minijinja (Jinja2 syntax). The register map is available
+    /// as `map`. Other templates in the same directory can be used with
+    /// `include`, `import` and `extends`.
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub template: PathBuf,
+}
+
+// ====== Generator ============================================================
+
+pub fn generate(out: &mut dyn Write, map: &RegisterMap, opts: &GeneratorOpts) -> Result<(), Error> {
+    render_file(out, map, &opts.template)
+}
+
+/// Render a template file with the given map as context.
+pub fn render_file(out: &mut dyn Write, map: &RegisterMap, template: &Path) -> Result<(), Error> {
+    let Some(name) = template.file_name() else {
+        return Err(Error::GeneratorError(format!("Invalid template path '{}'", template.to_string_lossy())));
+    };
+    let dir = match template.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    if !template.is_file() {
+        return Err(Error::GeneratorError(format!("Template '{}' does not exist", template.to_string_lossy())));
+    }
+
+    let mut env = environment();
+    env.set_loader(path_loader(dir));
+    let tmpl = env.get_template(&name.to_string_lossy())?;
+    write!(out, "{}", tmpl.render(context! { map => MapContext::from(map) })?)?;
+    Ok(())
+}
+
+/// Render a template source with the given map as context.
+pub fn render_str(out: &mut dyn Write, map: &RegisterMap, source: &str) -> Result<(), Error> {
+    let env = environment();
+    write!(out, "{}", env.render_str(source, context! { map => MapContext::from(map) })?)?;
+    Ok(())
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("hex", hex_filter);
+    env
+}
+
+/// `hex` filter: Format a value as hex literal, optionally zero-padded to the given number of digits.
+fn hex_filter(value: u128, digits: Option<usize>) -> String {
+    format!("0x{value:0width$X}", width = digits.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const MAP: &str = r#"
+        name: test
+        registers:
+          REG_B: !Register
+            adr: 0x10
+            bitwidth: 8
+            layout: !Layout
+              A:
+                bits: "1-0"
+                access: [R, W]
+                accepts: !SharedEnum E
+          REG_A: !Register
+            adr: 0x02
+            bitwidth: 8
+            reset_val: 0x5
+            layout: !Layout
+              X:
+                bits: 7
+        enums:
+          E:
+            bitwidth: 2
+            enum:
+              B: { val: 2 }
+              A: { val: 1 }
+    "#;
+
+    #[test]
+    fn test_render() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+
+        let template = "\
+= {{ map.name }}
+{% for reg in map.registers %}
+== {{ reg.name }} @ {{ reg.adr | hex(2) }}
+{% for field in reg.layout.fields %}
+* {{ field.name }} [{{ field.msb }}:{{ field.lsb }}] {{ field.access or '-' }}
+{% if field.enum %}
+{% for entry in field.enum.entries %}
+** {{ entry.name }} = {{ entry.value }}
+{% endfor %}
+{% endif %}
+{% endfor %}
+{% endfor %}
+";
+
+        let mut out = String::new();
+        render_str(&mut out, &map, template).unwrap();
+
+        let should = "\
+= test
+== REG_A @ 0x02
+* X [7:7] -
+== REG_B @ 0x10
+* A [1:0] R/W
+** A = 1
+** B = 2
+";
+        assert_eq!(out, should);
+    }
+
+    #[test]
+    fn test_render_error() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let mut out = String::new();
+        assert!(render_str(&mut out, &map, "{% for x in %}").is_err());
+        assert!(render_file(&mut out, &map, Path::new("does/not/exist.j2")).is_err());
+    }
+}
@@ -18,6 +18,12 @@ pub enum Error {
     OutputError(#[from] std::fmt::Error),
     #[error("Generator error: {0}.")]
     GeneratorError(String),
+    #[error("Config error: {0}.")]
+    ConfigError(String),
+    #[error("Import error: {0}.")]
+    ImportError(String),
+    #[error("Template error: {0}.")]
+    TemplateError(#[from] minijinja::Error),
     #[error("Validation Error: {0}")]
     VerificationError(String),
 }
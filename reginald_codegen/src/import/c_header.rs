@@ -0,0 +1,915 @@
+//! Reconstruct a register listing from a C header of register macros.
+//!
+//! Registers are recovered from address macros (`{PREFIX}_{REG}_ADDRESS`),
+//! fields from mask and shift macros (`{PREFIX}_{REG}_{FIELD}_MASK/_SHIFT`),
+//! enums from value macros (`{PREFIX}_{REG}_{FIELD}_VAL_{ENTRY}`), and register
+//! blocks from offset and instance macros (`{PREFIX}_{BLOCK}_{REG}_OFFSET`,
+//! `{PREFIX}_{BLOCK}_INSTANCE_{INSTANCE}`), as emitted by the `c-macromap`
+//! generator. All suffixes are configurable to support headers produced by
+//! other tools.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+
+use crate::{
+    bits::bitmask_from_range,
+    error::Error,
+    regmap::{TypeAdr, TypeBitwidth, TypeValue, listing},
+};
+
+use super::{Imported, fitting_bitwidth, listing_bits};
+
+// ====== Import Opts ==========================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct ImportOpts {
+    /// Name of the register map
+    ///
+    /// Defaults to the common macro prefix.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub name: Option<String>,
+
+    /// Macro prefix that is removed from all names
+    ///
+    /// Detected from the address macros if not given.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub prefix: Option<String>,
+
+    /// Bitwidth of all registers
+    ///
+    /// If not given, the smallest of 8, 16, 32, 64 or 128 bits that fits all
+    /// fields and reset values of a register is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub bitwidth: Option<TypeBitwidth>,
+
+    /// Suffix of register address macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().address_suffix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub address_suffix: Vec<String>,
+
+    /// Suffix of register reset value macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().reset_suffix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub reset_suffix: Vec<String>,
+
+    /// Suffix of field mask macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().mask_suffix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub mask_suffix: Vec<String>,
+
+    /// Suffix of field shift macros
+    ///
+    /// May be given multiple times. If the header contains no shift macros,
+    /// fields are reconstructed from their masks alone. Otherwise, masks
+    /// without a matching shift macro are ignored.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().shift_suffix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub shift_suffix: Vec<String>,
+
+    /// Infix between a field and its enum entries in field value macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().enum_infix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub enum_infix: Vec<String>,
+
+    /// Suffix of register block member offset macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().offset_suffix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub offset_suffix: Vec<String>,
+
+    /// Infix between a register block and its instances in instance address macros
+    ///
+    /// May be given multiple times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(action = clap::ArgAction::Append))]
+    #[cfg_attr(feature = "clap", arg(default_values_t = Self::default().instance_infix))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub instance_infix: Vec<String>,
+}
+
+impl Default for ImportOpts {
+    fn default() -> Self {
+        let strings = |x: &[&str]| x.iter().map(|x| x.to_string()).collect();
+        Self {
+            name: None,
+            prefix: None,
+            bitwidth: None,
+            address_suffix: strings(&["_ADDRESS", "_ADDR"]),
+            reset_suffix: strings(&["_RESET"]),
+            mask_suffix: strings(&["_MASK", "_Msk"]),
+            shift_suffix: strings(&["_SHIFT", "_Pos"]),
+            enum_infix: strings(&["_VAL_"]),
+            offset_suffix: strings(&["_OFFSET"]),
+            instance_infix: strings(&["_INSTANCE_"]),
+        }
+    }
+}
+
+// ====== Import ===============================================================
+
+struct Field {
+    name: String,
+    lsb: TypeBitwidth,
+    msb: TypeBitwidth,
+    entries: listing::EnumEntries,
+}
+
+#[derive(Default)]
+struct Block {
+    /// Instance name and address.
+    instances: Vec<(String, TypeAdr)>,
+    /// Member name, offset and macro base.
+    members: Vec<(String, TypeAdr, String)>,
+}
+
+pub fn import(input: &str, opts: &ImportOpts) -> Result<Imported, Error> {
+    let mut warnings = vec![];
+
+    // Evaluate all object-like macros:
+    let defines = parse_defines(input);
+    let lookup: HashMap<&str, &str> = defines.iter().map(|(n, v)| (n.as_str(), v.as_str())).collect();
+    let mut values: Vec<(&str, TypeValue)> = vec![];
+    for (name, value) in &defines {
+        match evaluate(&lookup, value, 0) {
+            Some(val) => values.push((name, val)),
+            None => {
+                let all_suffixes = [
+                    &opts.address_suffix,
+                    &opts.reset_suffix,
+                    &opts.mask_suffix,
+                    &opts.shift_suffix,
+                ];
+                if all_suffixes.iter().any(|x| strip_suffix(name, x).is_some()) {
+                    warnings.push(format!("Ignoring '{name}': cannot evaluate '{value}'"));
+                }
+            }
+        }
+    }
+
+    // Classify macros:
+    let mut addresses: Vec<(&str, TypeAdr)> = vec![];
+    let mut resets: HashMap<&str, TypeValue> = HashMap::new();
+    let mut masks: Vec<(&str, TypeValue)> = vec![];
+    let mut shifts: HashMap<&str, TypeValue> = HashMap::new();
+    let mut offsets: Vec<(&str, TypeAdr)> = vec![];
+    let mut blocks: BTreeMap<&str, Block> = BTreeMap::new();
+
+    for (name, val) in &values {
+        let val = *val;
+        // Addresses and offsets must fit the address type:
+        let mut adr = || {
+            let adr = TypeAdr::try_from(val).ok();
+            if adr.is_none() {
+                warnings.push(format!("Ignoring '{name}': 0x{val:X} is not a valid address"));
+            }
+            adr
+        };
+        if let Some((block, instance)) = split_infix(name, &opts.instance_infix) {
+            if let Some(adr) = adr() {
+                blocks
+                    .entry(block)
+                    .or_default()
+                    .instances
+                    .push((instance.to_string(), adr));
+            }
+        } else if let Some(base) = strip_suffix(name, &opts.offset_suffix) {
+            if let Some(adr) = adr() {
+                offsets.push((base, adr));
+            }
+        } else if let Some(base) = strip_suffix(name, &opts.address_suffix) {
+            if let Some(adr) = adr() {
+                addresses.push((base, adr));
+            }
+        } else if let Some(base) = strip_suffix(name, &opts.reset_suffix) {
+            resets.insert(base, val);
+        } else if let Some(base) = strip_suffix(name, &opts.mask_suffix) {
+            masks.push((base, val));
+        } else if let Some(base) = strip_suffix(name, &opts.shift_suffix) {
+            shifts.insert(base, val);
+        }
+    }
+
+    // Macro prefix:
+    let prefix = match &opts.prefix {
+        Some(prefix) if !prefix.is_empty() && !prefix.ends_with('_') => format!("{prefix}_"),
+        Some(prefix) => prefix.clone(),
+        None => {
+            let bases: Vec<&str> = addresses.iter().map(|x| x.0).chain(blocks.keys().copied()).collect();
+            common_prefix(&bases)
+        }
+    };
+    let strip_prefix = |base: &str| -> String {
+        match base.strip_prefix(&prefix) {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => base.to_string(),
+        }
+    };
+
+    // Register block members:
+    for (base, offset) in &offsets {
+        let block = blocks
+            .keys()
+            .filter(|block| base.starts_with(&format!("{block}_")))
+            .max_by_key(|block| block.len())
+            .copied();
+        match block {
+            Some(block) => {
+                let member = base[block.len() + 1..].to_string();
+                blocks
+                    .get_mut(block)
+                    .unwrap()
+                    .members
+                    .push((member, *offset, base.to_string()));
+            }
+            None => warnings.push(format!("Ignoring offset of '{base}': no matching register block instances")),
+        }
+    }
+
+    // Registers generated from register blocks are only kept as part of the block:
+    let mut block_registers: HashSet<&str> = HashSet::new();
+    let mut block_resets: HashMap<(&str, String, String), TypeValue> = HashMap::new();
+    for (block, info) in &blocks {
+        for (instance, instance_adr) in &info.instances {
+            for (member, offset, _) in &info.members {
+                let base = format!("{prefix}{instance}_{member}");
+                if let Some((base, _)) = addresses
+                    .iter()
+                    .find(|(b, adr)| *b == base && instance_adr.checked_add(*offset) == Some(*adr))
+                {
+                    block_registers.insert(base);
+                    if let Some(reset) = resets.get(base) {
+                        block_resets.insert((block, instance.clone(), member.clone()), *reset);
+                    }
+                }
+            }
+        }
+    }
+
+    // Fields:
+    let mut targets: Vec<&str> = addresses
+        .iter()
+        .map(|x| x.0)
+        .filter(|x| !block_registers.contains(x))
+        .collect();
+    for info in blocks.values() {
+        targets.extend(info.members.iter().map(|x| x.2.as_str()));
+    }
+
+    let mut fields: HashMap<&str, Vec<Field>> = HashMap::new();
+    for (base, mask) in &masks {
+        let Some(target) = targets
+            .iter()
+            .filter(|target| base.starts_with(&format!("{target}_")))
+            .max_by_key(|target| target.len())
+            .copied()
+        else {
+            warnings.push(format!("Ignoring mask of '{base}': no matching register"));
+            continue;
+        };
+        let name = &base[target.len() + 1..];
+
+        let lsb = mask.trailing_zeros();
+        let shifted = mask.checked_shr(lsb).unwrap_or(0);
+        let width = shifted.trailing_ones();
+        if *mask == 0 || shifted.checked_shr(width).unwrap_or(0) != 0 {
+            warnings.push(format!("Ignoring field '{base}': mask 0x{mask:X} is not contiguous"));
+            continue;
+        }
+        let msb = lsb + width - 1;
+
+        match shifts.get(base) {
+            Some(shift) if *shift != lsb as TypeValue => {
+                warnings.push(format!("Ignoring field '{base}': shift {shift} does not match mask 0x{mask:X}"));
+                continue;
+            }
+            None if !shifts.is_empty() => {
+                warnings.push(format!("Ignoring field '{base}': no shift defined"));
+                continue;
+            }
+            _ => (),
+        }
+
+        let target_fields = fields.entry(target).or_default();
+        if let Some(other) = target_fields
+            .iter()
+            .find(|x| (lsb..=msb).any(|bit| (x.lsb..=x.msb).contains(&bit)))
+        {
+            warnings.push(format!("Ignoring field '{base}': overlaps with field '{}'", other.name));
+            continue;
+        }
+
+        let mut entries = listing::EnumEntries::new();
+        for infix in &opts.enum_infix {
+            let entry_prefix = format!("{base}{infix}");
+            for (entry_name, val) in &values {
+                let Some(entry) = entry_name.strip_prefix(&entry_prefix) else {
+                    continue;
+                };
+                let field_max = mask >> lsb;
+                let val = if *val <= field_max {
+                    *val
+                } else if val & !mask == 0 {
+                    val >> lsb
+                } else {
+                    warnings.push(format!("Ignoring enum value '{entry_name}': does not fit into field"));
+                    continue;
+                };
+                entries.insert(entry.to_string(), listing::EnumEntry { val, doc: None });
+            }
+        }
+
+        target_fields.push(Field {
+            name: name.to_string(),
+            lsb,
+            msb,
+            entries,
+        });
+    }
+
+    let mut take_layout = |base: &str| -> (listing::RegisterLayout, TypeValue) {
+        let mut layout = listing::LayoutFields::new();
+        let mut occupied = 0;
+        for field in fields.remove(base).unwrap_or_default() {
+            occupied |= bitmask_from_range(&(field.lsb..=field.msb));
+            let accepts = if field.entries.is_empty() {
+                listing::FieldType::UInt
+            } else {
+                listing::FieldType::Enum(field.entries)
+            };
+            let field_listing = listing::LayoutField {
+                bits: listing_bits(field.lsb, field.msb),
+                doc: None,
+                accepts,
+                access: None,
+                unit: None,
+                scale: None,
+                offset: None,
+                table: None,
+                min: None,
+                max: None,
+                step: None,
+                reserved: None,
+            };
+            layout.insert(field.name, field_listing);
+        }
+        (listing::RegisterLayout::Layout(layout), occupied)
+    };
+
+    // Assemble listing:
+    let mut registers: BTreeMap<String, listing::RegisterListing> = BTreeMap::new();
+
+    for (base, adr) in &addresses {
+        if block_registers.contains(base) {
+            continue;
+        }
+        let name = strip_prefix(base);
+        if registers.contains_key(&name) {
+            warnings.push(format!("Ignoring address of '{base}': register '{name}' already defined"));
+            continue;
+        }
+        let reset_val = resets.get(base).copied();
+        let (layout, occupied) = take_layout(base);
+        let register = listing::Register {
+            adr: *adr,
+            doc: None,
+            bitwidth: Some(
+                opts.bitwidth
+                    .unwrap_or(fitting_bitwidth(occupied | reset_val.unwrap_or(0))),
+            ),
+            bit_order: None,
+            reset_val,
+            layout,
+        };
+        registers.insert(name, listing::RegisterListing::Register(register));
+    }
+
+    for (block, info) in &blocks {
+        let name = strip_prefix(block);
+        if info.members.is_empty() {
+            warnings.push(format!("Ignoring register block '{name}': no members defined"));
+            continue;
+        }
+
+        let mut instances = BTreeMap::new();
+        let mut members = BTreeMap::new();
+        for (member, offset, base) in &info.members {
+            let resets: Vec<Option<TypeValue>> = info
+                .instances
+                .iter()
+                .map(|(instance, _)| block_resets.get(&(*block, instance.clone(), member.clone())).copied())
+                .collect();
+            let common_reset = match resets.first() {
+                Some(Some(first)) if resets.iter().all(|x| *x == Some(*first)) => Some(*first),
+                _ => None,
+            };
+
+            let (layout, occupied) = take_layout(base);
+            let max_reset = resets.iter().flatten().fold(0, |acc, x| acc | x);
+            let member_listing = listing::RegisterBlockMember {
+                offset: *offset,
+                doc: None,
+                bitwidth: Some(opts.bitwidth.unwrap_or(fitting_bitwidth(occupied | max_reset))),
+                bit_order: None,
+                reset_val: common_reset,
+                layout,
+            };
+            members.insert(member.clone(), member_listing);
+        }
+
+        for (instance, adr) in &info.instances {
+            let mut reset_vals = BTreeMap::new();
+            for (member, _, _) in &info.members {
+                let reset = block_resets.get(&(*block, instance.clone(), member.clone()));
+                if let (Some(reset), None) = (reset, members[member].reset_val) {
+                    reset_vals.insert(member.clone(), *reset);
+                }
+            }
+            let instance_listing = listing::Instance {
+                adr: *adr,
+                doc: None,
+                reset_vals,
+            };
+            instances.insert(instance.clone(), instance_listing);
+        }
+
+        let block_listing = listing::RegisterBlock {
+            instances,
+            doc: None,
+            registers: members,
+        };
+        registers.insert(name, listing::RegisterListing::RegisterBlock(block_listing));
+    }
+
+    let name = match &opts.name {
+        Some(name) => name.clone(),
+        None if !prefix.trim_end_matches('_').is_empty() => prefix.trim_end_matches('_').to_string(),
+        None => {
+            return Err(Error::ImportError(
+                "Cannot determine map name from macro prefix, please specify a name".to_string(),
+            ));
+        }
+    };
+
+    let map = listing::RegisterMap {
+        name,
+        registers,
+        ..Default::default()
+    };
+
+    Ok(Imported { map, warnings })
+}
+
+fn strip_suffix<'a>(name: &'a str, suffixes: &[String]) -> Option<&'a str> {
+    suffixes
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix.as_str()).filter(|x| !x.is_empty()))
+}
+
+fn split_infix<'a>(name: &'a str, infixes: &[String]) -> Option<(&'a str, &'a str)> {
+    infixes.iter().find_map(|infix| {
+        name.split_once(infix.as_str())
+            .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+    })
+}
+
+/// Longest common prefix of all names that ends in an underscore.
+fn common_prefix(names: &[&str]) -> String {
+    if names.len() < 2 {
+        return String::new();
+    }
+    let first = names[0];
+    let len = names[1..].iter().fold(first.len(), |len, name| {
+        first
+            .bytes()
+            .zip(name.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    match first[..len].rfind('_') {
+        Some(pos) => first[..=pos].to_string(),
+        None => String::new(),
+    }
+}
+
+// ====== Macro Parsing ========================================================
+
+/// All object-like macro definitions as name and value.
+fn parse_defines(input: &str) -> Vec<(String, String)> {
+    let source = strip_comments(&input.replace("\\\r\n", " ").replace("\\\n", " "));
+
+    let mut defines = vec![];
+    for line in source.lines() {
+        let Some(rest) = line.trim().strip_prefix('#') else {
+            continue;
+        };
+        let Some(rest) = rest.trim_start().strip_prefix("define") else {
+            continue;
+        };
+        if !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let rest = rest.trim_start();
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (name, value) = rest.split_at(name_len);
+
+        // Skip function-like macros:
+        if name.is_empty() || value.starts_with('(') {
+            continue;
+        }
+
+        defines.push((name.to_string(), value.trim().to_string()));
+    }
+    defines
+}
+
+/// Remove C and C++ comments, preserving line breaks.
+fn strip_comments(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|x| *x != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            ('"', _) => {
+                out.push(c);
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == '"' && !escaped {
+                        break;
+                    }
+                    escaped = c == '\\' && !escaped;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ====== Expression Evaluation ================================================
+
+/// Maximum depth of nested macro references.
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Num(TypeValue),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+/// Evaluate an integer constant expression, resolving references to other macros.
+fn evaluate(defines: &HashMap<&str, &str>, expr: &str, depth: usize) -> Option<TypeValue> {
+    if depth > MAX_EXPANSION_DEPTH {
+        return None;
+    }
+    let tokens = tokenize(expr)?;
+    let mut parser = ExprParser {
+        defines,
+        tokens,
+        pos: 0,
+        depth,
+    };
+    let val = parser.expr(0)?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    Some(val)
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let bytes = expr.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let c = bytes[pos] as char;
+        let word_len = expr[pos..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(expr.len() - pos);
+        if c.is_ascii_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit() {
+            tokens.push(Token::Num(parse_int_literal(&expr[pos..pos + word_len])?));
+            pos += word_len;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            tokens.push(Token::Ident(expr[pos..pos + word_len].to_string()));
+            pos += word_len;
+        } else if expr[pos..].starts_with("<<") || expr[pos..].starts_with(">>") {
+            tokens.push(Token::Op(if c == '<' { "<<" } else { ">>" }));
+            pos += 2;
+        } else {
+            tokens.push(match c {
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                '+' => Token::Op("+"),
+                '-' => Token::Op("-"),
+                '*' => Token::Op("*"),
+                '/' => Token::Op("/"),
+                '%' => Token::Op("%"),
+                '&' => Token::Op("&"),
+                '|' => Token::Op("|"),
+                '^' => Token::Op("^"),
+                '~' => Token::Op("~"),
+                _ => return None,
+            });
+            pos += 1;
+        }
+    }
+    Some(tokens)
+}
+
+/// Parse a C integer literal, including hex, binary and octal prefixes and integer suffixes.
+fn parse_int_literal(s: &str) -> Option<TypeValue> {
+    let s = s.trim_end_matches(['u', 'U', 'l', 'L']);
+    if let Some(hex) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+        TypeValue::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = s.strip_prefix("0b").or(s.strip_prefix("0B")) {
+        TypeValue::from_str_radix(bin, 2).ok()
+    } else if s.len() > 1 && s.starts_with('0') {
+        TypeValue::from_str_radix(&s[1..], 8).ok()
+    } else {
+        s.parse().ok()
+    }
+}
+
+struct ExprParser<'a> {
+    defines: &'a HashMap<&'a str, &'a str>,
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expr(&mut self, min_prec: u8) -> Option<TypeValue> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            let prec = match op {
+                "|" => 1,
+                "^" => 2,
+                "&" => 3,
+                "<<" | ">>" => 4,
+                "+" | "-" => 5,
+                "*" | "/" | "%" => 6,
+                _ => return None,
+            };
+            if prec < min_prec {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.expr(prec + 1)?;
+            lhs = match op {
+                "|" => lhs | rhs,
+                "^" => lhs ^ rhs,
+                "&" => lhs & rhs,
+                "<<" => lhs.checked_shl(rhs.try_into().ok()?)?,
+                ">>" => lhs.checked_shr(rhs.try_into().ok()?)?,
+                "+" => lhs.wrapping_add(rhs),
+                "-" => lhs.wrapping_sub(rhs),
+                "*" => lhs.wrapping_mul(rhs),
+                "/" => lhs.checked_div(rhs)?,
+                "%" => lhs.checked_rem(rhs)?,
+                _ => unreachable!(),
+            };
+        }
+        Some(lhs)
+    }
+
+    fn unary(&mut self) -> Option<TypeValue> {
+        match self.next()? {
+            Token::Num(val) => Some(val),
+            Token::Op("~") => Some(!self.unary()?),
+            Token::Op("-") => Some(self.unary()?.wrapping_neg()),
+            Token::Op("+") => self.unary(),
+            Token::Ident(name) => evaluate(self.defines, self.defines.get(name.as_str())?, self.depth + 1),
+            Token::LParen => {
+                // Skip casts such as '(uint32_t)':
+                if let (Some(Token::Ident(name)), Some(Token::RParen)) =
+                    (self.tokens.get(self.pos), self.tokens.get(self.pos + 1))
+                    && !self.defines.contains_key(name.as_str())
+                {
+                    self.pos += 2;
+                    return self.unary();
+                }
+                let val = self.expr(0)?;
+                match self.next()? {
+                    Token::RParen => Some(val),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin::c::macromap;
+    use crate::regmap::RegisterMap;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn eval(expr: &str) -> Option<TypeValue> {
+        let defines = HashMap::from([("A_POS", "(4U)"), ("A_MSK", "(0x3UL << A_POS)"), ("LOOP", "LOOP")]);
+        evaluate(&defines, expr, 0)
+    }
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(eval("(0x1FU)"), Some(0x1F));
+        assert_eq!(eval("0b101"), Some(5));
+        assert_eq!(eval("010"), Some(8));
+        assert_eq!(eval("1 + 2 * 3"), Some(7));
+        assert_eq!(eval("(1 | 2) << 4"), Some(0x30));
+        assert_eq!(eval("A_MSK"), Some(0x30));
+        assert_eq!(eval("((uint32_t)0x40000000UL)"), Some(0x4000_0000));
+        assert_eq!(eval("~0U & 0xFF"), Some(0xFF));
+        assert_eq!(eval("LOOP"), None);
+        assert_eq!(eval("UNKNOWN"), None);
+        assert_eq!(eval("\"string\""), None);
+        assert_eq!(eval("1 +"), None);
+    }
+
+    #[test]
+    fn test_parse_defines() {
+        let header = "
+            #ifndef GUARD
+            #define GUARD
+            #define A (1U) /* comment */
+            # define B \\
+                (2U) // comment
+            #define F(x) ((x) << 1)
+            /* #define C 3 */
+        ";
+        let defines = parse_defines(header);
+        let should = vec![
+            ("GUARD".to_string(), "".to_string()),
+            ("A".to_string(), "(1U)".to_string()),
+            ("B".to_string(), "(2U)".to_string()),
+        ];
+        assert_eq!(defines, should);
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        assert_eq!(common_prefix(&["CHIP_REG_A", "CHIP_REG_B"]), "CHIP_REG_");
+        assert_eq!(common_prefix(&["CHIP_A", "CHIP_AB"]), "CHIP_");
+        assert_eq!(common_prefix(&["A", "B"]), "");
+        assert_eq!(common_prefix(&["CHIP_A"]), "");
+    }
+
+    #[test]
+    fn test_import_vendor_header() {
+        let header = "
+            #define DEV_CTRL_ADDR      0x10
+            #define DEV_CTRL_RESET     0x0100
+            #define DEV_CTRL_EN_Pos    0U
+            #define DEV_CTRL_EN_Msk    (0x1UL << DEV_CTRL_EN_Pos)
+            #define DEV_CTRL_MODE_Pos  8U
+            #define DEV_CTRL_MODE_Msk  (0x3UL << DEV_CTRL_MODE_Pos)
+            #define DEV_CTRL_MODE_VAL_FAST (0x2UL << DEV_CTRL_MODE_Pos)
+            #define DEV_CTRL_MODE_VAL_SLOW 0x1
+            #define DEV_CTRL_BAD_Pos   3U
+            #define DEV_CTRL_BAD_Msk   0x5U
+            #define DEV_STAT_ADDR      0x11
+            #define DEV_WIDE_ADDR      0x12
+            #define DEV_WIDE_HI_Pos    72U
+            #define DEV_WIDE_HI_Msk    0xFF000000000000000000
+            #define DEV_FULL_ADDR      0x13
+            #define DEV_FULL_VAL_Pos   0U
+            #define DEV_FULL_VAL_Msk   0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF
+        ";
+
+        let imported = import(header, &ImportOpts::default()).unwrap();
+        assert_eq!(imported.warnings, vec!["Ignoring field 'DEV_CTRL_BAD': mask 0x5 is not contiguous"]);
+
+        let map = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+        assert_eq!(map.name, "DEV");
+
+        let ctrl = &map.registers["CTRL"];
+        assert_eq!(ctrl.adr, 0x10);
+        assert_eq!(ctrl.reset_val, Some(0x100));
+        assert_eq!(ctrl.layout.bitwidth, 16);
+        assert_eq!(ctrl.layout.fields["EN"].bits, (0..=0).into());
+        assert_eq!(ctrl.layout.fields["MODE"].bits, (8..=9).into());
+        let crate::regmap::FieldType::Enum(e) = &ctrl.layout.fields["MODE"].accepts else {
+            panic!("MODE should be an enum");
+        };
+        assert_eq!(e.entries["FAST"].value, 2);
+        assert_eq!(e.entries["SLOW"].value, 1);
+
+        let stat = &map.registers["STAT"];
+        assert_eq!(stat.adr, 0x11);
+        assert_eq!(stat.layout.bitwidth, 8);
+        assert!(stat.layout.fields.is_empty());
+
+        let wide = &map.registers["WIDE"];
+        assert_eq!(wide.layout.bitwidth, 128);
+        assert_eq!(wide.layout.fields["HI"].bits, (72..=79).into());
+
+        let full = &map.registers["FULL"];
+        assert_eq!(full.layout.fields["VAL"].bits, (0..=127).into());
+    }
+
+    #[test]
+    fn test_import_macromap_roundtrip() {
+        let original = RegisterMap::from_yaml_str(include_str!("../../../examples/maps/dummy.yaml")).unwrap();
+        let mut header = String::new();
+        macromap::generate(&mut header, &original, Path::new("dummy.h"), &macromap::GeneratorOpts::default()).unwrap();
+
+        let imported = import(&header, &ImportOpts::default()).unwrap();
+        let map = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+
+        assert_eq!(map.name, "DUMMYCHIP");
+        for register in original.registers.values() {
+            let name = register.name.to_uppercase();
+            let imported = &map.registers[&name];
+            assert_eq!(imported.adr, register.adr, "{name}");
+            assert_eq!(imported.reset_val, register.reset_val, "{name}");
+        }
+
+        let ctrl = &map.registers["CTRL"];
+        assert_eq!(ctrl.layout.fields.keys().collect::<Vec<_>>(), vec!["CTRL", "STATUS"]);
+        let crate::regmap::FieldType::Enum(e) = &ctrl.layout.fields["STATUS"].accepts else {
+            panic!("STATUS should be an enum");
+        };
+        assert_eq!(e.entries["HOT"].value, 3);
+
+        let block = &map.register_blocks["CHN"];
+        assert_eq!(block.instances.keys().collect::<Vec<_>>(), vec!["CH1", "CH2", "CH3"]);
+        assert_eq!(block.members["CHN_CONFIG"].layout.fields["VAL"].bits, (0..=7).into());
+    }
+
+    #[test]
+    fn test_import_block_address_overflow() {
+        let header = "
+            #define DEV_BASE_INSTANCE_A 0xFFFFFFFFFFFFFFF0
+            #define DEV_BASE_X_OFFSET   0x20
+            #define DEV_A_X_ADDR        0x10
+        ";
+
+        let imported = import(header, &ImportOpts::default()).unwrap();
+        assert!(imported.map.registers.contains_key("A_X"));
+    }
+}
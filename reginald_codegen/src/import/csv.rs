@@ -0,0 +1,630 @@
+//! Reconstruct a register listing from a CSV table, such as a spreadsheet export.
+//!
+//! Every row describes a register, a field of a register, or an enum value of
+//! a field, depending on which of the mapped columns are filled in. Empty
+//! register and field cells continue the register and field of the previous
+//! row, so sheets with merged cells can be imported directly:
+//!
+//! ```text
+//! Register, Address, Bitwidth, Field,  Bits, Access, Reset, Enum Value, Enum Name, Description
+//! CTRL,     0x00,    8,        ,       ,     ,       0x01,  ,           ,          Control register
+//! ,         ,        ,         EN,     0,    RW,     ,      ,           ,          Enable
+//! ,         ,        ,         MODE,   2:1,  RW,     ,      ,           ,          Mode
+//! ,         ,        ,         ,       ,     ,       ,      0,          SLOW,      Slow mode
+//! ,         ,        ,         ,       ,     ,       ,      1,          FAST,      Fast mode
+//! ```
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "clap")]
+use clap::Parser;
+
+use crate::{
+    error::Error,
+    regmap::{BitRange, FieldBits, MAX_BITWIDTH, TypeAdr, TypeBitwidth, TypeValue, listing},
+};
+
+use super::{Imported, fitting_bitwidth, listing_bits, sanitize_name};
+
+// ====== Column Mapping =======================================================
+
+/// Header names of the CSV columns. Matched case-insensitively.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct CsvColumns {
+    /// Header of the register name column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().register_column))]
+    pub register_column: String,
+
+    /// Header of the register address column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().address_column))]
+    pub address_column: String,
+
+    /// Header of the register bitwidth column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().bitwidth_column))]
+    pub bitwidth_column: String,
+
+    /// Header of the field name column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().field_column))]
+    pub field_column: String,
+
+    /// Header of the field bit range column (for example '7:4', '[3]', '4-7' or '7, 3:1')
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().bits_column))]
+    pub bits_column: String,
+
+    /// Header of the field access column (for example 'RW', 'R/W', 'RO' or 'WO')
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().access_column))]
+    pub access_column: String,
+
+    /// Header of the reset value column
+    ///
+    /// Reset values given in a field row are values of that field.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().reset_column))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub reset_column: String,
+
+    /// Header of the description column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().description_column))]
+    pub description_column: String,
+
+    /// Header of the enum value column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().enum_value_column))]
+    pub enum_value_column: String,
+
+    /// Header of the enum name column
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().enum_name_column))]
+    pub enum_name_column: String,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        Self {
+            register_column: "Register".to_string(),
+            address_column: "Address".to_string(),
+            bitwidth_column: "Bitwidth".to_string(),
+            field_column: "Field".to_string(),
+            bits_column: "Bits".to_string(),
+            access_column: "Access".to_string(),
+            reset_column: "Reset".to_string(),
+            description_column: "Description".to_string(),
+            enum_value_column: "Enum Value".to_string(),
+            enum_name_column: "Enum Name".to_string(),
+        }
+    }
+}
+
+impl CsvColumns {
+    /// All column headers, in canonical order.
+    pub fn headers(&self) -> [&str; 10] {
+        [
+            &self.register_column,
+            &self.address_column,
+            &self.bitwidth_column,
+            &self.field_column,
+            &self.bits_column,
+            &self.access_column,
+            &self.reset_column,
+            &self.enum_value_column,
+            &self.enum_name_column,
+            &self.description_column,
+        ]
+    }
+}
+
+// ====== Import Opts ==========================================================
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(Parser))]
+pub struct ImportOpts {
+    /// Name of the register map
+    ///
+    /// Defaults to the input file name.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub name: Option<String>,
+
+    /// Bitwidth of registers without an entry in the bitwidth column
+    ///
+    /// If not given, the smallest of 8, 16, 32, 64 or 128 bits that fits all
+    /// fields and the reset value of a register is used.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(verbatim_doc_comment))]
+    pub bitwidth: Option<TypeBitwidth>,
+
+    /// Field delimiter
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "clap", arg(default_value_t = Self::default().delimiter))]
+    pub delimiter: char,
+
+    #[cfg_attr(feature = "clap", command(flatten))]
+    pub columns: CsvColumns,
+}
+
+impl Default for ImportOpts {
+    fn default() -> Self {
+        Self {
+            name: None,
+            bitwidth: None,
+            delimiter: ',',
+            columns: CsvColumns::default(),
+        }
+    }
+}
+
+// ====== Import ===============================================================
+
+#[derive(Default)]
+struct Register {
+    name: String,
+    adr: Option<TypeAdr>,
+    bitwidth: Option<TypeBitwidth>,
+    reset_val: Option<TypeValue>,
+    doc: Option<String>,
+    fields: Vec<Field>,
+}
+
+struct Field {
+    name: String,
+    bits: FieldBits,
+    access: Option<listing::Access>,
+    reset_val: Option<TypeValue>,
+    doc: Option<String>,
+    entries: listing::EnumEntries,
+}
+
+/// Column indices of the mapped columns that are present.
+struct ColumnIndices {
+    register: Option<usize>,
+    address: Option<usize>,
+    bitwidth: Option<usize>,
+    field: Option<usize>,
+    bits: Option<usize>,
+    access: Option<usize>,
+    reset: Option<usize>,
+    description: Option<usize>,
+    enum_value: Option<usize>,
+    enum_name: Option<usize>,
+}
+
+pub fn import(input: &str, opts: &ImportOpts) -> Result<Imported, Error> {
+    let Some(name) = &opts.name else {
+        return Err(Error::ImportError("No map name specified".to_string()));
+    };
+    if !opts.delimiter.is_ascii() {
+        return Err(Error::ImportError(format!("Delimiter '{}' is not an ASCII character", opts.delimiter)));
+    }
+
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(opts.delimiter as u8)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let find = |column: &str| {
+        headers
+            .iter()
+            .position(|x| x.trim().eq_ignore_ascii_case(column.trim()))
+    };
+    let cols = ColumnIndices {
+        register: find(&opts.columns.register_column),
+        address: find(&opts.columns.address_column),
+        bitwidth: find(&opts.columns.bitwidth_column),
+        field: find(&opts.columns.field_column),
+        bits: find(&opts.columns.bits_column),
+        access: find(&opts.columns.access_column),
+        reset: find(&opts.columns.reset_column),
+        description: find(&opts.columns.description_column),
+        enum_value: find(&opts.columns.enum_value_column),
+        enum_name: find(&opts.columns.enum_name_column),
+    };
+    for (col, header) in [
+        (cols.register, &opts.columns.register_column),
+        (cols.address, &opts.columns.address_column),
+    ] {
+        if col.is_none() {
+            return Err(Error::ImportError(format!("Required column '{header}' not found")));
+        }
+    }
+
+    let mut warnings = vec![];
+    let mut registers: Vec<Register> = vec![];
+    let mut current_reg: Option<usize> = None;
+    let mut current_field: Option<usize> = None;
+
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map_or(0, |x| x.line());
+        let cell = |col: Option<usize>| col.and_then(|x| record.get(x)).map(str::trim).filter(|x| !x.is_empty());
+        let mut warn = |msg: String| warnings.push(format!("Line {line}: {msg}"));
+
+        if record.iter().all(|x| x.trim().is_empty()) {
+            continue;
+        }
+
+        // Register:
+        if let Some(reg_name) = cell(cols.register) {
+            let reg_name = sanitize_name(reg_name);
+            let idx = match registers.iter().position(|x| x.name == reg_name) {
+                Some(idx) => idx,
+                None => {
+                    registers.push(Register {
+                        name: reg_name,
+                        ..Default::default()
+                    });
+                    registers.len() - 1
+                }
+            };
+            if current_reg != Some(idx) {
+                current_reg = Some(idx);
+                current_field = None;
+            }
+        }
+        let Some(reg_idx) = current_reg else {
+            warn("Ignoring row without register".to_string());
+            continue;
+        };
+        let reg = &mut registers[reg_idx];
+
+        if let Some(adr) = cell(cols.address) {
+            match (parse_value(adr).and_then(|x| TypeAdr::try_from(x).ok()), reg.adr) {
+                (None, _) => warn(format!("Ignoring invalid address '{adr}'")),
+                (Some(adr), None) => reg.adr = Some(adr),
+                (Some(adr), Some(existing)) if adr != existing => {
+                    warn(format!("Ignoring address 0x{adr:X}: register '{}' is at 0x{existing:X}", reg.name))
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(bitwidth) = cell(cols.bitwidth) {
+            match (bitwidth.parse::<TypeBitwidth>().ok().filter(|x| *x != 0), reg.bitwidth) {
+                (None, _) => warn(format!("Ignoring invalid bitwidth '{bitwidth}'")),
+                (Some(bitwidth), None) => reg.bitwidth = Some(bitwidth),
+                (Some(bitwidth), Some(existing)) if bitwidth != existing => {
+                    warn(format!("Ignoring bitwidth {bitwidth}: register '{}' is {existing} bits wide", reg.name))
+                }
+                _ => (),
+            }
+        }
+
+        // Field:
+        let bits = match cell(cols.bits) {
+            Some(bits) => match parse_bits(bits) {
+                Some(bits) => Some(bits),
+                None => {
+                    warn(format!("Ignoring invalid bit range '{bits}'"));
+                    None
+                }
+            },
+            None => None,
+        };
+        let field_in_row = cell(cols.field).is_some();
+        if let Some(field_name) = cell(cols.field) {
+            let field_name = sanitize_name(field_name);
+            match reg.fields.iter().position(|x| x.name == field_name) {
+                Some(idx) => current_field = Some(idx),
+                None => {
+                    let Some(bits) = bits else {
+                        warn(format!("Ignoring field '{field_name}' without bit range"));
+                        continue;
+                    };
+                    reg.fields.push(Field {
+                        name: field_name,
+                        bits,
+                        access: None,
+                        reset_val: None,
+                        doc: None,
+                        entries: listing::EnumEntries::new(),
+                    });
+                    current_field = Some(reg.fields.len() - 1);
+                }
+            }
+        }
+
+        // Enum entry:
+        let enum_entry = match (cell(cols.enum_value), cell(cols.enum_name)) {
+            (Some(val), Some(entry_name)) => match (parse_value(val), current_field) {
+                (Some(val), Some(field_idx)) => {
+                    let entry_name = sanitize_name(entry_name);
+                    let entry = listing::EnumEntry { val, doc: None };
+                    reg.fields[field_idx].entries.insert(entry_name.clone(), entry);
+                    Some((field_idx, entry_name))
+                }
+                (None, _) => {
+                    warn(format!("Ignoring invalid enum value '{val}'"));
+                    None
+                }
+                (_, None) => {
+                    warn(format!("Ignoring enum entry '{entry_name}' without field"));
+                    None
+                }
+            },
+            (None, None) => None,
+            _ => {
+                warn("Ignoring enum entry without both name and value".to_string());
+                None
+            }
+        };
+
+        if let Some(access) = cell(cols.access) {
+            match (parse_access(access), current_field) {
+                (Some(access), Some(field_idx)) if field_in_row => reg.fields[field_idx].access = Some(access),
+                (None, _) => warn(format!("Ignoring unknown access '{access}'")),
+                _ => (),
+            }
+        }
+
+        if let Some(reset) = cell(cols.reset) {
+            match (parse_value(reset), current_field) {
+                (None, _) => warn(format!("Ignoring invalid reset value '{reset}'")),
+                (Some(val), Some(field_idx)) if field_in_row => reg.fields[field_idx].reset_val = Some(val),
+                (Some(val), _) => reg.reset_val = Some(val),
+            }
+        }
+
+        if let Some(description) = cell(cols.description) {
+            let doc = Some(description.to_string());
+            match (enum_entry, current_field) {
+                (Some((field_idx, entry_name)), _) => {
+                    reg.fields[field_idx].entries.get_mut(&entry_name).unwrap().doc = doc;
+                }
+                (None, Some(field_idx)) if field_in_row => reg.fields[field_idx].doc = doc,
+                _ => reg.doc = doc,
+            }
+        }
+    }
+
+    // Assemble listing:
+    let mut listing_registers = BTreeMap::new();
+    for reg in registers {
+        let Some(adr) = reg.adr else {
+            warnings.push(format!("Ignoring register '{}' without address", reg.name));
+            continue;
+        };
+
+        let mut layout = listing::LayoutFields::new();
+        let mut occupied = 0;
+        let mut field_resets = None;
+        for field in reg.fields {
+            occupied |= field.bits.mask();
+            if let Some(reset) = field.reset_val {
+                field_resets = Some(field_resets.unwrap_or(0) | field.bits.insert(reset));
+            }
+            let accepts = if field.entries.is_empty() {
+                listing::FieldType::UInt
+            } else {
+                listing::FieldType::Enum(field.entries)
+            };
+            let field_listing = listing::LayoutField {
+                bits: field_listing_bits(&field.bits),
+                doc: field.doc,
+                accepts,
+                access: field.access,
+                unit: None,
+                scale: None,
+                offset: None,
+                table: None,
+                min: None,
+                max: None,
+                step: None,
+                reserved: None,
+            };
+            layout.insert(field.name, field_listing);
+        }
+
+        let reset_val = reg.reset_val.or(field_resets);
+        let register = listing::Register {
+            adr,
+            doc: reg.doc,
+            bitwidth: Some(
+                reg.bitwidth
+                    .or(opts.bitwidth)
+                    .unwrap_or(fitting_bitwidth(occupied | reset_val.unwrap_or(0))),
+            ),
+            bit_order: None,
+            reset_val,
+            layout: listing::RegisterLayout::Layout(layout),
+        };
+        listing_registers.insert(reg.name, listing::RegisterListing::Register(register));
+    }
+
+    let map = listing::RegisterMap {
+        name: name.clone(),
+        registers: listing_registers,
+        ..Default::default()
+    };
+
+    Ok(Imported { map, warnings })
+}
+
+fn csv_error(err: ::csv::Error) -> Error {
+    Error::ImportError(format!("Failed to read CSV: {err}"))
+}
+
+/// Parse a decimal, hexadecimal ('0x..' or '..h') or binary ('0b..') number.
+fn parse_value(s: &str) -> Option<TypeValue> {
+    let s = s.replace('_', "");
+    if let Some(hex) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+        TypeValue::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = s.strip_prefix("0b").or(s.strip_prefix("0B")) {
+        TypeValue::from_str_radix(bin, 2).ok()
+    } else if let Some(hex) = s.strip_suffix('h').or(s.strip_suffix('H')) {
+        TypeValue::from_str_radix(hex, 16).ok()
+    } else {
+        s.parse().ok()
+    }
+}
+
+/// Parse the bits of a field: A single bit range, or a comma-separated list of ranges
+/// of a split field, most significant part first (such as '7, 3:1').
+fn parse_bits(s: &str) -> Option<FieldBits> {
+    let ranges: Option<Vec<BitRange>> = s.split(',').map(parse_range).collect();
+    Some(FieldBits(ranges?))
+}
+
+/// Parse a bit range such as '7:4', '[7:4]', '4-7', '4..7' or '3'.
+fn parse_range(s: &str) -> Option<BitRange> {
+    let s = s.trim().trim_start_matches('[').trim_end_matches(']');
+    let parts: Vec<&str> = s.split([':', '-']).flat_map(|x| x.split("..")).map(str::trim).collect();
+    let (lsb, msb): (TypeBitwidth, TypeBitwidth) = match parts.as_slice() {
+        [bit] => {
+            let bit = bit.parse().ok()?;
+            (bit, bit)
+        }
+        [a, b] => {
+            let (a, b): (TypeBitwidth, TypeBitwidth) = (a.parse().ok()?, b.parse().ok()?);
+            (a.min(b), a.max(b))
+        }
+        _ => return None,
+    };
+    if msb >= MAX_BITWIDTH {
+        return None;
+    }
+    Some((lsb..=msb).into())
+}
+
+/// Listing representation of the bits of a field.
+fn field_listing_bits(bits: &FieldBits) -> listing::Bits {
+    let mut ranges: Vec<listing::Bits> = bits
+        .ranges()
+        .iter()
+        .map(|x| listing_bits(x.lsb_pos(), x.msb_pos()))
+        .collect();
+    if ranges.len() == 1 {
+        ranges.remove(0)
+    } else {
+        listing::Bits::Split(ranges)
+    }
+}
+
+/// Parse an access specification such as 'RW', 'R/W', 'RO', 'WO' or 'read-only'.
+fn parse_access(s: &str) -> Option<listing::Access> {
+    let normalized: String = s
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase();
+    match normalized.as_str() {
+        "rw" | "readwrite" => Some(vec![listing::AccessMode::R, listing::AccessMode::W]),
+        "r" | "ro" | "read" | "readonly" => Some(vec![listing::AccessMode::R]),
+        "w" | "wo" | "write" | "writeonly" => Some(vec![listing::AccessMode::W]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regmap::{AccessMode, FieldType, RegisterMap};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_bits() {
+        assert_eq!(parse_bits("3"), Some((3..=3).into()));
+        assert_eq!(parse_bits("[7:4]"), Some((4..=7).into()));
+        assert_eq!(parse_bits("4-7"), Some((4..=7).into()));
+        assert_eq!(parse_bits("0..2"), Some((0..=2).into()));
+        assert_eq!(parse_bits("7, 3:1"), Some(FieldBits(vec![(7..=7).into(), (1..=3).into()])));
+        assert_eq!(parse_bits("a:b"), None);
+        assert_eq!(parse_bits("1:2:3"), None);
+        assert_eq!(parse_bits("200"), None);
+        assert_eq!(parse_bits("7:200"), None);
+        assert_eq!(parse_bits("7,"), None);
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("12"), Some(12));
+        assert_eq!(parse_value("0x1F"), Some(0x1F));
+        assert_eq!(parse_value("1Fh"), Some(0x1F));
+        assert_eq!(parse_value("0b1010_0101"), Some(0xA5));
+        assert_eq!(parse_value("abc"), None);
+    }
+
+    #[test]
+    fn test_import() {
+        let input = "\
+Register;Address;Field;Bits;Access;Reset;Enum Value;Enum Name;Description
+CTRL;0x00;;;;;;;Control register
+;;EN;0;RW;1;;;Enable
+;;MODE;2:1;RO;;;;Mode
+;;;;;;0;SLOW;Slow mode
+;;;;;;1;FAST;Fast mode
+STATUS;0x01;;;;0x80;;;
+STATUS;;FLAG;7;R/W;;;;
+;;;;;;;;
+;;BAD;;;;;;
+";
+        let opts = ImportOpts {
+            name: Some("test".to_string()),
+            delimiter: ';',
+            ..Default::default()
+        };
+        let imported = import(input, &opts).unwrap();
+        assert_eq!(imported.warnings, vec!["Line 10: Ignoring field 'BAD' without bit range"]);
+
+        let map = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+
+        let ctrl = &map.registers["CTRL"];
+        assert_eq!(ctrl.adr, 0);
+        assert_eq!(ctrl.reset_val, Some(0x1));
+        assert_eq!(ctrl.docs.brief.as_deref(), Some("Control register"));
+        assert_eq!(ctrl.layout.fields["EN"].access, Some(vec![AccessMode::R, AccessMode::W]));
+        assert_eq!(ctrl.layout.fields["MODE"].bits, (1..=2).into());
+        assert_eq!(ctrl.layout.fields["MODE"].access, Some(vec![AccessMode::R]));
+        let FieldType::Enum(e) = &ctrl.layout.fields["MODE"].accepts else {
+            panic!("MODE should be an enum");
+        };
+        assert_eq!(e.entries["FAST"].value, 1);
+        assert_eq!(e.entries["SLOW"].docs.brief.as_deref(), Some("Slow mode"));
+
+        let status = &map.registers["STATUS"];
+        assert_eq!(status.adr, 1);
+        assert_eq!(status.reset_val, Some(0x80));
+        assert_eq!(status.layout.fields["FLAG"].bits, (7..=7).into());
+    }
+
+    #[test]
+    fn test_import_invalid_bits() {
+        let input = "\
+Register,Address,Field,Bits
+R,0x0,A,200
+R,0x0,B,3:0
+";
+        let opts = ImportOpts {
+            name: Some("test".to_string()),
+            ..Default::default()
+        };
+        let imported = import(input, &opts).unwrap();
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "Line 2: Ignoring invalid bit range '200'",
+                "Line 2: Ignoring field 'A' without bit range",
+            ]
+        );
+
+        let map = RegisterMap::from_yaml_str(&imported.map.to_yaml().unwrap()).unwrap();
+        let fields = &map.registers["R"].layout.fields;
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["B"]);
+    }
+
+    #[test]
+    fn test_import_missing_column() {
+        let opts = ImportOpts {
+            name: Some("test".to_string()),
+            ..Default::default()
+        };
+        assert!(import("Name,Address\nA,0\n", &opts).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+pub mod c_header;
+pub mod csv;
+
+use crate::regmap::{TypeBitwidth, TypeValue, listing};
+
+/// Register map reconstructed by an importer.
+#[derive(Debug)]
+pub struct Imported {
+    pub map: listing::RegisterMap,
+    /// Definitions that could not be imported, and why.
+    pub warnings: Vec<String>,
+}
+
+/// Listing bit specification of the given bit range.
+fn listing_bits(lsb: TypeBitwidth, msb: TypeBitwidth) -> listing::Bits {
+    if lsb == msb {
+        listing::Bits::Bit(lsb)
+    } else {
+        listing::Bits::Range(format!("{msb}-{lsb}"))
+    }
+}
+
+/// Replace all characters that are not allowed in names with underscores.
+fn sanitize_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// Smallest common register width (8, 16, 32, 64 or 128 bits) that fits the given value.
+fn fitting_bitwidth(val: TypeValue) -> TypeBitwidth {
+    match val {
+        0..=0xFF => 8,
+        0x100..=0xFFFF => 16,
+        0x1_0000..=0xFFFF_FFFF => 32,
+        0x1_0000_0000..=0xFFFF_FFFF_FFFF_FFFF => 64,
+        _ => 128,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("CTRL_1"), "CTRL_1");
+        assert_eq!(sanitize_name(" Control Reg. "), "Control_Reg_");
+        assert_eq!(sanitize_name("1V8_EN"), "_1V8_EN");
+    }
+
+    #[test]
+    fn test_fitting_bitwidth() {
+        assert_eq!(fitting_bitwidth(0), 8);
+        assert_eq!(fitting_bitwidth(0xFF), 8);
+        assert_eq!(fitting_bitwidth(0x100), 16);
+        assert_eq!(fitting_bitwidth(0x8000_0000), 32);
+        assert_eq!(fitting_bitwidth(0x1_0000_0000), 64);
+        assert_eq!(fitting_bitwidth(0x1_0000_0000_0000_0000), 128);
+    }
+}
@@ -4,6 +4,7 @@
 pub mod bits;
 pub mod builtin;
 pub mod error;
+pub mod import;
 pub mod regmap;
 pub mod utils;
 pub mod writer;
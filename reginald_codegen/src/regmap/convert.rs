@@ -1,6 +1,6 @@
 use crate::{
     error::Error,
-    regmap::{BitRange, Layout, validate::validate_docs},
+    regmap::{BitOrder, BitRange, FieldBits, Layout, validate::validate_docs},
 };
 
 use reginald_utils::join_with_underscore;
@@ -9,12 +9,14 @@ use regex::Regex;
 use std::{collections::BTreeMap, path::PathBuf, rc::Rc, sync::LazyLock};
 
 use super::{
-    Access, AccessMode, Defaults, Docs, Enum, EnumEntry, FieldType, LayoutField, Register, RegisterBlock,
-    RegisterBlockInstance, RegisterBlockMember, RegisterMap, RegisterOrigin, TypeBitwidth, TypeValue,
+    Access, AccessMode, AddressSpace, Defaults, Docs, Enum, EnumEntry, FieldConstraints, FieldType, LayoutField,
+    Register, RegisterBlock, RegisterBlockInstance, RegisterBlockMember, RegisterMap, RegisterOrigin, Scaling,
+    ScalingConversion, TypeBitwidth, TypeValue, VirtualField, VirtualFieldPart,
     listing::{self},
     validate::{
         Namespace, validate_bitpos, validate_bitwidth, validate_enum, validate_layout, validate_map_author,
-        validate_name, validate_name_unique, validate_register, validate_register_properties,
+        validate_name, validate_name_unique, validate_register, validate_register_addresses,
+        validate_register_properties, validate_virtual_field,
     },
 };
 
@@ -32,6 +34,10 @@ pub fn convert_map(m: &listing::RegisterMap, input_file: &Option<PathBuf>) -> Re
     validate_map_author(&author, bt)?;
     let docs = convert_docs(&m.doc, bt)?;
     let defaults = convert_defaults(&m.defaults, bt)?;
+    let address_space = match &m.address_space {
+        Some(address_space) => Some(convert_address_space(address_space, bt)?),
+        None => None,
+    };
 
     // Construct empty register map:
     let mut map = RegisterMap {
@@ -41,21 +47,25 @@ pub fn convert_map(m: &listing::RegisterMap, input_file: &Option<PathBuf>) -> Re
         notice,
         author,
         defaults,
+        address_space,
         enums: BTreeMap::new(),
         layouts: BTreeMap::new(),
         register_blocks: BTreeMap::new(),
         registers: BTreeMap::new(),
+        virtual_fields: BTreeMap::new(),
     };
 
     // Namespace map to detect naming collisions and provide nice error messages:
     let mut namespace: Namespace = BTreeMap::new();
 
     // Convert data.
-    // Note: Order matters. Registers depend on layouts and enums. Layouts depend
-    // on enums.
+    // Note: Order matters. Virtual fields depend on registers. Registers depend
+    // on layouts and enums. Layouts depend on enums.
     convert_shared_enums(&mut map, &mut namespace, m, bt)?;
     convert_shared_layouts(&mut map, &mut namespace, m, bt)?;
     convert_registers(&mut map, &mut namespace, m, bt)?;
+    validate_register_addresses(&map, bt)?;
+    convert_virtual_fields(&mut map, &mut namespace, m, bt)?;
 
     Ok(map)
 }
@@ -71,16 +81,90 @@ fn convert_defaults(defaults: &listing::Defaults, bt: &str) -> Result<Defaults,
     Ok(Defaults {
         layout_bitwidth: defaults.layout_bitwidth,
         field_access_mode: defaults.field_access_mode.as_ref().map(convert_access_list),
+        bit_order: defaults.bit_order.map(convert_bit_order).unwrap_or_default(),
+    })
+}
+
+fn convert_address_space(address_space: &listing::AddressSpace, bt: &str) -> Result<AddressSpace, Error> {
+    let bt = bt.to_owned() + ".address_space";
+    if let Some(unit_bitwidth) = address_space.unit_bitwidth {
+        validate_bitwidth(unit_bitwidth, &(bt + ".unit_bitwidth"))?;
+    }
+
+    Ok(AddressSpace {
+        unit_bitwidth: address_space.unit_bitwidth,
+        aligned: address_space.aligned,
     })
 }
 
-fn convert_bits(bits: &listing::Bits, bt: &str) -> Result<BitRange, Error> {
+fn convert_bit_order(bit_order: listing::BitOrder) -> BitOrder {
+    match bit_order {
+        listing::BitOrder::Lsb0 => BitOrder::Lsb0,
+        listing::BitOrder::Msb0 => BitOrder::Msb0,
+    }
+}
+
+fn convert_layout_bit_order(map: &RegisterMap, bit_order: &Option<listing::BitOrder>) -> BitOrder {
+    bit_order.map(convert_bit_order).unwrap_or(map.defaults.bit_order)
+}
+
+/// Convert the bits of a field in a layout of the given bitwidth and bit order.
+///
+/// MSB-0 bit numbers are normalised to LSB-0 positions.
+fn convert_bits(
+    bits: &listing::Bits,
+    bitwidth: TypeBitwidth,
+    bit_order: BitOrder,
+    bt: &str,
+) -> Result<FieldBits, Error> {
     let bt = bt.to_owned() + ".bits";
     let result = match bits {
-        listing::Bits::Bit(bitpos) => convert_bitpos(*bitpos, &bt)?,
-        listing::Bits::Range(range) => convert_bitrange(range, &bt)?,
+        listing::Bits::Split(ranges) => {
+            if ranges.is_empty() {
+                return Err(Error::ConversionError {
+                    bt,
+                    msg: "Split field requires at least one bit range.".to_string(),
+                });
+            }
+            let ranges: Result<Vec<BitRange>, Error> =
+                ranges.iter().map(|range| convert_bits_range(range, &bt)).collect();
+            FieldBits(ranges?)
+        }
+        range => convert_bits_range(range, &bt)?.into(),
     };
-    Ok(result)
+
+    match bit_order {
+        BitOrder::Lsb0 => Ok(result),
+        BitOrder::Msb0 => {
+            let ranges: Result<Vec<BitRange>, Error> = result
+                .ranges()
+                .iter()
+                .map(|range| msb0_to_lsb0(range, bitwidth, &bt))
+                .collect();
+            Ok(FieldBits(ranges?))
+        }
+    }
+}
+
+fn msb0_to_lsb0(range: &BitRange, bitwidth: TypeBitwidth, bt: &str) -> Result<BitRange, Error> {
+    if range.msb_pos() >= bitwidth {
+        return Err(Error::ConversionError {
+            bt: bt.to_string(),
+            msg: format!("MSB-0 bit {} is outside the {bitwidth}-bit layout.", range.msb_pos()),
+        });
+    }
+    Ok(BitRange((bitwidth - 1 - range.msb_pos())..=(bitwidth - 1 - range.lsb_pos())))
+}
+
+fn convert_bits_range(bits: &listing::Bits, bt: &str) -> Result<BitRange, Error> {
+    match bits {
+        listing::Bits::Bit(bitpos) => convert_bitpos(*bitpos, bt),
+        listing::Bits::Range(range) => convert_bitrange(range, bt),
+        listing::Bits::Split(_) => Err(Error::ConversionError {
+            bt: bt.to_string(),
+            msg: "Split bit ranges cannot be nested.".to_string(),
+        }),
+    }
 }
 
 fn convert_bitpos(bitpos: TypeBitwidth, bt: &str) -> Result<BitRange, Error> {
@@ -275,11 +359,14 @@ fn convert_shared_layouts(
         validate_name_unique(shared_layout_name, namespace, &bt)?;
 
         let docs = convert_docs(&shared_layout.doc, &bt)?;
-        let fields = convert_layout_fields(map, namespace, &shared_layout.layout, &bt)?;
+        let bitwidth = convert_bitwidth(map, &shared_layout.bitwidth, &bt)?;
+        let bit_order = convert_layout_bit_order(map, &shared_layout.bit_order);
+        let fields = convert_layout_fields(map, namespace, &shared_layout.layout, bitwidth, bit_order, &bt)?;
 
         let layout = Layout {
             name: shared_layout_name.clone(),
-            bitwidth: convert_bitwidth(map, &shared_layout.bitwidth, &bt)?,
+            bitwidth,
+            bit_order,
             is_local: false,
             docs,
             fields,
@@ -295,6 +382,8 @@ fn convert_layout_fields(
     map: &mut RegisterMap,
     namespace: &mut Namespace,
     fields: &listing::LayoutFields,
+    bitwidth: TypeBitwidth,
+    bit_order: BitOrder,
     bt: &str,
 ) -> Result<BTreeMap<String, LayoutField>, Error> {
     let mut result: BTreeMap<String, LayoutField> = BTreeMap::new();
@@ -304,7 +393,7 @@ fn convert_layout_fields(
     for (field_name, field) in fields {
         let bt = bt.clone() + "." + field_name;
 
-        result.insert(field_name.clone(), convert_field(map, namespace, field_name, field, &bt)?);
+        result.insert(field_name.clone(), convert_field(map, namespace, field_name, field, bitwidth, bit_order, &bt)?);
     }
 
     Ok(result)
@@ -315,16 +404,19 @@ fn convert_field(
     namespace: &mut Namespace,
     field_name: &str,
     field: &listing::LayoutField,
+    layout_bitwidth: TypeBitwidth,
+    bit_order: BitOrder,
     bt: &str,
 ) -> Result<LayoutField, Error> {
     let bt = bt.to_owned() + "." + field_name;
     validate_name(field_name, &bt, "")?;
 
     // Convert bits
-    let bits = convert_bits(&field.bits, &bt)?;
+    let bits = convert_bits(&field.bits, layout_bitwidth, bit_order, &bt)?;
 
     let accepts = match &field.accepts {
         listing::FieldType::UInt => FieldType::UInt,
+        listing::FieldType::SInt => FieldType::SInt,
         listing::FieldType::Bool => FieldType::Bool,
         listing::FieldType::Fixed(fixed) => FieldType::Fixed(*fixed),
         listing::FieldType::SharedEnum(name) => {
@@ -352,7 +444,7 @@ fn convert_field(
         }
         listing::FieldType::Layout(entries) => {
             let field_width = bits.width();
-            convert_field_local_layout(map, namespace, field_name, field, entries, field_width, &bt)?
+            convert_field_local_layout(map, namespace, field_name, field, entries, field_width, bit_order, &bt)?
         }
     };
 
@@ -360,11 +452,60 @@ fn convert_field(
         name: field_name.to_owned(),
         docs: convert_docs(&field.doc, &bt)?,
         access: convert_access(map, &field.access),
+        scaling: convert_scaling(field, &bt)?,
+        constraints: convert_constraints(field, &bits),
         bits,
         accepts,
     })
 }
 
+fn convert_constraints(field: &listing::LayoutField, bits: &FieldBits) -> Option<FieldConstraints> {
+    // Drop constraints that do not restrict the field:
+    let constraints = FieldConstraints {
+        min: field.min.filter(|x| *x != 0),
+        max: field.max.filter(|x| *x != bits.unpositioned_mask()),
+        step: field.step.filter(|x| *x != 1),
+        reserved: field.reserved.clone().unwrap_or_default(),
+    };
+
+    if constraints == FieldConstraints::default() {
+        None
+    } else {
+        Some(constraints)
+    }
+}
+
+fn convert_scaling(field: &listing::LayoutField, bt: &str) -> Result<Option<Scaling>, Error> {
+    let conversion = match (&field.table, field.scale, field.offset) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            return Err(Error::ConversionError {
+                bt: bt.to_owned(),
+                msg: "A field cannot have both a lookup table and a scale/offset.".to_string(),
+            });
+        }
+        (Some(table), None, None) => ScalingConversion::Table(table.clone()),
+        (None, scale, offset) => ScalingConversion::Linear {
+            scale: scale.unwrap_or(1),
+            offset: offset.unwrap_or(0),
+        },
+    };
+
+    let Some(unit) = &field.unit else {
+        if field.table.is_some() || field.scale.is_some() || field.offset.is_some() {
+            return Err(Error::ConversionError {
+                bt: bt.to_owned(),
+                msg: "Scaled field requires a unit.".to_string(),
+            });
+        }
+        return Ok(None);
+    };
+
+    Ok(Some(Scaling {
+        unit: unit.trim().to_string(),
+        conversion,
+    }))
+}
+
 fn convert_local_enum(
     map: &mut RegisterMap,
     namespace: &mut Namespace,
@@ -392,6 +533,7 @@ fn convert_local_enum(
     Ok(FieldType::Enum(e))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn convert_field_local_layout(
     map: &mut RegisterMap,
     namespace: &mut Namespace,
@@ -399,6 +541,7 @@ fn convert_field_local_layout(
     field: &listing::LayoutField,
     entries: &listing::LayoutFields,
     bitwidth: TypeBitwidth,
+    bit_order: BitOrder,
     bt: &str,
 ) -> Result<FieldType, Error> {
     validate_name(field_name, bt, "")?;
@@ -408,8 +551,9 @@ fn convert_field_local_layout(
         name: field_name.to_owned(),
         docs: convert_docs(&field.doc, bt)?,
         bitwidth,
+        bit_order,
         is_local: true,
-        fields: convert_layout_fields(map, namespace, entries, bt)?,
+        fields: convert_layout_fields(map, namespace, entries, bitwidth, bit_order, bt)?,
     };
 
     validate_layout(&layout, bt)?;
@@ -442,6 +586,7 @@ fn convert_registers(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn convert_register_layout(
     map: &mut RegisterMap,
     namespace: &mut Namespace,
@@ -449,6 +594,7 @@ fn convert_register_layout(
     name: &str,
     docs: Docs,
     bitwidth: Option<TypeBitwidth>,
+    bit_order: Option<listing::BitOrder>,
     bt: &str,
 ) -> Result<Rc<Layout>, Error> {
     match &layout {
@@ -456,12 +602,15 @@ fn convert_register_layout(
             validate_name(name, bt, "")?;
             validate_name_unique(name, namespace, bt)?;
 
-            let fields = convert_layout_fields(map, namespace, fields, bt)?;
+            let bitwidth = convert_bitwidth(map, &bitwidth, bt)?;
+            let bit_order = convert_layout_bit_order(map, &bit_order);
+            let fields = convert_layout_fields(map, namespace, fields, bitwidth, bit_order, bt)?;
 
             let layout = Layout {
                 name: name.to_string(),
                 is_local: true,
-                bitwidth: convert_bitwidth(map, &bitwidth, bt)?,
+                bitwidth,
+                bit_order,
                 docs,
                 fields,
             };
@@ -482,6 +631,15 @@ fn convert_register_layout(
                 });
             }
 
+            if bit_order.is_some() {
+                return Err(Error::ConversionError {
+                    bt: bt.to_string(),
+                    msg:
+                        "Specified bit order for shared layout. Bit order can only be specified if specifying new layout."
+                            .to_string(),
+                });
+            }
+
             let layout = match map.layouts.get(shared_name) {
                 Some(e) if !e.is_local => Ok(e),
                 _ => Err(Error::ConversionError {
@@ -506,7 +664,8 @@ fn convert_register(
     validate_name(reg_name, &bt, "")?;
 
     let docs = convert_docs(&reg.doc, &bt)?;
-    let layout = convert_register_layout(map, namespace, &reg.layout, reg_name, docs.clone(), reg.bitwidth, &bt)?;
+    let layout =
+        convert_register_layout(map, namespace, &reg.layout, reg_name, docs.clone(), reg.bitwidth, reg.bit_order, &bt)?;
     let reg = Register {
         name: reg_name.to_owned(),
         docs,
@@ -547,8 +706,16 @@ fn convert_register_block(
         let docs = convert_docs(&member.doc, &bt)?;
         let offset = member.offset;
         let bitwidth = convert_bitwidth(map, &member.bitwidth, &bt)?;
-        let layout =
-            convert_register_layout(map, namespace, &member.layout, &member_name, docs.clone(), member.bitwidth, &bt)?;
+        let layout = convert_register_layout(
+            map,
+            namespace,
+            &member.layout,
+            &member_name,
+            docs.clone(),
+            member.bitwidth,
+            member.bit_order,
+            &bt,
+        )?;
 
         if let Some(reset_val) = member.reset_val {
             fixed_reset_vals.insert(member_name.to_string(), reset_val);
@@ -588,12 +755,20 @@ fn convert_register_block(
         }
 
         for member in members.values() {
-            let adr = member.offset + block_instance.adr;
+            let Some(adr) = block_instance.adr.checked_add(member.offset) else {
+                return Err(Error::ConversionError {
+                    bt: bt.to_string(),
+                    msg: format!(
+                        "Address of register block member '{}' in instance '{block_instance_name}' exceeds the address space!",
+                        member.name_raw
+                    ),
+                });
+            };
             let member_name_generic = &member.name;
             let member_name_raw = &member.name_raw;
             let register_instance_name = join_with_underscore(block_instance_name, &member.name_raw);
             let reset_val = match (
-                block_instance.reset_vals.get(member_name_generic),
+                block_instance.reset_vals.get(member_name_raw),
                 fixed_reset_vals.get(member_name_generic),
             ) {
                 (None, None) => None,
@@ -652,6 +827,81 @@ fn convert_register_block(
     Ok(())
 }
 
+// ==== Virtual Field Conversion ===================================================================
+
+fn convert_virtual_fields(
+    map: &mut RegisterMap,
+    namespace: &mut Namespace,
+    inp: &listing::RegisterMap,
+    bt: &str,
+) -> Result<(), Error> {
+    let bt = bt.to_owned() + ".virtual_fields";
+
+    for (name, field) in &inp.virtual_fields {
+        let bt = bt.to_owned() + "." + name;
+        validate_name(name, &bt, "")?;
+        validate_name_unique(name, namespace, &bt)?;
+
+        let signed = match &field.accepts {
+            listing::FieldType::UInt => false,
+            listing::FieldType::SInt => true,
+            _ => {
+                return Err(Error::ConversionError {
+                    bt: bt + ".accepts",
+                    msg: "Virtual fields can only accept unsigned or signed values.".to_string(),
+                });
+            }
+        };
+
+        let parts: Result<Vec<VirtualFieldPart>, Error> = field
+            .parts
+            .iter()
+            .map(|part| convert_virtual_field_part(map, part, &(bt.to_owned() + ".parts")))
+            .collect();
+
+        let field = VirtualField {
+            name: name.to_owned(),
+            docs: convert_docs(&field.doc, &bt)?,
+            signed,
+            parts: parts?,
+        };
+
+        validate_virtual_field(&field, &bt)?;
+
+        map.virtual_fields.insert(name.to_owned(), field);
+    }
+
+    Ok(())
+}
+
+fn convert_virtual_field_part(map: &RegisterMap, part: &str, bt: &str) -> Result<VirtualFieldPart, Error> {
+    let Some((register_name, field_name)) = part.split_once('.') else {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned(),
+            msg: format!("Malformed part '{part}'. Parts are given as 'REGISTER.FIELD'"),
+        });
+    };
+
+    let Some(register) = map.registers.get(register_name) else {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned(),
+            msg: format!("Register '{register_name}' not found."),
+        });
+    };
+
+    if !register.layout.fields.contains_key(field_name) {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned(),
+            msg: format!("Register '{register_name}' has no field '{field_name}'."),
+        });
+    }
+
+    Ok(VirtualFieldPart {
+        register: register.clone(),
+        field: field_name.to_owned(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::regmap::assert_regmap_eq;
@@ -880,20 +1130,156 @@ mod tests {
         assert_eq!(layout_fields, vec![String::from("FIELD_A"), String::from("FIELD_B")]);
     }
 
+    #[test]
+    fn test_convert_block_instance_reset_vals() {
+        let yaml = "
+        name: DummyChip
+        registers:
+            CHn: !RegisterBlock
+                instances:
+                    CH1:
+                        adr: 0x10
+                        reset_vals:
+                            STATUS: 0x01
+                    CH2:
+                        adr: 0x20
+                registers:
+                    STATUS:
+                        offset: 0x01
+                        bitwidth: 8
+                        layout: !Layout
+                            VAL:
+                                bits: 6-0
+        ";
+        let map = RegisterMap::from_yaml_str(yaml).unwrap();
+        assert_eq!(map.registers["CH1_STATUS"].reset_val, Some(0x01));
+        assert_eq!(map.registers["CH2_STATUS"].reset_val, None);
+    }
+
     #[test]
     fn test_convert_bits() {
-        assert_eq!(convert_bits(&listing::Bits::Bit(0), "").unwrap(), BitRange(0..=0));
-        assert_eq!(convert_bits(&listing::Bits::Bit(8), "").unwrap(), BitRange(8..=8));
-        assert_eq!(convert_bits(&listing::Bits::Range("3-4".into()), "").unwrap(), BitRange(3..=4));
+        assert_eq!(convert_bits(&listing::Bits::Bit(0), 16, BitOrder::Lsb0, "").unwrap(), (0..=0).into());
+        assert_eq!(convert_bits(&listing::Bits::Bit(8), 16, BitOrder::Lsb0, "").unwrap(), (8..=8).into());
+        assert_eq!(convert_bits(&listing::Bits::Range("3-4".into()), 16, BitOrder::Lsb0, "").unwrap(), (3..=4).into());
+        assert_eq!(
+            convert_bits(
+                &listing::Bits::Split(vec![listing::Bits::Bit(7), listing::Bits::Range("3-0".into())]),
+                16,
+                BitOrder::Lsb0,
+                ""
+            )
+            .unwrap(),
+            FieldBits(vec![BitRange(7..=7), BitRange(0..=3)])
+        );
+    }
+
+    #[test]
+    fn test_convert_bits_msb0() {
+        assert_eq!(convert_bits(&listing::Bits::Bit(0), 8, BitOrder::Msb0, "").unwrap(), (7..=7).into());
+        assert_eq!(convert_bits(&listing::Bits::Range("0-3".into()), 8, BitOrder::Msb0, "").unwrap(), (4..=7).into());
+        assert_eq!(
+            convert_bits(
+                &listing::Bits::Split(vec![listing::Bits::Bit(0), listing::Bits::Range("4-7".into())]),
+                8,
+                BitOrder::Msb0,
+                ""
+            )
+            .unwrap(),
+            FieldBits(vec![BitRange(7..=7), BitRange(0..=3)])
+        );
+        convert_bits(&listing::Bits::Bit(8), 8, BitOrder::Msb0, "").unwrap_err();
+    }
+
+    #[test]
+    fn test_convert_bit_order() {
+        let yaml = "
+        name: DummyChip
+        defaults:
+            layout_bitwidth: 8
+            bit_order: Msb0
+        layouts:
+            SHARED:
+                bit_order: Lsb0
+                layout:
+                    LOW:
+                        bits: 0
+        registers:
+            A: !Register
+                adr: 0x00
+                layout: !Layout
+                    TOP:
+                        bits: \"0-1\"
+                    NESTED:
+                        bits: \"4-7\"
+                        accepts: !Layout
+                            INNER:
+                                bits: 0
+            B: !Register
+                adr: 0x01
+                bitwidth: 16
+                bit_order: Lsb0
+                layout: !Layout
+                    LOW:
+                        bits: 0
+        ";
+        let map = RegisterMap::from_yaml_str(yaml).unwrap();
+
+        let a = &map.registers["A"].layout;
+        assert_eq!(a.bit_order, BitOrder::Msb0);
+        assert_eq!(a.fields["TOP"].bits, (6..=7).into());
+        assert_eq!(a.fields["NESTED"].bits, (0..=3).into());
+        let FieldType::Layout(nested) = &a.fields["NESTED"].accepts else {
+            panic!();
+        };
+        assert_eq!(nested.bit_order, BitOrder::Msb0);
+        assert_eq!(nested.fields["INNER"].bits, (3..=3).into());
+
+        assert_eq!(map.registers["B"].layout.fields["LOW"].bits, (0..=0).into());
+        assert_eq!(map.layouts["SHARED"].fields["LOW"].bits, (0..=0).into());
+
+        // Bit order can only be given for new layouts:
+        let yaml = "
+        name: DummyChip
+        defaults:
+            layout_bitwidth: 8
+        layouts:
+            SHARED:
+                layout:
+                    LOW:
+                        bits: 0
+        registers:
+            A: !Register
+                adr: 0x00
+                bit_order: Msb0
+                layout: !SharedLayout SHARED
+        ";
+        RegisterMap::from_yaml_str(yaml).unwrap_err();
+    }
+
+    #[test]
+    fn test_catch_malformed_split() {
+        convert_bits(&listing::Bits::Split(vec![]), 16, BitOrder::Lsb0, "").unwrap_err();
+        convert_bits(
+            &listing::Bits::Split(vec![listing::Bits::Split(vec![listing::Bits::Bit(0)])]),
+            16,
+            BitOrder::Lsb0,
+            "",
+        )
+        .unwrap_err();
     }
 
     #[test]
     fn test_catch_malformed_range() {
-        convert_bits(&listing::Bits::Range("3- 4".into()), "").unwrap_err();
-        convert_bits(&listing::Bits::Range("4".into()), "").unwrap_err();
-        convert_bits(&listing::Bits::Range("a-b".into()), "").unwrap_err();
-        convert_bits(&listing::Bits::Range("0-999999999999999999999999999999999999999999999999999999".into()), "")
-            .unwrap_err();
+        convert_bits(&listing::Bits::Range("3- 4".into()), 16, BitOrder::Lsb0, "").unwrap_err();
+        convert_bits(&listing::Bits::Range("4".into()), 16, BitOrder::Lsb0, "").unwrap_err();
+        convert_bits(&listing::Bits::Range("a-b".into()), 16, BitOrder::Lsb0, "").unwrap_err();
+        convert_bits(
+            &listing::Bits::Range("0-999999999999999999999999999999999999999999999999999999".into()),
+            16,
+            BitOrder::Lsb0,
+            "",
+        )
+        .unwrap_err();
     }
 
     #[test]
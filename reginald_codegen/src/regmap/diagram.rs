@@ -0,0 +1,295 @@
+use std::fmt::Write;
+
+use reginald_utils::{RangeStyle, numbers_as_ranges};
+use serde_json::{Value, json};
+
+use super::{BitRange, FieldType, Layout, TypeBitwidth, TypeValue, access_str};
+
+// ==== Diagram Segments =======================================================
+
+/// Maximum number of bits drawn in a single diagram lane.
+const BITS_PER_LANE: TypeBitwidth = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiagramSegmentKind {
+    /// Regular field.
+    Field,
+    /// Field that always holds the given value.
+    Fixed(TypeValue),
+    /// Bits not covered by any field.
+    Reserved,
+}
+
+/// Contiguous range of bits in a bitfield diagram.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagramSegment {
+    pub bits: BitRange,
+    /// Field name, with nested layout fields joined by '.'.
+    pub name: Option<String>,
+    pub access: Option<String>,
+    pub kind: DiagramSegmentKind,
+}
+
+impl Layout {
+    /// Split a layout into diagram segments, ordered from LSB to MSB.
+    ///
+    /// Nested layouts are expanded into their fields.
+    pub fn diagram_segments(&self) -> Vec<DiagramSegment> {
+        let fields = self.flattened_fields();
+
+        let mut segments: Vec<DiagramSegment> = vec![];
+        for field in &fields {
+            let name = field.name.join(".");
+            for (range, value_pos) in field.bits.pieces() {
+                // Pieces of split fields are labeled with the value bits they hold:
+                let name = if field.bits.is_split() {
+                    let value_bits = BitRange(value_pos..=(value_pos + range.width() - 1));
+                    format!("{name}[{}]", value_bits.to_string(RangeStyle::Verilog))
+                } else {
+                    name.clone()
+                };
+                let kind = match &field.field.accepts {
+                    FieldType::Fixed(fixed) => {
+                        DiagramSegmentKind::Fixed((*fixed >> value_pos) & range.unpositioned_mask())
+                    }
+                    _ => DiagramSegmentKind::Field,
+                };
+                segments.push(DiagramSegment {
+                    bits: range,
+                    name: Some(name),
+                    access: field.field.access.as_ref().map(access_str),
+                    kind,
+                });
+            }
+        }
+
+        let reserved_bits: Vec<TypeBitwidth> = (0..self.bitwidth)
+            .filter(|bit| !fields.iter().any(|x| x.bits.contains(bit)))
+            .collect();
+        for range in numbers_as_ranges(reserved_bits) {
+            segments.push(DiagramSegment {
+                bits: range.into(),
+                name: None,
+                access: None,
+                kind: DiagramSegmentKind::Reserved,
+            });
+        }
+
+        segments.sort_by_key(|x| x.bits.lsb_pos());
+        segments
+    }
+}
+
+// ==== WaveDrom ===============================================================
+
+/// WaveDrom colour type used for fixed fields.
+const WAVEDROM_TYPE_FIXED: u32 = 2;
+/// WaveDrom colour type used for reserved bits.
+const WAVEDROM_TYPE_RESERVED: u32 = 1;
+
+impl Layout {
+    /// WaveDrom `reg` description of this layout.
+    ///
+    /// Fixed fields show their value as attribute, and both fixed and reserved
+    /// bits are given their own colour type.
+    pub fn wavedrom_reg(&self) -> Value {
+        let reg: Vec<Value> = self
+            .diagram_segments()
+            .into_iter()
+            .map(|segment| {
+                let bits = segment.bits.width();
+                match (&segment.kind, &segment.name) {
+                    (DiagramSegmentKind::Fixed(fixed), Some(name)) => json!({
+                        "bits": bits,
+                        "name": name,
+                        "attr": format!("0x{fixed:X}"),
+                        "type": WAVEDROM_TYPE_FIXED,
+                    }),
+                    (DiagramSegmentKind::Field, Some(name)) => match &segment.access {
+                        Some(access) => json!({ "bits": bits, "name": name, "attr": access }),
+                        None => json!({ "bits": bits, "name": name }),
+                    },
+                    _ => json!({ "bits": bits, "type": WAVEDROM_TYPE_RESERVED }),
+                }
+            })
+            .collect();
+
+        json!({
+            "reg": reg,
+            "config": {
+                "bits": self.bitwidth,
+                "lanes": self.bitwidth.div_ceil(BITS_PER_LANE).max(1),
+            },
+        })
+    }
+}
+
+// ==== SVG ====================================================================
+
+const SVG_BIT_WIDTH: TypeBitwidth = 36;
+const SVG_LABEL_HEIGHT: TypeBitwidth = 16;
+const SVG_BOX_HEIGHT: TypeBitwidth = 32;
+const SVG_LANE_GAP: TypeBitwidth = 8;
+const SVG_MARGIN: TypeBitwidth = 2;
+
+impl Layout {
+    /// Standalone SVG bitfield diagram of this layout.
+    ///
+    /// Bits are drawn MSB-first, in lanes of at most 16 bits. Fixed fields
+    /// are filled yellow and labelled with their value, reserved bits are
+    /// filled grey.
+    pub fn bitfield_svg(&self) -> String {
+        let bits_per_lane = BITS_PER_LANE.min(self.bitwidth.max(1));
+        let lanes = self.bitwidth.max(1).div_ceil(bits_per_lane);
+        let lane_height = SVG_LABEL_HEIGHT + SVG_BOX_HEIGHT + SVG_LANE_GAP;
+        let width = bits_per_lane * SVG_BIT_WIDTH + 2 * SVG_MARGIN;
+        let height = lanes * lane_height + 2 * SVG_MARGIN;
+
+        let segments = self.diagram_segments();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" text-anchor=\"middle\">"
+        );
+
+        for lane in 0..lanes {
+            // Top lane holds the most significant bits:
+            let lane_lsb = (lanes - lane - 1) * bits_per_lane;
+            let lane_msb = (lane_lsb + bits_per_lane).min(self.bitwidth.max(1)) - 1;
+            let y = SVG_MARGIN + lane * lane_height;
+            let bit_x = |bit: TypeBitwidth| SVG_MARGIN + (lane_lsb + bits_per_lane - 1 - bit) * SVG_BIT_WIDTH;
+
+            // Bit numbers:
+            for bit in lane_lsb..=lane_msb {
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"10\">{}</text>",
+                    bit_x(bit) + SVG_BIT_WIDTH / 2,
+                    y + SVG_LABEL_HEIGHT - 4,
+                    self.bit_number(bit)
+                );
+            }
+
+            // Segments, clipped to this lane:
+            for segment in &segments {
+                let lsb = segment.bits.lsb_pos().max(lane_lsb);
+                let msb = segment.bits.msb_pos().min(lane_msb);
+                if lsb > msb {
+                    continue;
+                }
+                let x = bit_x(msb);
+                let w = (msb - lsb + 1) * SVG_BIT_WIDTH;
+                let box_y = y + SVG_LABEL_HEIGHT;
+
+                let (fill, label) = match &segment.kind {
+                    DiagramSegmentKind::Field => ("#ffffff", segment.name.clone().unwrap_or_default()),
+                    DiagramSegmentKind::Fixed(fixed) => {
+                        ("#fff2cc", format!("{} = 0x{fixed:X}", segment.name.clone().unwrap_or_default()))
+                    }
+                    DiagramSegmentKind::Reserved => ("#e4e4e4", String::new()),
+                };
+
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{x}\" y=\"{box_y}\" width=\"{w}\" height=\"{SVG_BOX_HEIGHT}\" fill=\"{fill}\" stroke=\"#000000\"/>"
+                );
+                if !label.is_empty() {
+                    let _ = writeln!(
+                        out,
+                        "<text x=\"{}\" y=\"{}\" font-size=\"12\">{}</text>",
+                        x + w / 2,
+                        box_y + SVG_BOX_HEIGHT / 2 + 4,
+                        svg_escape(&label)
+                    );
+                }
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn svg_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regmap::RegisterMap;
+    use pretty_assertions::assert_eq;
+
+    const MAP: &str = r#"
+        name: test
+        registers:
+          REG: !Register
+            adr: 0x00
+            bitwidth: 8
+            layout: !Layout
+              A:
+                bits: "1-0"
+                access: [R]
+              B:
+                bits: "5-4"
+                accepts: !Fixed 0x2
+              C:
+                bits: 7
+                accepts: !Layout
+                  D:
+                    bits: 0
+    "#;
+
+    #[test]
+    fn test_diagram_segments() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let segments = map.registers["REG"].layout.diagram_segments();
+
+        let summary: Vec<_> = segments
+            .iter()
+            .map(|x| (x.bits.lsb_pos(), x.bits.msb_pos(), x.name.as_deref(), x.kind.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 1, Some("A"), DiagramSegmentKind::Field),
+                (2, 3, None, DiagramSegmentKind::Reserved),
+                (4, 5, Some("B"), DiagramSegmentKind::Fixed(0x2)),
+                (6, 6, None, DiagramSegmentKind::Reserved),
+                (7, 7, Some("C.D"), DiagramSegmentKind::Field),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wavedrom_reg() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let reg = map.registers["REG"].layout.wavedrom_reg();
+
+        let should = json!({
+            "reg": [
+                { "bits": 2, "name": "A", "attr": "R" },
+                { "bits": 2, "type": 1 },
+                { "bits": 2, "name": "B", "attr": "0x2", "type": 2 },
+                { "bits": 1, "type": 1 },
+                { "bits": 1, "name": "C.D" },
+            ],
+            "config": { "bits": 8, "lanes": 1 },
+        });
+        assert_eq!(reg, should);
+    }
+
+    #[test]
+    fn test_bitfield_svg() {
+        let map = RegisterMap::from_yaml_str(MAP).unwrap();
+        let svg = map.registers["REG"].layout.bitfield_svg();
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 5);
+        assert_eq!(svg.matches("fill=\"#e4e4e4\"").count(), 2);
+        assert!(svg.contains(">B = 0x2</text>"));
+    }
+}
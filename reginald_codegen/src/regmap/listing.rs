@@ -12,6 +12,8 @@ use std::{collections::BTreeMap, io};
 pub enum Bits {
     Bit(TypeBitwidth),
     Range(String),
+    /// Non-contiguous field. The first entry holds the most significant bits of the value.
+    Split(Vec<Bits>),
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -22,11 +24,150 @@ pub enum AccessMode {
 
 pub type Access = Vec<AccessMode>;
 
+/// Bit numbering convention of `bits` entries.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BitOrder {
+    /// Bit 0 is the least significant bit.
+    Lsb0,
+    /// Bit 0 is the most significant bit.
+    Msb0,
+}
+
+/// (De)serialization of register/field values, which may be up to 128 bits wide.
+///
+/// Not all listing formats support integers that wide, so values may also be
+/// given as a string such as `"0x1_0000_0000_0000_0000"`. Values that do not fit
+/// into 64 bits are always serialized as hex strings.
+pub(crate) mod value {
+    use std::{collections::BTreeMap, fmt};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{self, Visitor},
+    };
+
+    use crate::regmap::TypeValue;
+
+    pub struct Value(pub TypeValue);
+
+    impl Serialize for Value {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match u64::try_from(self.0) {
+                Ok(val) => serializer.serialize_u64(val),
+                Err(_) => serializer.serialize_str(&format!("0x{:X}", self.0)),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Value {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(ValueVisitor)
+        }
+    }
+
+    pub struct ValueVisitor;
+
+    impl Visitor<'_> for ValueVisitor {
+        type Value = Value;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an unsigned integer of at most 128 bits, or a string holding one")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+            Ok(Value(v.into()))
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<Value, E> {
+            Ok(Value(v))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+            TypeValue::try_from(v)
+                .map(Value)
+                .map_err(|_| E::custom(format!("negative value {v}")))
+        }
+
+        fn visit_i128<E: de::Error>(self, v: i128) -> Result<Value, E> {
+            TypeValue::try_from(v)
+                .map(Value)
+                .map_err(|_| E::custom(format!("negative value {v}")))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+            let s = v.trim().replace('_', "");
+            let val = if let Some(hex) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+                TypeValue::from_str_radix(hex, 16)
+            } else if let Some(bin) = s.strip_prefix("0b").or(s.strip_prefix("0B")) {
+                TypeValue::from_str_radix(bin, 2)
+            } else {
+                s.parse()
+            };
+            val.map(Value).map_err(|_| E::custom(format!("invalid value '{v}'")))
+        }
+    }
+
+    pub fn serialize<S: Serializer>(val: &TypeValue, serializer: S) -> Result<S::Ok, S::Error> {
+        Value(*val).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TypeValue, D::Error> {
+        Ok(Value::deserialize(deserializer)?.0)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(val: &Option<TypeValue>, serializer: S) -> Result<S::Ok, S::Error> {
+            val.map(Value).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<TypeValue>, D::Error> {
+            Ok(Option::<Value>::deserialize(deserializer)?.map(|x| x.0))
+        }
+    }
+
+    pub mod option_vec {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(val: &Option<Vec<TypeValue>>, serializer: S) -> Result<S::Ok, S::Error> {
+            val.as_ref()
+                .map(|x| x.iter().map(|x| Value(*x)).collect::<Vec<_>>())
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<TypeValue>>, D::Error> {
+            Ok(Option::<Vec<Value>>::deserialize(deserializer)?.map(|x| x.into_iter().map(|x| x.0).collect()))
+        }
+    }
+
+    pub mod map {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(val: &BTreeMap<String, TypeValue>, serializer: S) -> Result<S::Ok, S::Error> {
+            val.iter()
+                .map(|(k, v)| (k, Value(*v)))
+                .collect::<BTreeMap<_, _>>()
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<BTreeMap<String, TypeValue>, D::Error> {
+            Ok(BTreeMap::<String, Value>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(k, v)| (k, v.0))
+                .collect())
+        }
+    }
+}
+
 // ==== Enums ==================================================================
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct EnumEntry {
+    #[serde(with = "value")]
     pub val: TypeValue,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc: Option<String>,
@@ -52,8 +193,9 @@ pub struct SharedEnum {
 pub enum FieldType {
     #[default]
     UInt,
+    SInt,
     Bool,
-    Fixed(TypeValue),
+    Fixed(#[serde(with = "value")] TypeValue),
     Enum(EnumEntries),
     SharedEnum(String),
     Layout(LayoutFields),
@@ -70,6 +212,22 @@ pub struct LayoutField {
     pub accepts: FieldType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access: Option<Access>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<Vec<i64>>,
+    #[serde(default, with = "value::option", skip_serializing_if = "Option::is_none")]
+    pub min: Option<TypeValue>,
+    #[serde(default, with = "value::option", skip_serializing_if = "Option::is_none")]
+    pub max: Option<TypeValue>,
+    #[serde(default, with = "value::option", skip_serializing_if = "Option::is_none")]
+    pub step: Option<TypeValue>,
+    #[serde(default, with = "value::option_vec", skip_serializing_if = "Option::is_none")]
+    pub reserved: Option<Vec<TypeValue>>,
 }
 
 pub type LayoutFields = BTreeMap<String, LayoutField>;
@@ -95,6 +253,8 @@ pub struct SharedLayout {
     pub doc: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bitwidth: Option<TypeBitwidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_order: Option<BitOrder>,
     pub layout: LayoutFields,
 }
 
@@ -110,6 +270,8 @@ pub struct Register {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bitwidth: Option<TypeBitwidth>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_order: Option<BitOrder>,
+    #[serde(default, with = "value::option", skip_serializing_if = "Option::is_none")]
     pub reset_val: Option<TypeValue>,
 
     #[serde(default)]
@@ -126,7 +288,8 @@ pub struct Instance {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc: Option<String>,
 
-    #[serde(default = "BTreeMap::new")]
+    #[serde(default = "BTreeMap::new", with = "value::map")]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub reset_vals: BTreeMap<String, TypeValue>,
 }
 
@@ -140,6 +303,8 @@ pub struct RegisterBlockMember {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bitwidth: Option<TypeBitwidth>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_order: Option<BitOrder>,
+    #[serde(default, with = "value::option", skip_serializing_if = "Option::is_none")]
     pub reset_val: Option<TypeValue>,
 
     pub layout: RegisterLayout,
@@ -155,6 +320,19 @@ pub struct RegisterBlock {
     pub registers: BTreeMap<String, RegisterBlockMember>,
 }
 
+// ==== Virtual Fields =========================================================
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct VirtualField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    #[serde(default)]
+    pub accepts: FieldType,
+    /// Register fields holding the value as `REGISTER.FIELD`, most significant part first.
+    pub parts: Vec<String>,
+}
+
 // ==== Register Map ===========================================================
 
 // TODO: Implement custom deser logic to allow untagged representation?
@@ -172,6 +350,8 @@ pub struct Defaults {
     pub layout_bitwidth: Option<TypeBitwidth>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field_access_mode: Option<Access>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_order: Option<BitOrder>,
 }
 
 impl Defaults {
@@ -180,6 +360,17 @@ impl Defaults {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AddressSpace {
+    /// Number of bits held by each address. If not given, every register occupies a single address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_bitwidth: Option<TypeBitwidth>,
+    /// Require registers to be aligned to their width.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub aligned: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
 #[serde(deny_unknown_fields)]
 pub struct RegisterMap {
@@ -195,6 +386,9 @@ pub struct RegisterMap {
     #[serde(skip_serializing_if = "Defaults::is_default")]
     pub defaults: Defaults,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_space: Option<AddressSpace>,
+
     #[serde(default = "BTreeMap::new")]
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub enums: BTreeMap<String, SharedEnum>,
@@ -206,6 +400,10 @@ pub struct RegisterMap {
     #[serde(default = "BTreeMap::new")]
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub registers: BTreeMap<String, RegisterListing>,
+
+    #[serde(default = "BTreeMap::new")]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub virtual_fields: BTreeMap<String, VirtualField>,
 }
 
 impl RegisterMap {
@@ -261,6 +459,10 @@ mod tests {
         let yaml = "2";
         let v: Bits = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(v, Bits::Bit(2));
+
+        let yaml = "[7, \"3-0\"]";
+        let v: Bits = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(v, Bits::Split(vec![Bits::Bit(7), Bits::Range("3-0".into())]));
     }
 
     #[test]
@@ -272,6 +474,10 @@ mod tests {
         let hjson = "2";
         let v: Bits = deser_hjson::from_str(hjson).unwrap();
         assert_eq!(v, Bits::Bit(2));
+
+        let hjson = "[7, \"3-0\"]";
+        let v: Bits = deser_hjson::from_str(hjson).unwrap();
+        assert_eq!(v, Bits::Split(vec![Bits::Bit(7), Bits::Range("3-0".into())]));
     }
 
     #[test]
@@ -391,6 +597,14 @@ mod tests {
                             doc: None,
                             accepts: FieldType::default(),
                             access: None,
+                            unit: None,
+                            scale: None,
+                            offset: None,
+                            table: None,
+                            min: None,
+                            max: None,
+                            step: None,
+                            reserved: None,
                         },
                     ),
                     (
@@ -400,6 +614,14 @@ mod tests {
                             doc: None,
                             accepts: FieldType::default(),
                             access: None,
+                            unit: None,
+                            scale: None,
+                            offset: None,
+                            table: None,
+                            min: None,
+                            max: None,
+                            step: None,
+                            reserved: None,
                         },
                     ),
                 ])),
@@ -460,6 +682,14 @@ mod tests {
             ("B".into(), EnumEntry { val: 0x0, doc: None }),
         ])),
         access: None,
+        unit: None,
+        scale: None,
+        offset: None,
+        table: None,
+        min: None,
+        max: None,
+        step: None,
+        reserved: None,
     });
 
     #[test]
@@ -500,6 +730,14 @@ mod tests {
         doc: None,
         accepts: FieldType::SharedEnum("TestEnum".into()),
         access: None,
+        unit: None,
+        scale: None,
+        offset: None,
+        table: None,
+        min: None,
+        max: None,
+        step: None,
+        reserved: None,
     });
 
     #[test]
@@ -524,6 +762,82 @@ mod tests {
         assert_eq!(field_is, *FIELD_SHARED_ENUM_EXPECT);
     }
 
+    static WIDE_REGISTER_EXPECT: LazyLock<Register> = LazyLock::new(|| Register {
+        adr: 0x10,
+        bitwidth: Some(96),
+        reset_val: Some(0x1234_5678_9ABC_DEF0_1234_5678),
+        ..Default::default()
+    });
+
+    #[test]
+    fn deser_yaml_wide_register() {
+        let yaml = "
+        adr: 0x10
+        bitwidth: 96
+        reset_val: 0x123456789ABCDEF012345678
+        ";
+        let is: Register = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(is, *WIDE_REGISTER_EXPECT);
+
+        let yaml = "
+        adr: 0x10
+        bitwidth: 96
+        reset_val: '0x1234_5678_9ABC_DEF0_1234_5678'
+        ";
+        let is: Register = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(is, *WIDE_REGISTER_EXPECT);
+    }
+
+    #[test]
+    fn deser_hjson_wide_register() {
+        let hjson = "
+        adr: 16
+        bitwidth: 96
+        reset_val: '0x123456789ABCDEF012345678'
+        ";
+        let is: Register = deser_hjson::from_str(hjson).unwrap();
+        assert_eq!(is, *WIDE_REGISTER_EXPECT);
+    }
+
+    #[test]
+    fn ser_json_wide_register() {
+        let json = serde_json::to_string(&*WIDE_REGISTER_EXPECT).unwrap();
+        assert!(json.contains("\"reset_val\":\"0x123456789ABCDEF012345678\""));
+        let is: Register = serde_json::from_str(&json).unwrap();
+        assert_eq!(is, *WIDE_REGISTER_EXPECT);
+    }
+
+    #[test]
+    fn deser_catch_bad_value() {
+        serde_yaml::from_str::<EnumEntry>("val: -1").unwrap_err();
+        serde_yaml::from_str::<EnumEntry>("val: '0xG'").unwrap_err();
+        serde_yaml::from_str::<EnumEntry>("val: '0x1_0000_0000_0000_0000_0000_0000_0000_0000'").unwrap_err();
+    }
+
+    #[test]
+    fn deser_yaml_address_space() {
+        let yaml = "
+        name: DummyChip
+        address_space:
+            unit_bitwidth: 8
+            aligned: true
+        ";
+        let is: RegisterMap = serde_yaml::from_str(yaml).unwrap();
+        let expect = RegisterMap {
+            name: "DummyChip".to_string(),
+            address_space: Some(AddressSpace {
+                unit_bitwidth: Some(8),
+                aligned: true,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(is, expect);
+
+        // Address space is optional:
+        let json = serde_json::to_string(&RegisterMap::default()).unwrap();
+        assert!(!json.contains("address_space"));
+    }
+
     fn parse_yaml_example(file: &str) -> RegisterMap {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("../examples/maps/");
@@ -1,4 +1,5 @@
 mod convert;
+pub mod diagram;
 pub mod listing;
 mod validate;
 
@@ -12,16 +13,16 @@ use std::{
 
 use reginald_utils::{RangeStyle, numbers_as_ranges, range_to_str};
 
-use crate::bits::{bitmask_from_range, bitmask_from_width, bitwidth_to_width_bytes, unpositioned_mask};
+use crate::bits::{bitmask_from_range, bitmask_from_width, bitwidth_to_width_bytes, sign_extend, unpositioned_mask};
 use crate::error::Error;
 
 use self::convert::convert_map;
 
 // ==== Basic Types ============================================================
 
-pub type TypeValue = u64;
+pub type TypeValue = u128;
 pub type TypeBitwidth = u32;
-pub const MAX_BITWIDTH: TypeBitwidth = 64;
+pub const MAX_BITWIDTH: TypeBitwidth = 128;
 pub type TypeAdr = u64;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -41,6 +42,13 @@ pub struct Docs {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BitRange(pub RangeInclusive<TypeBitwidth>);
 
+/// Bits occupied by a field, which may be split across several non-contiguous ranges.
+///
+/// Ranges are listed in value order: The first range holds the most significant
+/// bits of the field's value, and the last range holds the least significant bits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldBits(pub Vec<BitRange>);
+
 // ==== Enums ==================================================================
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -65,6 +73,7 @@ pub struct Enum {
 pub enum FieldType {
     #[default]
     UInt,
+    SInt,
     Bool,
     Fixed(TypeValue),
     Enum(Rc<Enum>),
@@ -74,10 +83,51 @@ pub enum FieldType {
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct LayoutField {
     pub name: String,
-    pub bits: BitRange,
+    pub bits: FieldBits,
     pub docs: Docs,
     pub accepts: FieldType,
     pub access: Option<Access>,
+    pub scaling: Option<Scaling>,
+    pub constraints: Option<FieldConstraints>,
+}
+
+/// Restrictions on the values an unsigned field may hold.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FieldConstraints {
+    pub min: Option<TypeValue>,
+    pub max: Option<TypeValue>,
+    /// Valid values are spaced `step` apart, starting at `min` (or zero).
+    pub step: Option<TypeValue>,
+    /// Values that are invalid, even if they satisfy all other constraints.
+    pub reserved: Vec<TypeValue>,
+}
+
+/// Conversion between the value of a numeric field and a physical quantity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scaling {
+    /// Unit of the physical value, such as `mV`.
+    pub unit: String,
+    pub conversion: ScalingConversion,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScalingConversion {
+    /// `physical = offset + scale * value`
+    Linear { scale: i64, offset: i64 },
+    /// `physical = table[value]`
+    Table(Vec<i64>),
+}
+
+/// Bit numbering convention used when listing and displaying bit positions.
+///
+/// Internally, bit positions are always numbered LSB-0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// Bit 0 is the least significant bit.
+    #[default]
+    Lsb0,
+    /// Bit 0 is the most significant bit.
+    Msb0,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -86,6 +136,7 @@ pub struct Layout {
     pub docs: Docs,
     pub is_local: bool,
     pub bitwidth: TypeBitwidth,
+    pub bit_order: BitOrder,
 
     pub fields: BTreeMap<String, LayoutField>,
 }
@@ -143,12 +194,47 @@ pub struct RegisterBlock {
     pub members: BTreeMap<String, Rc<RegisterBlockMember>>,
 }
 
+// ==== Virtual Fields =========================================================
+
+/// Register field holding part of a virtual field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VirtualFieldPart {
+    pub register: Rc<Register>,
+    /// Name of the field in the register's layout.
+    pub field: String,
+}
+
+/// Value composed of fields from several registers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VirtualField {
+    pub name: String,
+    pub docs: Docs,
+    pub signed: bool,
+    /// Parts of the value, most significant part first.
+    pub parts: Vec<VirtualFieldPart>,
+}
+
+// ==== Address Space ==========================================================
+
+/// How register addresses relate to the storage occupied by registers.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct AddressSpace {
+    /// Number of bits held by each address, such as 8 for a byte-addressed map.
+    ///
+    /// If not given, every register occupies a single address regardless of its width.
+    pub unit_bitwidth: Option<TypeBitwidth>,
+    /// Registers must start at a multiple of their width in address units, rounded
+    /// up to a power of two.
+    pub aligned: bool,
+}
+
 // ==== Register Map ===========================================================
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Defaults {
     pub layout_bitwidth: Option<TypeBitwidth>,
     pub field_access_mode: Option<Access>,
+    pub bit_order: BitOrder,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -159,6 +245,8 @@ pub struct RegisterMap {
     pub notice: Option<String>,
     pub author: Option<String>,
     pub defaults: Defaults,
+    /// Register addresses are only validated if an address space is given.
+    pub address_space: Option<AddressSpace>,
 
     // All enums:
     pub enums: BTreeMap<String, Rc<Enum>>,
@@ -171,6 +259,9 @@ pub struct RegisterMap {
 
     // Register blocks
     pub register_blocks: BTreeMap<String, RegisterBlock>,
+
+    // Values composed of fields from several registers:
+    pub virtual_fields: BTreeMap<String, VirtualField>,
 }
 
 // ==== Impls ==================================================================
@@ -231,6 +322,100 @@ impl BitRange {
     }
 }
 
+impl Default for FieldBits {
+    fn default() -> Self {
+        Self(vec![BitRange::default()])
+    }
+}
+
+impl From<BitRange> for FieldBits {
+    fn from(range: BitRange) -> Self {
+        Self(vec![range])
+    }
+}
+
+impl From<RangeInclusive<TypeBitwidth>> for FieldBits {
+    fn from(range: RangeInclusive<TypeBitwidth>) -> Self {
+        Self(vec![range.into()])
+    }
+}
+
+impl From<&RangeInclusive<TypeBitwidth>> for FieldBits {
+    fn from(range: &RangeInclusive<TypeBitwidth>) -> Self {
+        Self(vec![range.into()])
+    }
+}
+
+impl FieldBits {
+    pub fn ranges(&self) -> &[BitRange] {
+        &self.0
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.0.len() > 1
+    }
+
+    /// Each range of the field, together with the position of its least significant
+    /// bit within the field's value.
+    pub fn pieces(&self) -> Vec<(BitRange, TypeBitwidth)> {
+        let mut result = vec![];
+        let mut value_pos = 0;
+        for range in self.0.iter().rev() {
+            result.push((range.clone(), value_pos));
+            value_pos += range.width();
+        }
+        result.reverse();
+        result
+    }
+
+    pub fn to_string(&self, style: RangeStyle) -> String {
+        let ranges: Vec<String> = self.0.iter().map(|x| x.to_string(style)).collect();
+        ranges.join(", ")
+    }
+
+    pub fn width(&self) -> TypeBitwidth {
+        self.0.iter().map(BitRange::width).sum()
+    }
+
+    pub fn shift_left(&self, amnt: TypeBitwidth) -> FieldBits {
+        FieldBits(self.0.iter().map(|x| x.shift_left(amnt)).collect())
+    }
+
+    pub fn mask(&self) -> TypeValue {
+        self.0.iter().fold(0, |mask, x| mask | x.mask())
+    }
+
+    pub fn unpositioned_mask(&self) -> TypeValue {
+        bitmask_from_width(self.width())
+    }
+
+    pub fn lsb_pos(&self) -> TypeBitwidth {
+        self.0.iter().map(BitRange::lsb_pos).min().unwrap_or(0)
+    }
+
+    pub fn msb_pos(&self) -> TypeBitwidth {
+        self.0.iter().map(BitRange::msb_pos).max().unwrap_or(0)
+    }
+
+    pub fn contains(&self, bitpos: &TypeBitwidth) -> bool {
+        self.0.iter().any(|x| x.contains(bitpos))
+    }
+
+    /// Extract the field's value from a register value.
+    pub fn extract(&self, val: TypeValue) -> TypeValue {
+        self.pieces().iter().fold(0, |result, (range, value_pos)| {
+            result | (((val >> range.lsb_pos()) & range.unpositioned_mask()) << value_pos)
+        })
+    }
+
+    /// Position a field value inside a register value.
+    pub fn insert(&self, val: TypeValue) -> TypeValue {
+        self.pieces().iter().fold(0, |result, (range, value_pos)| {
+            result | (((val >> value_pos) & range.unpositioned_mask()) << range.lsb_pos())
+        })
+    }
+}
+
 impl Docs {
     pub fn is_empty(&self) -> bool {
         self.brief.is_none() && self.doc.is_none()
@@ -278,15 +463,18 @@ impl Enum {
     /// Check if enum can represent every possible value that fits into a field of it's size:
     pub fn can_always_unpack(&self) -> bool {
         // All unique enum values:
-        let enum_vals: HashSet<u64> = self.entries.values().map(|x| x.value).collect();
+        let enum_vals: HashSet<TypeValue> = self.entries.values().map(|x| x.value).collect();
 
-        // Number of values the mask can represent:
-        let mask_vals_count = 2_u128.pow(self.bitwidth);
+        // Number of values the mask can represent. An enum can never have as
+        // many entries as a 128-bit field can hold:
+        let Some(mask_vals_count) = 2_u128.checked_pow(self.bitwidth) else {
+            return false;
+        };
 
         let enum_vals_count: u128 = enum_vals
             .len()
             .try_into()
-            .expect("HashSet holding u64 cannot have more than u64::MAX entries");
+            .expect("HashSet cannot have more than u128::MAX entries");
 
         mask_vals_count == enum_vals_count
     }
@@ -309,8 +497,104 @@ impl Enum {
     }
 }
 
+impl FieldConstraints {
+    /// Check if the given field value satisfies all constraints.
+    pub fn allows(&self, val: TypeValue) -> bool {
+        let min = self.min.unwrap_or(0);
+        if val < min || self.max.is_some_and(|max| val > max) {
+            return false;
+        }
+        if self.step.is_some_and(|step| !(val - min).is_multiple_of(step)) {
+            return false;
+        }
+        !self.reserved.contains(&val)
+    }
+}
+
+impl Scaling {
+    /// Physical value of the given field value, if the conversion defines one.
+    pub fn to_physical(&self, val: i64) -> Option<i64> {
+        match &self.conversion {
+            ScalingConversion::Linear { scale, offset } => scale.checked_mul(val)?.checked_add(*offset),
+            ScalingConversion::Table(table) => table.get(usize::try_from(val).ok()?).copied(),
+        }
+    }
+
+    /// Check if every possible value of a field of the given width has a physical value.
+    pub fn can_always_convert(&self, bitwidth: TypeBitwidth) -> bool {
+        match &self.conversion {
+            ScalingConversion::Linear { .. } => true,
+            ScalingConversion::Table(table) => 2_u128.checked_pow(bitwidth) == Some(table.len() as u128),
+        }
+    }
+
+    /// Spelled-out, lowercase name of the unit, for use in identifiers.
+    ///
+    /// ```
+    /// # use reginald_codegen::regmap::{Scaling, ScalingConversion};
+    /// let scaling = Scaling {
+    ///     unit: "mV".to_string(),
+    ///     conversion: ScalingConversion::Linear { scale: 25, offset: 800 },
+    /// };
+    /// assert_eq!(scaling.unit_name(), "millivolts");
+    /// ```
+    pub fn unit_name(&self) -> String {
+        const PREFIXES: [(&str, &str); 8] = [
+            ("p", "pico"),
+            ("n", "nano"),
+            ("u", "micro"),
+            ("µ", "micro"),
+            ("m", "milli"),
+            ("k", "kilo"),
+            ("M", "mega"),
+            ("G", "giga"),
+        ];
+        const UNITS: [(&str, &str); 14] = [
+            ("V", "volts"),
+            ("A", "amps"),
+            ("W", "watts"),
+            ("s", "seconds"),
+            ("Hz", "hertz"),
+            ("Ohm", "ohms"),
+            ("ohm", "ohms"),
+            ("Ω", "ohms"),
+            ("F", "farads"),
+            ("H", "henries"),
+            ("°C", "degrees_celsius"),
+            ("degC", "degrees_celsius"),
+            ("dB", "decibels"),
+            ("%", "percent"),
+        ];
+
+        let unit = self.unit.trim();
+        let find_unit = |unit: &str| UNITS.iter().find(|(symbol, _)| *symbol == unit).map(|(_, name)| *name);
+
+        if let Some(name) = find_unit(unit) {
+            return name.to_string();
+        }
+
+        for (prefix, prefix_name) in PREFIXES {
+            if let Some(name) = unit.strip_prefix(prefix).and_then(find_unit) {
+                return format!("{prefix_name}{name}");
+            }
+        }
+
+        // Unknown unit: Use as-is.
+        unit.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
 pub enum DecodedField {
     UInt(TypeValue),
+    SInt(i128),
     Fixed { val: TypeValue, is_correct: bool },
     Bool(bool),
     EnumEntry(String),
@@ -319,7 +603,8 @@ pub enum DecodedField {
 impl LayoutField {
     pub fn can_always_unpack(&self) -> bool {
         match &self.accepts {
-            FieldType::UInt => true,
+            FieldType::UInt => self.constraints.is_none(),
+            FieldType::SInt => true,
             FieldType::Bool => true,
             FieldType::Enum(e) => e.can_always_unpack(),
             FieldType::Fixed(_) => true,
@@ -328,13 +613,19 @@ impl LayoutField {
     }
 
     pub fn decode_unpositioned_value(&self, val: TypeValue) -> Result<DecodedField, Error> {
-        self.decode_value(val >> self.bits.lsb_pos())
+        self.decode_value(self.bits.extract(val))
     }
 
     pub fn decode_value(&self, val: TypeValue) -> Result<DecodedField, Error> {
         let val = val & self.bits.unpositioned_mask();
         match &self.accepts {
-            FieldType::UInt => Ok(DecodedField::UInt(val)),
+            FieldType::UInt => match &self.constraints {
+                Some(constraints) if !constraints.allows(val) => {
+                    Err(Error::GeneratorError(format!("Field '{}' does not allow value 0x{:X}.", self.name, val)))
+                }
+                _ => Ok(DecodedField::UInt(val)),
+            },
+            FieldType::SInt => Ok(DecodedField::SInt(sign_extend(val, self.bits.width()))),
             FieldType::Bool => Ok(DecodedField::Bool(val != 0)),
             FieldType::Enum(e) => Ok(DecodedField::EnumEntry(e.decode(val)?)),
             FieldType::Fixed(expected) => Ok(DecodedField::Fixed {
@@ -348,9 +639,65 @@ impl LayoutField {
     pub fn contains_content(&self) -> bool {
         !matches!(self.accepts, FieldType::Fixed(_))
     }
+
+    /// Physical value of the given (unpositioned) field value, if the field is scaled.
+    pub fn decode_physical_value(&self, val: TypeValue) -> Option<i64> {
+        let val = val & self.bits.unpositioned_mask();
+        let val = match &self.accepts {
+            FieldType::UInt => i64::try_from(val).ok()?,
+            FieldType::SInt => i64::try_from(sign_extend(val, self.bits.width())).ok()?,
+            _ => return None,
+        };
+        self.scaling.as_ref()?.to_physical(val)
+    }
+
+    /// Smallest and largest value a numeric field can hold.
+    ///
+    /// Only available for fields of at most 64 bits.
+    pub fn numeric_range(&self) -> Option<RangeInclusive<i128>> {
+        let width = self.bits.width();
+        if width > 64 {
+            return None;
+        }
+        match &self.accepts {
+            FieldType::UInt => Some(0..=(1_i128 << width) - 1),
+            FieldType::SInt => Some(-(1_i128 << (width - 1))..=(1_i128 << (width - 1)) - 1),
+            _ => None,
+        }
+    }
 }
 
 impl Layout {
+    /// Number of the given (LSB-0) bit position in this layout's bit order.
+    pub fn bit_number(&self, bitpos: TypeBitwidth) -> TypeBitwidth {
+        match self.bit_order {
+            BitOrder::Lsb0 => bitpos,
+            BitOrder::Msb0 => self.bitwidth - 1 - bitpos,
+        }
+    }
+
+    /// Format a bit range of this layout, numbered in the layout's bit order.
+    pub fn range_to_string(&self, range: &BitRange, style: RangeStyle) -> String {
+        match self.bit_order {
+            BitOrder::Lsb0 => range.to_string(style),
+            BitOrder::Msb0 => {
+                // MSB-0 numbers count up from the most significant bit:
+                let msb = self.bit_number(range.msb_pos());
+                let lsb = self.bit_number(range.lsb_pos());
+                match style {
+                    RangeStyle::Verilog if msb != lsb => format!("{msb}:{lsb}"),
+                    _ => range_to_str(&(msb..=lsb), style),
+                }
+            }
+        }
+    }
+
+    /// Format the bits of a field of this layout, numbered in the layout's bit order.
+    pub fn bits_to_string(&self, bits: &FieldBits, style: RangeStyle) -> String {
+        let ranges: Vec<String> = bits.ranges().iter().map(|x| self.range_to_string(x, style)).collect();
+        ranges.join(", ")
+    }
+
     pub fn field_at_bitpos(&self, bitpos: TypeBitwidth) -> Option<&LayoutField> {
         self.fields.values().find(|&field| field.bits.contains(&bitpos))
     }
@@ -395,7 +742,7 @@ impl Layout {
         let mut val: TypeValue = 0;
         for field in self.fields.values() {
             if let FieldType::Fixed(fixed_val) = field.accepts {
-                val |= fixed_val << field.bits.lsb_pos();
+                val |= field.bits.insert(fixed_val);
             }
         }
         val
@@ -507,13 +854,14 @@ impl Layout {
             let name = markdown_escape(&field.name.join("."));
 
             let indent = field.name.len() - 1;
-            let indent = String::from_iter(std::iter::repeat("  ").take(indent));
+            let indent = String::from_iter(std::iter::repeat_n("  ", indent));
 
-            let bits_str = field.bits.to_string(RangeStyle::Verilog);
+            let bits_str = self.bits_to_string(&field.bits, RangeStyle::Verilog);
             let bits = markdown_escape(&format!("[{}]", bits_str));
 
             let type_string = match &field.field.accepts {
                 FieldType::UInt => String::from("(uint)"),
+                FieldType::SInt => String::from("(sint)"),
                 FieldType::Bool => String::from("(bool)"),
                 FieldType::Fixed(fix) => format!("(fixed: 0x{fix:x})"),
                 FieldType::Enum(e) => format!("(enum {})", markdown_escape(&e.name)),
@@ -537,6 +885,93 @@ impl Layout {
     }
 }
 
+impl AddressSpace {
+    /// Number of addresses occupied by a register of the given bitwidth.
+    pub fn width_in_units(&self, bitwidth: TypeBitwidth) -> TypeAdr {
+        match self.unit_bitwidth {
+            Some(unit_bitwidth) => bitwidth.div_ceil(unit_bitwidth).max(1).into(),
+            None => 1,
+        }
+    }
+
+    /// Required alignment, in address units, of a register of the given bitwidth.
+    pub fn alignment(&self, bitwidth: TypeBitwidth) -> TypeAdr {
+        if self.aligned {
+            self.width_in_units(bitwidth).next_power_of_two()
+        } else {
+            1
+        }
+    }
+
+    /// Addresses occupied by a register of the given bitwidth, or `None` if it does not
+    /// fit into the address space.
+    pub fn adr_range(&self, adr: TypeAdr, bitwidth: TypeBitwidth) -> Option<RangeInclusive<TypeAdr>> {
+        let last = adr.checked_add(self.width_in_units(bitwidth) - 1)?;
+        Some(adr..=last)
+    }
+}
+
+impl VirtualFieldPart {
+    pub fn field(&self) -> &LayoutField {
+        &self.register.layout.fields[&self.field]
+    }
+
+    /// Part name as `REGISTER.FIELD`.
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.register.name, self.field)
+    }
+}
+
+impl VirtualField {
+    pub fn width(&self) -> TypeBitwidth {
+        self.parts.iter().map(|x| x.field().bits.width()).sum()
+    }
+
+    /// Each part, together with the position of its least significant bit within
+    /// the virtual field's value.
+    pub fn pieces(&self) -> Vec<(&VirtualFieldPart, TypeBitwidth)> {
+        let mut result = vec![];
+        let mut value_pos = 0;
+        for part in self.parts.iter().rev() {
+            result.push((part, value_pos));
+            value_pos += part.field().bits.width();
+        }
+        result.reverse();
+        result
+    }
+
+    /// Registers holding parts of the value, in order of their first part.
+    pub fn registers(&self) -> Vec<&Rc<Register>> {
+        let mut result: Vec<&Rc<Register>> = vec![];
+        for part in &self.parts {
+            if !result.iter().any(|x| x.name == part.register.name) {
+                result.push(&part.register);
+            }
+        }
+        result
+    }
+
+    /// Assemble the (unsigned) value from register values.
+    ///
+    /// Returns `None` if the value of any register holding a part is unknown.
+    pub fn assemble(&self, register_value: impl Fn(&Register) -> Option<TypeValue>) -> Option<TypeValue> {
+        let mut result = 0;
+        for (part, value_pos) in self.pieces() {
+            result |= part.field().bits.extract(register_value(&part.register)?) << value_pos;
+        }
+        Some(result)
+    }
+
+    /// Interpret an assembled value, sign-extending it if the virtual field is signed.
+    pub fn decode_value(&self, val: TypeValue) -> DecodedField {
+        if self.signed {
+            DecodedField::SInt(sign_extend(val, self.width()))
+        } else {
+            DecodedField::UInt(val)
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RegisterBitrangeContent<'a> {
     pub field: &'a LayoutField,
@@ -550,17 +985,19 @@ pub struct RegisterBitrange<'a> {
 }
 
 impl Layout {
-    pub fn split_to_bitranges(&self) -> Vec<RegisterBitrange> {
+    pub fn split_to_bitranges(&self) -> Vec<RegisterBitrange<'_>> {
         let mut result = vec![];
 
         for field in self.fields.values() {
-            result.push(RegisterBitrange {
-                bits: field.bits.clone(),
-                content: Some(RegisterBitrangeContent {
-                    field,
-                    subfield_mask: field.bits.unpositioned_mask(),
-                }),
-            });
+            for (range, value_pos) in field.bits.pieces() {
+                result.push(RegisterBitrange {
+                    content: Some(RegisterBitrangeContent {
+                        field,
+                        subfield_mask: range.unpositioned_mask() << value_pos,
+                    }),
+                    bits: range,
+                });
+            }
         }
 
         let empty_bits: Vec<TypeBitwidth> = (0..self.bitwidth).filter(|x| self.empty_at_bitpos(*x)).collect();
@@ -581,12 +1018,12 @@ impl Layout {
 #[derive(Clone, Debug)]
 pub struct FlattenedLayoutField<'a> {
     pub name: Vec<String>,
-    pub bits: BitRange,
+    pub bits: FieldBits,
     pub field: &'a LayoutField,
 }
 
 impl Layout {
-    pub fn nested_fields(&self) -> Vec<FlattenedLayoutField> {
+    pub fn nested_fields(&self) -> Vec<FlattenedLayoutField<'_>> {
         let mut result = vec![];
 
         for field in self.fields.values() {
@@ -624,14 +1061,14 @@ impl Layout {
         result
     }
 
-    pub fn nested_fields_with_content(&self) -> Vec<FlattenedLayoutField> {
+    pub fn nested_fields_with_content(&self) -> Vec<FlattenedLayoutField<'_>> {
         self.nested_fields()
             .into_iter()
             .filter(|x| x.field.contains_content())
             .collect()
     }
 
-    pub fn flattened_fields_with_content(&self) -> Vec<FlattenedLayoutField> {
+    pub fn flattened_fields_with_content(&self) -> Vec<FlattenedLayoutField<'_>> {
         self.nested_fields()
             .into_iter()
             .filter(|x| !matches!(x.field.accepts, FieldType::Layout(_)))
@@ -639,7 +1076,7 @@ impl Layout {
             .collect()
     }
 
-    pub fn flattened_fields(&self) -> Vec<FlattenedLayoutField> {
+    pub fn flattened_fields(&self) -> Vec<FlattenedLayoutField<'_>> {
         self.nested_fields()
             .into_iter()
             .filter(|x| !matches!(x.field.accepts, FieldType::Layout(_)))
@@ -696,6 +1133,32 @@ impl RegisterMap {
         max_width
     }
 
+    /// Error if any field is split across several bit ranges. Used by generators that
+    /// only support contiguous fields.
+    pub fn require_contiguous_fields(&self, generator: &str) -> Result<(), Error> {
+        for layout in self.layouts.values() {
+            if let Some(field) = layout.fields.values().find(|x| x.bits.is_split()) {
+                return Err(Error::GeneratorError(format!(
+                    "Field {}.{} is split across several bit ranges, which is not supported by the {generator} generator",
+                    layout.name, field.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Error if any layout is wider than the given bitwidth. Used by generators that
+    /// represent complete registers as a single integer.
+    pub fn require_max_layout_width(&self, max_width: TypeBitwidth, generator: &str) -> Result<(), Error> {
+        if let Some(layout) = self.layouts.values().find(|x| x.bitwidth > max_width) {
+            return Err(Error::GeneratorError(format!(
+                "Layout {} is {} bits wide, but the {generator} generator supports at most {max_width} bits",
+                layout.name, layout.bitwidth
+            )));
+        }
+        Ok(())
+    }
+
     pub fn shared_enums(&self) -> impl Iterator<Item = &Enum> {
         self.enums.values().filter(|x| !x.is_local).map(|x| x.deref())
     }
@@ -754,6 +1217,36 @@ pub fn access_str(access: &Access) -> String {
         .join("/")
 }
 
+/// Human-readable description of a scaling conversion, such as `800 + 25 × value`.
+pub fn scaling_str(scaling: &Scaling) -> String {
+    match &scaling.conversion {
+        ScalingConversion::Linear { scale: 1, offset: 0 } => "value".to_string(),
+        ScalingConversion::Linear { scale: 1, offset } => format!("{offset} + value"),
+        ScalingConversion::Linear { scale, offset: 0 } => format!("{scale} × value"),
+        ScalingConversion::Linear { scale, offset } => format!("{offset} + {scale} × value"),
+        ScalingConversion::Table(_) => "lookup table".to_string(),
+    }
+}
+
+/// Human-readable description of field value constraints, such as `min 0x1, max 0x5A, step 0x2`.
+pub fn constraints_str(constraints: &FieldConstraints) -> String {
+    let mut parts = vec![];
+    if let Some(min) = constraints.min {
+        parts.push(format!("min 0x{min:X}"));
+    }
+    if let Some(max) = constraints.max {
+        parts.push(format!("max 0x{max:X}"));
+    }
+    if let Some(step) = constraints.step {
+        parts.push(format!("step 0x{step:X}"));
+    }
+    if !constraints.reserved.is_empty() {
+        let reserved: Vec<_> = constraints.reserved.iter().map(|x| format!("0x{x:X}")).collect();
+        parts.push(format!("reserved {}", reserved.join("/")));
+    }
+    parts.join(", ")
+}
+
 #[cfg(test)]
 pub fn assert_regmap_eq(left: RegisterMap, right: RegisterMap) {
     use std::iter::zip;
@@ -786,10 +1279,35 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_field_bits() {
+        // Bit 7 holds bit 4 of the value, bits 3-0 hold bits 3-0:
+        let bits = FieldBits(vec![BitRange(7..=7), BitRange(0..=3)]);
+        assert!(bits.is_split());
+        assert_eq!(bits.width(), 5);
+        assert_eq!(bits.mask(), 0x8F);
+        assert_eq!(bits.unpositioned_mask(), 0x1F);
+        assert_eq!(bits.lsb_pos(), 0);
+        assert_eq!(bits.msb_pos(), 7);
+        assert!(bits.contains(&7));
+        assert!(!bits.contains(&5));
+        assert_eq!(bits.pieces(), vec![(BitRange(7..=7), 4), (BitRange(0..=3), 0)]);
+        assert_eq!(bits.to_string(RangeStyle::Verilog), "7, 3:0");
+        assert_eq!(bits.insert(0x15), 0x85);
+        assert_eq!(bits.extract(0xF5), 0x15);
+        assert_eq!(bits.shift_left(8).mask(), 0x8F00);
+
+        let bits: FieldBits = (2..=5).into();
+        assert!(!bits.is_split());
+        assert_eq!(bits.insert(0xF), 0x3C);
+        assert_eq!(bits.extract(0x3C), 0xF);
+    }
+
     #[test]
     fn test_split_to_bitranges() {
         let layout = Layout {
             bitwidth: 16,
+            bit_order: BitOrder::Lsb0,
             name: String::new(),
             docs: Docs::default(),
             is_local: false,
@@ -841,9 +1359,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bit_order() {
+        let mut layout = Layout {
+            bitwidth: 8,
+            bit_order: BitOrder::Msb0,
+            fields: BTreeMap::from([(
+                "A".into(),
+                LayoutField {
+                    name: "A".into(),
+                    bits: FieldBits(vec![BitRange(7..=7), BitRange(0..=3)]),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(layout.bit_number(7), 0);
+        assert_eq!(layout.bit_number(0), 7);
+        assert_eq!(layout.range_to_string(&BitRange(4..=7), RangeStyle::Verilog), "0:3");
+        assert_eq!(layout.range_to_string(&BitRange(4..=7), RangeStyle::RustInclusive), "0..=3");
+        assert_eq!(layout.bits_to_string(&layout.fields["A"].bits, RangeStyle::Verilog), "0, 4:7");
+        assert_eq!(layout.overview_text(false), "- [0, 4:7] A (uint)");
+
+        layout.bit_order = BitOrder::Lsb0;
+        assert_eq!(layout.bits_to_string(&layout.fields["A"].bits, RangeStyle::Verilog), "7, 3:0");
+        assert_eq!(layout.overview_text(false), "- [7, 3:0] A (uint)");
+    }
+
+    #[test]
+    fn test_scaling() {
+        let scaling = |unit: &str, conversion| Scaling {
+            unit: unit.to_string(),
+            conversion,
+        };
+        let linear = |scale, offset| ScalingConversion::Linear { scale, offset };
+
+        let vout = scaling("mV", linear(25, 800));
+        assert_eq!(vout.to_physical(8), Some(1000));
+        assert_eq!(vout.to_physical(i64::MAX), None);
+        assert!(vout.can_always_convert(7));
+        assert_eq!(scaling_str(&vout), "800 + 25 × value");
+        assert_eq!(scaling_str(&scaling("mV", linear(-2, 0))), "-2 × value");
+        assert_eq!(scaling_str(&scaling("mV", linear(1, 0))), "value");
+
+        let ilim = scaling("mA", ScalingConversion::Table(vec![100, 200, 500]));
+        assert_eq!(ilim.to_physical(2), Some(500));
+        assert_eq!(ilim.to_physical(3), None);
+        assert_eq!(ilim.to_physical(-1), None);
+        assert!(!ilim.can_always_convert(2));
+        assert_eq!(scaling_str(&ilim), "lookup table");
+
+        assert_eq!(ilim.unit_name(), "milliamps");
+        assert_eq!(scaling("V", linear(1, 0)).unit_name(), "volts");
+        assert_eq!(scaling("kHz", linear(1, 0)).unit_name(), "kilohertz");
+        assert_eq!(scaling("°C", linear(1, 0)).unit_name(), "degrees_celsius");
+        assert_eq!(scaling("LSB/g", linear(1, 0)).unit_name(), "lsb_g");
+    }
+
     #[test]
     fn test_enum_can_always_unpack() {
-        let create_enum = |values: Vec<u64>, width: TypeBitwidth| -> Enum {
+        let create_enum = |values: Vec<TypeValue>, width: TypeBitwidth| -> Enum {
             Enum {
                 entries: BTreeMap::from_iter(values.into_iter().map(|x| {
                     (
@@ -1,8 +1,11 @@
 use std::collections::{BTreeMap, HashSet};
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 use std::sync::LazyLock;
 
-use super::{Docs, Enum, FieldType, Layout, LayoutField, MAX_BITWIDTH, Register, TypeBitwidth, TypeValue};
+use super::{
+    Docs, Enum, FieldType, Layout, LayoutField, MAX_BITWIDTH, Register, RegisterMap, ScalingConversion, TypeAdr,
+    TypeBitwidth, TypeValue, VirtualField,
+};
 use crate::bits::{bitmask_from_width, fits_into_bitwidth};
 use crate::error::Error;
 use regex::Regex;
@@ -50,13 +53,13 @@ pub fn validate_name_unique(name: &str, namespace: &mut Namespace, bt: &str) ->
 }
 
 pub fn validate_map_author(author: &Option<String>, bt: &str) -> Result<(), Error> {
-    if let Some(author) = author {
-        if author.contains('\n') {
-            return Err(Error::ConversionError {
-                bt: bt.to_owned() + ".author",
-                msg: "Author may not contain more than one line.".to_owned(),
-            });
-        }
+    if let Some(author) = author
+        && author.contains('\n')
+    {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned() + ".author",
+            msg: "Author may not contain more than one line.".to_owned(),
+        });
     }
     Ok(())
 }
@@ -107,13 +110,13 @@ pub fn validate_docs(docs: Docs, bt: &str) -> Result<Docs, Error> {
         }
     };
 
-    if let Some(doc_content) = &docs.doc {
-        if doc_content.is_empty() {
-            return Err(Error::ConversionError {
-                bt: bt.to_owned() + ".doc",
-                msg: "Empty string".into(),
-            });
-        }
+    if let Some(doc_content) = &docs.doc
+        && doc_content.is_empty()
+    {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned() + ".doc",
+            msg: "Empty string".into(),
+        });
     };
 
     Ok(docs)
@@ -160,8 +163,20 @@ fn validate_field_type(field: &LayoutField, bt: &str) -> Result<(), Error> {
 
     let field_width = field.bits.width();
 
+    // Split fields are assembled from their ranges as plain numbers:
+    if field.bits.is_split() && !matches!(field.accepts, FieldType::UInt | FieldType::SInt | FieldType::Fixed(_)) {
+        return Err(Error::ConversionError {
+            bt,
+            msg: format!(
+                "Field {} is split across several bit ranges, and can only accept unsigned, signed or fixed values!",
+                field.name
+            ),
+        });
+    }
+
     match &field.accepts {
         FieldType::UInt => (),
+        FieldType::SInt => (),
         FieldType::Bool => {
             if field.bits.width() != 1 {
                 return Err(Error::ConversionError {
@@ -190,13 +205,7 @@ fn validate_field_type(field: &LayoutField, bt: &str) -> Result<(), Error> {
             }
 
             // Generate maximum value that fits in field_width bits
-            let max_value = if field_width == 0 {
-                0
-            } else if field_width >= 64 {
-                u64::MAX
-            } else {
-                (1u64 << field_width) - 1
-            };
+            let max_value = bitmask_from_width(field_width);
 
             if *val > max_value {
                 return Err(Error::ConversionError {
@@ -227,13 +236,7 @@ fn validate_field_type(field: &LayoutField, bt: &str) -> Result<(), Error> {
             }
 
             // Generate maximum value that fits in field_width bits
-            let max_value = if field_width == 0 {
-                0
-            } else if field_width >= 64 {
-                u64::MAX
-            } else {
-                (1u64 << field_width) - 1
-            };
+            let max_value = bitmask_from_width(field_width);
 
             if l.occupied_mask() > max_value {
                 return Err(Error::ConversionError {
@@ -249,6 +252,95 @@ fn validate_field_type(field: &LayoutField, bt: &str) -> Result<(), Error> {
     Ok(())
 }
 
+fn validate_field_scaling(field: &LayoutField, bt: &str) -> Result<(), Error> {
+    let Some(scaling) = &field.scaling else {
+        return Ok(());
+    };
+
+    let err = |msg: String| Error::ConversionError { bt: bt.to_owned(), msg };
+
+    if field.bits.width() > 64 {
+        return Err(err(format!("Scaled field {} must be at most 64 bits wide.", field.name)));
+    }
+
+    let Some(range) = field.numeric_range() else {
+        return Err(err(format!("Field {} is scaled, but does not accept an integer.", field.name)));
+    };
+
+    if !scaling.unit_name().starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(err(format!("Unit '{}' of field {} is not a valid name.", scaling.unit, field.name)));
+    }
+
+    match &scaling.conversion {
+        ScalingConversion::Linear { scale, offset } => {
+            if *scale == 0 {
+                return Err(err(format!("Scale of field {} is zero.", field.name)));
+            }
+
+            // All physical values, and the difference between them, must fit into an i64:
+            let to_physical = |val: i128| i128::from(*scale).checked_mul(val)?.checked_add(i128::from(*offset));
+            let span = i128::from(*scale).checked_mul(range.end() - range.start());
+            let fits = [to_physical(*range.start()), to_physical(*range.end()), span]
+                .iter()
+                .all(|x| x.is_some_and(|x| i64::try_from(x).is_ok()));
+            if !fits {
+                return Err(err(format!("Physical values of field {} do not fit into 64 bits.", field.name)));
+            }
+        }
+        ScalingConversion::Table(table) => {
+            if !matches!(field.accepts, FieldType::UInt) {
+                return Err(err(format!("Lookup table of field {} requires an unsigned field.", field.name)));
+            }
+            if field.bits.width() > 16 {
+                return Err(err(format!("Lookup table of field {} requires a field of at most 16 bits.", field.name)));
+            }
+            if table.is_empty() || table.len() as i128 > range.end() + 1 {
+                return Err(err(format!(
+                    "Lookup table of field {} must have between 1 and {} entries.",
+                    field.name,
+                    range.end() + 1
+                )));
+            }
+            if table.iter().collect::<HashSet<_>>().len() != table.len() {
+                return Err(err(format!("Lookup table of field {} contains duplicate values.", field.name)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_field_constraints(field: &LayoutField, bt: &str) -> Result<(), Error> {
+    let Some(constraints) = &field.constraints else {
+        return Ok(());
+    };
+
+    let err = |msg: String| Error::ConversionError { bt: bt.to_owned(), msg };
+
+    if !matches!(field.accepts, FieldType::UInt) {
+        return Err(err(format!("Field {} has value constraints, but is not an unsigned integer.", field.name)));
+    }
+
+    let field_max = field.bits.unpositioned_mask();
+    let min = constraints.min.unwrap_or(0);
+    let max = constraints.max.unwrap_or(field_max);
+
+    if min > field_max || max > field_max {
+        return Err(err(format!("Value constraints of field {} do not fit into the field.", field.name)));
+    }
+    if min > max {
+        return Err(err(format!("Minimum value of field {} is larger than its maximum value.", field.name)));
+    }
+    if constraints.step == Some(0) {
+        return Err(err(format!("Step of field {} is zero.", field.name)));
+    }
+    if let Some(val) = constraints.reserved.iter().find(|x| **x > field_max) {
+        return Err(err(format!("Reserved value 0x{val:X} does not fit into field {}.", field.name)));
+    }
+
+    Ok(())
+}
+
 fn find_layout_loop(layout: &Layout, mut visited_layouts: HashSet<String>, bt: &str) -> Result<(), Error> {
     if visited_layouts.contains(&layout.name) {
         return Err(Error::ConversionError {
@@ -276,7 +368,7 @@ pub fn validate_layout(layout: &Layout, bt: &str) -> Result<(), Error> {
         let bt = bt.to_owned() + ".fields." + &field.name;
 
         // Validate that field fits into layout:
-        if *field.bits.end() >= layout.bitwidth {
+        if field.bits.msb_pos() >= layout.bitwidth {
             return Err(Error::ConversionError {
                 bt,
                 msg: format!("Field {} is outside the {}-bit layout.", field.name, layout.bitwidth),
@@ -284,9 +376,11 @@ pub fn validate_layout(layout: &Layout, bt: &str) -> Result<(), Error> {
         }
 
         validate_field_type(field, &bt)?;
+        validate_field_scaling(field, &bt)?;
+        validate_field_constraints(field, &bt)?;
 
         // Validate that no fields overlap by checking for any overlapping bits
-        for bit_pos in field.bits.deref().clone() {
+        for bit_pos in field.bits.ranges().iter().flat_map(|x| x.deref().clone()) {
             if occupied_bits.contains(&bit_pos) {
                 return Err(Error::ConversionError {
                     bt,
@@ -315,12 +409,12 @@ pub fn validate_register_properties(
 
     // Validate that the field fits into the register:
     for field in layout.fields.values() {
-        if *field.bits.end() >= bitwidth {
+        if field.bits.msb_pos() >= bitwidth {
             return Err(Error::ConversionError {
                 bt: bt.to_owned() + ".bits",
                 msg: format!(
                     "Field with bit position {} does not fit into a {}-bit register!",
-                    field.bits.end(),
+                    field.bits.msb_pos(),
                     bitwidth
                 ),
             });
@@ -328,12 +422,33 @@ pub fn validate_register_properties(
     }
 
     // Validate that reset value fits into register:
+    if let Some(reset_val) = reset_val
+        && !fits_into_bitwidth(reset_val, bitwidth)
+    {
+        return Err(Error::ConversionError {
+            bt: bt.to_owned() + ".reset_val",
+            msg: format!("Reset value 0x{:x} does not fit into a {}-bit register!", reset_val, bitwidth),
+        });
+    }
+
+    // Validate that reset value satisfies all field constraints:
     if let Some(reset_val) = reset_val {
-        if !fits_into_bitwidth(reset_val, bitwidth) {
-            return Err(Error::ConversionError {
-                bt: bt.to_owned() + ".reset_val",
-                msg: format!("Reset value 0x{:x} does not fit into a {}-bit register!", reset_val, bitwidth),
-            });
+        for field in layout.nested_fields() {
+            let Some(constraints) = &field.field.constraints else {
+                continue;
+            };
+            let field_val = field.bits.extract(reset_val);
+            if !constraints.allows(field_val) {
+                return Err(Error::ConversionError {
+                    bt: bt.to_owned() + ".reset_val",
+                    msg: format!(
+                        "Reset value 0x{:x} sets field {} to 0x{:x}, which violates its value constraints!",
+                        reset_val,
+                        field.name.join("."),
+                        field_val
+                    ),
+                });
+            }
         }
     }
 
@@ -345,6 +460,144 @@ pub fn validate_register(reg: &Register, bt: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Validate that all registers fit into the address space, are aligned if required,
+/// and do not overlap.
+pub fn validate_register_addresses(map: &RegisterMap, bt: &str) -> Result<(), Error> {
+    let Some(space) = &map.address_space else {
+        return Ok(());
+    };
+
+    let mut occupied: Vec<(RangeInclusive<TypeAdr>, &Register)> = vec![];
+    for reg in map.registers.values() {
+        let bitwidth = reg.layout.bitwidth;
+        let Some(range) = space.adr_range(reg.adr, bitwidth) else {
+            return Err(Error::ConversionError {
+                bt: register_bt(reg, bt),
+                msg: format!("Register {} exceeds the address space!", describe_register(reg)),
+            });
+        };
+
+        let alignment = space.alignment(bitwidth);
+        if reg.adr % alignment != 0 {
+            return Err(Error::ConversionError {
+                bt: register_bt(reg, bt),
+                msg: format!(
+                    "Register {} at address 0x{:x} is not aligned to {alignment} address units!",
+                    describe_register(reg),
+                    reg.adr
+                ),
+            });
+        }
+
+        occupied.push((range, reg));
+    }
+
+    // Compare each register with the one reaching furthest among all that start before it:
+    occupied.sort_by_key(|(range, _)| *range.start());
+    let mut furthest: Option<&(RangeInclusive<TypeAdr>, &Register)> = None;
+    for entry in &occupied {
+        let (range, reg) = entry;
+        if let Some((other_range, other)) = furthest
+            && range.start() <= other_range.end()
+        {
+            let msg = match (&reg.from_block, &other.from_block) {
+                (Some(a), Some(b)) if a.block == b.block && a.instance != b.instance => format!(
+                    "Instances {} and {} of block {} collide: Register {} at {} overlaps register {} at {}!",
+                    b.instance,
+                    a.instance,
+                    a.block,
+                    other.name,
+                    adr_range_str(other_range),
+                    reg.name,
+                    adr_range_str(range),
+                ),
+                _ => format!(
+                    "Register {} at {} overlaps register {} at {}!",
+                    describe_register(reg),
+                    adr_range_str(range),
+                    describe_register(other),
+                    adr_range_str(other_range),
+                ),
+            };
+            return Err(Error::ConversionError {
+                bt: register_bt(reg, bt),
+                msg,
+            });
+        }
+
+        if furthest.is_none_or(|(other_range, _)| range.end() > other_range.end()) {
+            furthest = Some(entry);
+        }
+    }
+
+    Ok(())
+}
+
+fn register_bt(reg: &Register, bt: &str) -> String {
+    match &reg.from_block {
+        Some(origin) => format!("{bt}.registers.{}.instances.{}", origin.block, origin.instance),
+        None => format!("{bt}.registers.{}", reg.name),
+    }
+}
+
+fn describe_register(reg: &Register) -> String {
+    match &reg.from_block {
+        Some(origin) => format!("{} (instance {} of block {})", reg.name, origin.instance, origin.block),
+        None => reg.name.clone(),
+    }
+}
+
+fn adr_range_str(range: &RangeInclusive<TypeAdr>) -> String {
+    if range.start() == range.end() {
+        format!("0x{:x}", range.start())
+    } else {
+        format!("0x{:x}-0x{:x}", range.start(), range.end())
+    }
+}
+
+pub fn validate_virtual_field(field: &VirtualField, bt: &str) -> Result<(), Error> {
+    let bt = bt.to_owned() + ".parts";
+
+    if field.parts.is_empty() {
+        return Err(Error::ConversionError {
+            bt,
+            msg: format!("Virtual field {} requires at least one part!", field.name),
+        });
+    }
+
+    let mut seen_parts = HashSet::new();
+    for part in &field.parts {
+        // Parts hold raw bits of the value:
+        if !matches!(part.field().accepts, FieldType::UInt) {
+            return Err(Error::ConversionError {
+                bt,
+                msg: format!("Part {} of virtual field {} is not an unsigned field!", part.name(), field.name),
+            });
+        }
+
+        if !seen_parts.insert(part.name()) {
+            return Err(Error::ConversionError {
+                bt,
+                msg: format!("Part {} appears more than once in virtual field {}!", part.name(), field.name),
+            });
+        }
+    }
+
+    if field.width() > MAX_BITWIDTH {
+        return Err(Error::ConversionError {
+            bt,
+            msg: format!(
+                "Virtual field {} is {} bits wide, which exceeds the maximum of {} bits!",
+                field.name,
+                field.width(),
+                MAX_BITWIDTH
+            ),
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::regmap::RegisterMap;
@@ -477,6 +730,320 @@ mod tests {
         assert!(format!("{}", err).contains("that are already occupied"));
     }
 
+    #[test]
+    fn test_wide_registers() {
+        let map = |bitwidth: &str, field: &str| {
+            format!(
+                "
+        name: DummyChip
+        registers:
+            REG: !Register
+                adr: 0x1
+                bitwidth: {bitwidth}
+                reset_val: 0xAB000000000000000000000000000000
+                layout: !Layout
+                    A:
+                        bits: \"127-120\"
+                        accepts: !Fixed 0xAB
+                    B:
+{field}
+        "
+            )
+        };
+
+        let map_ok = RegisterMap::from_yaml_str(&map("128", "                        bits: \"95-0\"")).unwrap();
+        let field = &map_ok.registers["REG"].layout.fields["B"];
+        assert_eq!(field.bits.mask(), (1 << 96) - 1);
+
+        let cases = [
+            ("129", "                        bits: \"95-0\"", "greater than the maximal bitwidth"),
+            ("128", "                        bits: \"128-0\"", "outside the maximal bitwidth"),
+            (
+                "128",
+                "                        bits: \"95-0\"\n                        accepts: !Fixed 0x1000000000000000000000000",
+                "does not fit",
+            ),
+            (
+                "128",
+                "                        bits: \"64-0\"\n                        unit: mV\n                        scale: 2",
+                "at most 64 bits",
+            ),
+        ];
+
+        for (bitwidth, field, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&map(bitwidth, field)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+    }
+
+    #[test]
+    fn test_catch_bad_scaling() {
+        let map = |field: &str| {
+            format!(
+                "
+        name: DummyChip
+        registers:
+            REG: !Register
+                adr: 0x1
+                bitwidth: 8
+                layout: !Layout
+                    A:
+                        bits: \"0-3\"
+{field}
+        "
+            )
+        };
+
+        let cases = [
+            ("                        scale: 25", "requires a unit"),
+            ("                        unit: mV\n                        scale: 0", "is zero"),
+            (
+                "                        unit: mV\n                        accepts: !Fixed 3",
+                "does not accept an integer",
+            ),
+            (
+                "                        unit: mV\n                        table: [1, 2]\n                        scale: 2",
+                "both",
+            ),
+            ("                        unit: mV\n                        table: [1, 2, 1]", "duplicate"),
+            ("                        unit: mV\n                        table: []", "between 1 and 16"),
+            ("                        unit: mV\n                        scale: 0x7FFFFFFFFFFFFFFF", "do not fit"),
+            (
+                "                        unit: \"%\"\n                        accepts: !SInt\n                        table: [1]",
+                "unsigned",
+            ),
+        ];
+
+        for (field, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&map(field)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+
+        let map =
+            RegisterMap::from_yaml_str(&map("                        unit: mV\n                        scale: -25"))
+                .unwrap();
+        assert!(map.registers["REG"].layout.fields["A"].scaling.is_some());
+    }
+
+    #[test]
+    fn test_catch_bad_constraints() {
+        let listing = |field: &str, reset_val: &str| {
+            format!(
+                "
+        name: DummyChip
+        registers:
+            REG: !Register
+                adr: 0x1
+                bitwidth: 8
+                reset_val: {reset_val}
+                layout: !Layout
+                    A:
+                        bits: \"0-3\"
+{field}
+        "
+            )
+        };
+
+        let cases = [
+            ("                        max: 0x10", "0x0", "do not fit"),
+            ("                        min: 0x5\n                        max: 0x4", "0x5", "larger than its maximum"),
+            ("                        step: 0", "0x0", "is zero"),
+            ("                        reserved: [0x2, 0x11]", "0x0", "Reserved value 0x11"),
+            ("                        max: 0x2\n                        accepts: !SInt", "0x0", "not an unsigned"),
+            ("                        max: 0x9", "0xA", "violates"),
+            ("                        min: 0x1\n                        step: 0x2", "0x2", "violates"),
+            ("                        reserved: [0x0]", "0x0", "violates"),
+        ];
+
+        for (field, reset_val, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&listing(field, reset_val)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+
+        let map = RegisterMap::from_yaml_str(&listing(
+            "                        min: 0x1\n                        step: 0x2",
+            "0x3",
+        ))
+        .unwrap();
+        let constraints = map.registers["REG"].layout.fields["A"].constraints.as_ref().unwrap();
+        assert!(constraints.allows(0x3));
+        assert!(!constraints.allows(0x0));
+        assert!(!constraints.allows(0x4));
+
+        // Constraints that do not restrict the field are dropped:
+        let map = RegisterMap::from_yaml_str(&listing(
+            "                        min: 0\n                        max: 0xF",
+            "0x0",
+        ))
+        .unwrap();
+        assert!(map.registers["REG"].layout.fields["A"].constraints.is_none());
+    }
+
+    #[test]
+    fn test_catch_bad_split_fields() {
+        let listing = |bits: &str, accepts: &str| {
+            format!(
+                "
+        name: DummyChip
+        registers:
+            REG: !Register
+                adr: 0x1
+                bitwidth: 8
+                layout: !Layout
+                    A:
+                        bits: {bits}
+                        accepts: {accepts}
+        "
+            )
+        };
+
+        let cases = [
+            ("[7, \"4-0\", 3]", "!UInt", "already occupied"),
+            ("[8, \"3-0\"]", "!UInt", "outside"),
+            ("[7, \"3-0\"]", "!Enum { A: { val: 0 } }", "can only accept"),
+        ];
+
+        for (bits, accepts, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&listing(bits, accepts)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+
+        let map = RegisterMap::from_yaml_str(&listing("[7, \"3-0\"]", "!SInt")).unwrap();
+        assert_eq!(map.registers["REG"].layout.fields["A"].bits.mask(), 0x8F);
+    }
+
+    #[test]
+    fn test_catch_bad_virtual_fields() {
+        let listing = |parts: &str| {
+            format!(
+                "
+        name: DummyChip
+        registers:
+            HI: !Register
+                adr: 0x1
+                bitwidth: 8
+                layout: !Layout
+                    VAL:
+                        bits: \"3-0\"
+                        accepts: !UInt
+                    FLAG:
+                        bits: 7
+                        accepts: !Bool
+            LO: !Register
+                adr: 0x2
+                bitwidth: 8
+                layout: !Layout
+                    VAL:
+                        bits: \"7-0\"
+                        accepts: !UInt
+        virtual_fields:
+            COMBINED:
+                parts: {parts}
+        "
+            )
+        };
+
+        let cases = [
+            ("[]", "at least one part"),
+            ("[HI.FLAG, LO.VAL]", "not an unsigned field"),
+            ("[LO.VAL, LO.VAL]", "more than once"),
+            ("[HI]", "Malformed part"),
+            ("[MID.VAL]", "not found"),
+            ("[HI.OTHER]", "has no field"),
+        ];
+
+        for (parts, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&listing(parts)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+
+        let map = RegisterMap::from_yaml_str(&listing("[HI.VAL, LO.VAL]")).unwrap();
+        let field = &map.virtual_fields["COMBINED"];
+        assert_eq!(field.width(), 12);
+        let val = field.assemble(|reg| if reg.name == "HI" { Some(0xF5) } else { Some(0x3C) });
+        assert_eq!(val, Some(0x53C));
+        assert_eq!(field.assemble(|reg| if reg.name == "HI" { Some(0xF5) } else { None }), None);
+    }
+
+    #[test]
+    fn test_catch_bad_addresses() {
+        let listing = |address_space: &str, b_adr: &str, ch2_adr: &str| {
+            format!(
+                "
+        name: DummyChip
+        defaults:
+            layout_bitwidth: 32
+{address_space}
+        registers:
+            A: !Register
+                adr: 0x0
+            B: !Register
+                adr: {b_adr}
+                bitwidth: 16
+            CHn: !RegisterBlock
+                instances:
+                    CH1:
+                        adr: 0x100
+                    CH2:
+                        adr: {ch2_adr}
+                registers:
+                    STATUS:
+                        offset: 0x0
+                        layout: !Layout
+                    CTRL:
+                        offset: 0x4
+                        layout: !Layout
+        "
+            )
+        };
+        let registers = "        address_space: {}";
+        let bytes = "        address_space:\n            unit_bitwidth: 8";
+        let bytes_aligned = "        address_space:\n            unit_bitwidth: 8\n            aligned: true";
+
+        // Addresses are not validated without an address space:
+        RegisterMap::from_yaml_str(&listing("", "0x0", "0x100")).unwrap();
+
+        // Without a unit bitwidth, every register occupies a single address:
+        RegisterMap::from_yaml_str(&listing(registers, "0x1", "0x101")).unwrap();
+        RegisterMap::from_yaml_str(&listing(bytes, "0x4", "0x108")).unwrap();
+        RegisterMap::from_yaml_str(&listing(bytes_aligned, "0x6", "0x108")).unwrap();
+
+        let cases = [
+            (registers, "0x0", "0x108", "Register B at 0x0 overlaps register A at 0x0"),
+            (bytes, "0x3", "0x108", "Register B at 0x3-0x4 overlaps register A at 0x0-0x3"),
+            (bytes, "0x4", "0x106", "Instances CH1 and CH2 of block CHn collide"),
+            (bytes, "0x106", "0x200", "overlaps register CH1_CTRL (instance CH1 of block CHn)"),
+            (bytes_aligned, "0x5", "0x108", "not aligned to 2 address units"),
+            (bytes_aligned, "0x4", "0x102", "not aligned to 4 address units"),
+            (bytes, "0xFFFFFFFFFFFFFFFF", "0x108", "Register B exceeds the address space"),
+            (bytes, "0x4", "0xFFFFFFFFFFFFFFFE", "member 'CTRL' in instance 'CH2' exceeds the address space"),
+            ("        address_space:\n            unit_bitwidth: 0", "0x4", "0x108", "may not be zero"),
+        ];
+
+        for (address_space, b_adr, ch2_adr, msg) in cases {
+            let err = RegisterMap::from_yaml_str(&listing(address_space, b_adr, ch2_adr)).unwrap_err();
+            println!("{}", err);
+            assert!(format!("{}", err).contains(msg));
+        }
+
+        // Word-addressed map:
+        let map = RegisterMap::from_yaml_str(&listing(
+            "        address_space:\n            unit_bitwidth: 16",
+            "0x2",
+            "0x110",
+        ))
+        .unwrap();
+        let address_space = map.address_space.unwrap();
+        assert_eq!(address_space.width_in_units(32), 2);
+        assert_eq!(address_space.width_in_units(8), 1);
+        assert_eq!(address_space.adr_range(0x2, 32), Some(0x2..=0x3));
+    }
+
     #[test]
     fn test_catch_bad_enum() {
         let yaml = "
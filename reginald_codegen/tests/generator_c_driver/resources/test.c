@@ -0,0 +1,95 @@
+#include "Unity/unity.h"
+#include "Unity/unity_internals.h"
+#include "out.h"
+#include <string.h>
+
+// ====== FAKE BUS =============================================================
+
+struct bus {
+  uint8_t mem[0x40];
+  int error;
+};
+
+static int bus_read(void *user, uint8_t adr, uint8_t *buf, size_t len) {
+  struct bus *bus = (struct bus *)user;
+  if (bus->error != 0) {
+    return bus->error;
+  }
+  memcpy(buf, &bus->mem[adr], len);
+  return 0;
+}
+
+static int bus_write(void *user, uint8_t adr, const uint8_t *buf, size_t len) {
+  struct bus *bus = (struct bus *)user;
+  if (bus->error != 0) {
+    return bus->error;
+  }
+  memcpy(&bus->mem[adr], buf, len);
+  return 0;
+}
+
+static struct bus bus;
+static const struct chip_ctx ctx = {.read = bus_read, .write = bus_write, .user = &bus};
+
+static void set_field1_cool(struct chip_reg2 *r, void *arg) {
+  (void)arg;
+  r->field1 = CHIP_STAT_COOL;
+}
+
+// ====== TESTS ================================================================
+
+void test_read(void) {
+  bus.mem[0x10] = 0xBF;
+  bus.mem[0x17] = 0x81;
+
+  struct chip_reg3 reg = {0};
+  TEST_ASSERT_EQUAL(0, chip_read_reg3(&ctx, &reg));
+  TEST_ASSERT_EQUAL_HEX16(0xBF, reg.field0);
+  TEST_ASSERT_EQUAL_HEX8(0x81, reg.field1);
+}
+
+void test_write(void) {
+  struct chip_reg1 reg = {.field0 = true, .field1 = 0xA};
+  TEST_ASSERT_EQUAL(0, chip_write_reg1(&ctx, &reg));
+  TEST_ASSERT_EQUAL_HEX8((0x1 << 0) | (0xA << 2), bus.mem[0x0]);
+}
+
+void test_modify(void) {
+  // STAT_HOT in field 1, EN in field 2, and unused bits set:
+  bus.mem[0x0] = (0x3 << 6) | 0x3;
+  bus.mem[0x1] = 0xE0;
+
+  TEST_ASSERT_EQUAL(0, chip_modify_reg2(&ctx, set_field1_cool, NULL));
+  TEST_ASSERT_EQUAL_HEX8((0x1 << 6) | (0x1 << 4) | 0x3, bus.mem[0x0]);
+  TEST_ASSERT_EQUAL_HEX8(0xE0, bus.mem[0x1]);
+}
+
+void test_errors(void) {
+  struct chip_reg2 reg = {0};
+
+  // Invalid enum value in field 2:
+  bus.mem[0x0] = 0x0;
+  TEST_ASSERT_EQUAL(CHIP_ERR_UNPACK, chip_read_reg2(&ctx, &reg));
+  TEST_ASSERT_EQUAL(CHIP_ERR_UNPACK, chip_modify_reg2(&ctx, set_field1_cool, NULL));
+
+  // Transport error:
+  bus.error = 42;
+  TEST_ASSERT_EQUAL(42, chip_read_reg2(&ctx, &reg));
+  TEST_ASSERT_EQUAL(42, chip_write_reg2(&ctx, &reg));
+  TEST_ASSERT_EQUAL(42, chip_modify_reg2(&ctx, set_field1_cool, NULL));
+}
+
+// ======= MAIN ================================================================
+
+void setUp(void) { memset(&bus, 0, sizeof(bus)); }
+
+void tearDown(void) {}
+
+int main(void) {
+  UNITY_BEGIN();
+  RUN_TEST(test_read);
+  RUN_TEST(test_write);
+  RUN_TEST(test_modify);
+  RUN_TEST(test_errors);
+  return UNITY_END();
+}
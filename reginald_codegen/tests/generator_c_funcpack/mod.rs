@@ -16,7 +16,7 @@ use reginald_codegen::{
     utils::Endianess,
 };
 
-use crate::{TEST_MAP_FILE, print_cmd_output};
+use crate::{TEST_MAP_FILE, TEST_SPLIT_FIELDS_MAP_FILE, TEST_WIDE_MAP_FILE, print_cmd_output};
 
 // ==== Utils ==================================================================
 
@@ -124,11 +124,15 @@ fn finish_test(d: TempDir) {
 }
 
 fn run_reginald(d: &TempDir, output_name: &str, opts: GeneratorOpts) {
+    run_reginald_with_map(d, &TEST_MAP_FILE, output_name, opts);
+}
+
+fn run_reginald_with_map(d: &TempDir, map_file: &PathBuf, output_name: &str, opts: GeneratorOpts) {
     let output_path = d.path().to_owned().join(PathBuf::from(output_name));
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let artifacts_dir = manifest_dir.join(PathBuf::from("tests/generator_c_funcpack/artifacts"));
 
-    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+    let map = RegisterMap::from_file(map_file).unwrap();
 
     let mut out = String::new();
     c::funcpack::generate(&mut out, &map, Path::new(output_name), opts).unwrap();
@@ -214,6 +218,51 @@ fn generator_c_funcpack_defer_to_be() {
     finish_test(d);
 }
 
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_c_funcpack_mmio() {
+    let d = tempdir().unwrap();
+
+    run_reginald(
+        &d,
+        "out.h",
+        GeneratorOpts {
+            mmio_access: true,
+            ..GeneratorOpts::default()
+        },
+    );
+
+    test_generated_code(&d, &["-DTEST_MMIO", "-std=c11"], &[]);
+
+    finish_test(d);
+}
+
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_c_funcpack_split_fields() {
+    let d = tempdir().unwrap();
+
+    run_reginald(&d, "out.h", GeneratorOpts::default());
+    run_reginald_with_map(&d, &TEST_SPLIT_FIELDS_MAP_FILE, "out_split_fields.h", GeneratorOpts::default());
+
+    test_generated_code(&d, &["-DTEST_SPLIT_FIELDS", "-std=c11"], &[]);
+
+    finish_test(d);
+}
+
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_c_funcpack_wide_registers() {
+    let d = tempdir().unwrap();
+
+    run_reginald(&d, "out.h", GeneratorOpts::default());
+    run_reginald_with_map(&d, &TEST_WIDE_MAP_FILE, "out_wide.h", GeneratorOpts::default());
+
+    test_generated_code(&d, &["-DTEST_WIDE_REGISTERS", "-std=c11"], &[]);
+
+    finish_test(d);
+}
+
 #[test]
 #[cfg_attr(not(feature = "test_gen_output"), ignore)]
 fn generator_c_funcpack_split_header_source() {
@@ -225,6 +274,7 @@ fn generator_c_funcpack_split_header_source() {
         GeneratorOpts {
             funcs_as_prototypes: true,
             funcs_static_inline: false,
+            mmio_access: true,
             ..GeneratorOpts::default()
         },
     );
@@ -236,12 +286,13 @@ fn generator_c_funcpack_split_header_source() {
             funcs_static_inline: false,
             add_include: vec!["out.h".to_string()],
             include_guards: false,
+            mmio_access: true,
             to_generate: HashSet::from([Element::StructConversionFuncs]),
             ..GeneratorOpts::default()
         },
     );
 
-    test_generated_code(&d, &["-std=c11"], &[d.path().join("out.c").to_str().unwrap()]);
+    test_generated_code(&d, &["-DTEST_MMIO", "-std=c11"], &[d.path().join("out.c").to_str().unwrap()]);
 
     finish_test(d);
 }
@@ -3,6 +3,14 @@
 #include "out.h"
 #include <string.h>
 
+#ifdef TEST_SPLIT_FIELDS
+#include "out_split_fields.h"
+#endif
+
+#ifdef TEST_WIDE_REGISTERS
+#include "out_wide.h"
+#endif
+
 void reverse_array(uint8_t *from, uint8_t *to, size_t len) {
   for (size_t i = 0; i < len; i++) {
     to[i] = from[len - i - 1];
@@ -307,6 +315,234 @@ void test_nested_only_fixed(void) {
   TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_packed_reg, packed_reg_be, 1);
 }
 
+void test_signed_fields(void) {
+  struct chip_reg_signed reg = {
+      .offset = -2,
+      .trim = -8,
+      .full = -128,
+  };
+
+  // Packing:
+  uint8_t expected_packed_reg[3] = {0xFE, 0x8F, 0x80};
+  uint8_t packed_reg_le[3] = {0};
+  chip_reg_signed_pack_le(&reg, packed_reg_le);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_packed_reg, packed_reg_le, 3);
+
+  // Unpacking sign-extends:
+  reg = chip_reg_signed_unpack_le(expected_packed_reg);
+  TEST_ASSERT_EQUAL_INT16(-2, reg.offset);
+  TEST_ASSERT_EQUAL_INT8(-8, reg.trim);
+  TEST_ASSERT_EQUAL_INT8(-128, reg.full);
+
+  uint8_t packed_positive[3] = {0xFF, 0x77, 0x7F};
+  reg = chip_reg_signed_unpack_le(packed_positive);
+  TEST_ASSERT_EQUAL_INT16(0x7FF, reg.offset);
+  TEST_ASSERT_EQUAL_INT8(7, reg.trim);
+  TEST_ASSERT_EQUAL_INT8(127, reg.full);
+
+  // Validation:
+  TEST_ASSERT_EQUAL(0, chip_validate_reg_signed(&reg));
+  reg.trim = 8;
+  TEST_ASSERT_NOT_EQUAL(0, chip_validate_reg_signed(&reg));
+  reg.trim = -9;
+  TEST_ASSERT_NOT_EQUAL(0, chip_validate_reg_signed(&reg));
+}
+
+void test_scaled_fields(void) {
+  uint8_t vout = 0;
+  int8_t temp = 0;
+  uint8_t ilim = 0;
+  uint8_t vref = 0;
+  int64_t millivolts = 0;
+
+  // Linear conversion:
+  TEST_ASSERT_EQUAL_INT64(1000, chip_reg_scaled_vout_to_millivolts(8));
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(1000, &vout));
+  TEST_ASSERT_EQUAL_UINT8(8, vout);
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(800 + 25 * 127, &vout));
+  TEST_ASSERT_EQUAL_UINT8(127, vout);
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(1010, &vout));
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(775, &vout));
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(800 + 25 * 128, &vout));
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vout_from_millivolts(INT64_MIN, &vout));
+
+  // Signed linear conversion:
+  TEST_ASSERT_EQUAL_INT64(19, chip_reg_scaled_temp_to_degrees_celsius(-3));
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_temp_from_degrees_celsius(25 - 64, &temp));
+  TEST_ASSERT_EQUAL_INT8(-32, temp);
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_temp_from_degrees_celsius(25 + 62, &temp));
+  TEST_ASSERT_EQUAL_INT8(31, temp);
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_temp_from_degrees_celsius(25 - 66, &temp));
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_temp_from_degrees_celsius(25 + 64, &temp));
+
+  // Lookup table:
+  TEST_ASSERT_EQUAL_INT64(500, chip_reg_scaled_ilim_to_milliamps(2));
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_ilim_from_milliamps(1000, &ilim));
+  TEST_ASSERT_EQUAL_UINT8(3, ilim);
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_ilim_from_milliamps(300, &ilim));
+
+  // Partial lookup table:
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_vref_to_millivolts(0, &millivolts));
+  TEST_ASSERT_EQUAL_INT64(3300, millivolts);
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vref_to_millivolts(1, &millivolts));
+  TEST_ASSERT_EQUAL(0, chip_reg_scaled_vref_from_millivolts(3300, &vref));
+  TEST_ASSERT_EQUAL_UINT8(0, vref);
+  TEST_ASSERT_NOT_EQUAL(0, chip_reg_scaled_vref_from_millivolts(1800, &vref));
+}
+
+void test_constrained_fields(void) {
+  struct chip_reg_constrained reg = {0};
+
+  uint8_t packed_valid[1] = {0x80 | 0x30};
+  TEST_ASSERT_EQUAL(0, chip_reg_constrained_try_unpack_le(packed_valid, &reg));
+  TEST_ASSERT_EQUAL_UINT8(0x30, reg.code);
+  TEST_ASSERT_EQUAL_UINT8(0x2, reg.limit);
+
+  // Below minimum, off-step, reserved and above maximum:
+  uint8_t packed_invalid[] = {0x00, 0x03, 0x04, 0x08, 0x32};
+  for (size_t i = 0; i < sizeof(packed_invalid); i++) {
+    TEST_ASSERT_EQUAL(1, chip_reg_constrained_try_unpack_le(&packed_invalid[i], &reg));
+  }
+
+  uint8_t packed_invalid_limit[1] = {0xC2};
+  TEST_ASSERT_EQUAL(7, chip_reg_constrained_try_unpack_le(packed_invalid_limit, &reg));
+
+  reg.code = 0x12;
+  reg.limit = 0x1;
+  TEST_ASSERT_EQUAL(0, chip_validate_reg_constrained(&reg));
+  reg.code = 0x31;
+  TEST_ASSERT_EQUAL(1, chip_validate_reg_constrained(&reg));
+}
+
+void test_virtual_fields(void) {
+  // ADC: REG_ADC_MSB.VAL holds bits 11-4, REG_ADC_LSB.VAL holds bits 3-0.
+  struct chip_reg_adc_msb msb = {.val = 0xAB};
+  struct chip_reg_adc_lsb lsb = {.val = 0xC, .ready = true};
+  TEST_ASSERT_EQUAL_HEX16(0xABC, chip_adc_assemble(&msb, &lsb));
+
+  chip_adc_split(0x123, &msb, &lsb);
+  TEST_ASSERT_EQUAL_HEX8(0x12, msb.val);
+  TEST_ASSERT_EQUAL_HEX8(0x3, lsb.val);
+  TEST_ASSERT_TRUE(lsb.ready);
+
+  // ADC_TRIM: REG_ADC_LSB.VAL holds bits 7-4, REG1.FIELD1 holds bits 3-0.
+  struct chip_reg1 reg1 = {.field1 = 0xE, .field0 = true};
+  lsb.val = 0xF;
+  TEST_ASSERT_EQUAL_INT8(-2, chip_adc_trim_assemble(&lsb, &reg1));
+  lsb.val = 0x7;
+  TEST_ASSERT_EQUAL_INT8(0x7E, chip_adc_trim_assemble(&lsb, &reg1));
+
+  chip_adc_trim_split(-128, &lsb, &reg1);
+  TEST_ASSERT_EQUAL_HEX8(0x8, lsb.val);
+  TEST_ASSERT_EQUAL_HEX8(0x0, reg1.field1);
+  TEST_ASSERT_TRUE(reg1.field0);
+}
+
+#ifdef TEST_SPLIT_FIELDS
+void test_split_fields(void) {
+  // VAL: Bit 15 holds bit 4 of the value, bits 3-0 hold bits 3-0.
+  // OFS: Bits 13-12 hold bits 4-3 of the value, bits 9-8 hold bits 2-1, bit 4 holds bit 0.
+  // KEY: Fixed to 0b10 at bits 14 and 5.
+  struct split_reg_split reg = {
+      .val = 0x15,
+      .ofs = -3,
+  };
+
+  uint8_t expected_le[2] = {0x15, 0xF2};
+  uint8_t expected_be[2] = {0xF2, 0x15};
+
+  uint8_t packed_le[2] = {0};
+  split_reg_split_pack_le(&reg, packed_le);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_le, packed_le, 2);
+
+  uint8_t packed_be[2] = {0};
+  split_reg_split_pack_be(&reg, packed_be);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_be, packed_be, 2);
+
+  struct split_reg_split unpacked = split_reg_split_unpack_le(expected_le);
+  TEST_ASSERT_EQUAL_HEX8(0x15, unpacked.val);
+  TEST_ASSERT_EQUAL_INT8(-3, unpacked.ofs);
+
+  unpacked = split_reg_split_unpack_be(expected_be);
+  TEST_ASSERT_EQUAL_HEX8(0x15, unpacked.val);
+  TEST_ASSERT_EQUAL_INT8(-3, unpacked.ofs);
+
+  // Bits in between the ranges are ignored:
+  uint8_t packed_other[2] = {0xC0 | 0x0A, 0x0C | 0x40};
+  unpacked = split_reg_split_unpack_le(packed_other);
+  TEST_ASSERT_EQUAL_HEX8(0x0A, unpacked.val);
+  TEST_ASSERT_EQUAL_INT8(0, unpacked.ofs);
+}
+#endif
+
+#ifdef TEST_WIDE_REGISTERS
+void test_wide_registers(void) {
+  // 96-bit register:
+  struct wide_cal cal = {
+      .gain = 0x11223344,
+      .offset = -5,
+      .temp_coeff = 0xBEEF,
+  };
+
+  uint8_t expected_le[12] = {0x44, 0x33, 0x22, 0x11, 0xFB, 0xFF, 0xFF, 0xFF, 0xEF, 0xBE, 0x00, 0x12};
+  uint8_t packed_le[12] = {0};
+  wide_cal_pack_le(&cal, packed_le);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_le, packed_le, 12);
+
+  struct wide_cal unpacked = wide_cal_unpack_le(expected_le);
+  TEST_ASSERT_EQUAL_HEX32(0x11223344, unpacked.gain);
+  TEST_ASSERT_EQUAL_INT32(-5, unpacked.offset);
+  TEST_ASSERT_EQUAL_HEX16(0xBEEF, unpacked.temp_coeff);
+
+  uint8_t reset_be[12] = WIDE_CAL_RESET_BE;
+  uint8_t expected_reset_be[12] = {0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x12, 0x34, 0x56, 0x78};
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_reset_be, reset_be, 12);
+
+  // 128-bit register:
+  struct wide_desc desc = {
+      .addr = 0x0102030405060708U,
+      .len = 0xAABBCCDD,
+      .flags = WIDE_FLAGS_LAST,
+  };
+
+  uint8_t expected_be[16] = {0x02, 0x00, 0x00, 0x00, 0xAA, 0xBB, 0xCC, 0xDD,
+                             0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08};
+  uint8_t packed_be[16] = {0};
+  wide_desc_pack_be(&desc, packed_be);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_be, packed_be, 16);
+
+  struct wide_desc desc_unpacked = {0};
+  TEST_ASSERT_EQUAL(0, wide_desc_try_unpack_be(expected_be, &desc_unpacked));
+  TEST_ASSERT_EQUAL_HEX64(0x0102030405060708U, desc_unpacked.addr);
+  TEST_ASSERT_EQUAL_HEX32(0xAABBCCDD, desc_unpacked.len);
+  TEST_ASSERT_EQUAL(WIDE_FLAGS_LAST, desc_unpacked.flags);
+}
+#endif
+
+#ifdef TEST_MMIO
+void test_mmio(void) {
+  // Register block overlay:
+  TEST_ASSERT_EQUAL(6, sizeof(struct chip_block_regs));
+  TEST_ASSERT_EQUAL(4, offsetof(struct chip_block_regs, member_c));
+  TEST_ASSERT_EQUAL_PTR((void *)0x10, (void *)CHIP_BLOCK_INSTANCE_BLOCK1_REGS);
+
+  struct chip_block_regs block = {0};
+  chip_block_member_a_write(&block, 0xAB);
+  chip_block_member_c_write(&block, 0x0FED);
+  TEST_ASSERT_EQUAL_HEX8(0xAB, block.member_a);
+  TEST_ASSERT_EQUAL_HEX16(0x0FED, block.member_c);
+
+  block.member_b = 0x12;
+  TEST_ASSERT_EQUAL_HEX8(0x12, chip_block_member_b_read(&block));
+
+  // Individual registers:
+  uint64_t mem[8] = {0};
+  chip_reg3_write(mem, 0x8100000000000CBFU);
+  TEST_ASSERT_EQUAL_HEX64(0x8100000000000CBFU, mem[2]);
+  TEST_ASSERT_EQUAL_HEX64(0x8100000000000CBFU, chip_reg3_read(mem));
+}
+#endif
+
 // ======= MAIN ================================================================
 
 void setUp(void) {}
@@ -323,5 +559,18 @@ int main(void) {
   RUN_TEST(test_fixed_across_bytes);
   RUN_TEST(test_layout_fields);
   RUN_TEST(test_nested_only_fixed);
+  RUN_TEST(test_signed_fields);
+  RUN_TEST(test_scaled_fields);
+  RUN_TEST(test_constrained_fields);
+  RUN_TEST(test_virtual_fields);
+#ifdef TEST_SPLIT_FIELDS
+  RUN_TEST(test_split_fields);
+#endif
+#ifdef TEST_WIDE_REGISTERS
+  RUN_TEST(test_wide_registers);
+#endif
+#ifdef TEST_MMIO
+  RUN_TEST(test_mmio);
+#endif
   return UNITY_END();
 }
@@ -0,0 +1,133 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::{TempDir, tempdir};
+
+use reginald_codegen::{
+    builtin::cpp::{self, GeneratorOpts},
+    regmap::RegisterMap,
+};
+
+use crate::{TEST_MAP_FILE, print_cmd_output};
+
+// ==== Utils ==================================================================
+
+fn test_generated_code(test_dir: &TempDir, extra_cflags: &[&str]) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let test_resources_dir = manifest_dir.join(PathBuf::from("tests/generator_cpp/resources"));
+    let shared_resources_dir = manifest_dir.join(PathBuf::from("tests/resources"));
+
+    // Sources (Unity is compiled as C++ alongside the test):
+    let sources = vec![
+        test_resources_dir.join("test.cpp").to_str().unwrap().to_string(),
+        shared_resources_dir.join("Unity/unity.c").to_str().unwrap().to_string(),
+    ];
+
+    // c++ flags:
+    let mut cflags = vec![];
+    cflags.push("-Wall".to_string());
+    cflags.push("-Wextra".to_string());
+    cflags.push("-Wpedantic".to_string());
+    cflags.push("-Wconversion".to_string());
+    cflags.push("-Werror".to_string());
+    // include test resources dir (for test files):
+    cflags.push(format!("-I{}", test_resources_dir.to_str().unwrap()));
+    // include resources dir (for test framework):
+    cflags.push(format!("-I{}", shared_resources_dir.to_str().unwrap()));
+    // include test dir (for generated files):
+    cflags.push(format!("-I{}", test_dir.path().to_str().unwrap()));
+    // Extra c++ flags:
+    cflags.append(&mut extra_cflags.iter().map(|x| x.to_string()).collect());
+
+    // ==== Compile for host + run ====
+
+    let mut compile_args = vec![];
+    compile_args.push("-x".to_string());
+    compile_args.push("c++".to_string());
+    compile_args.extend(sources.clone());
+    compile_args.extend(cflags.clone());
+    compile_args.push("-fsanitize=undefined".to_string());
+
+    // output:
+    let test_exe = test_dir.path().join("test.out").to_str().unwrap().to_string();
+    compile_args.push("-o".to_string());
+    compile_args.push(test_exe.to_string());
+
+    println!("  G++ host args:");
+    for arg in &compile_args {
+        println!("    {}", arg);
+    }
+    println!("  Compiling for host...");
+    let compile_output = Command::new("g++").args(&compile_args).output().unwrap();
+    print_cmd_output(&compile_output);
+    assert!(compile_output.status.success());
+
+    println!("  Running tests...");
+    let test_output = Command::new(test_exe).output().unwrap();
+    print_cmd_output(&test_output);
+    assert!(test_output.status.success());
+
+    println!("  >>> OK!");
+}
+
+fn finish_test(d: TempDir) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let artifacts_dir = manifest_dir.join(PathBuf::from("tests/generator_cpp/artifacts"));
+    fs::create_dir_all(&artifacts_dir).unwrap();
+
+    for entry in fs::read_dir(d.path()).unwrap() {
+        let entry = entry.unwrap();
+        if !entry.file_type().unwrap().is_dir() {
+            fs::copy(entry.path(), artifacts_dir.join(entry.file_name())).unwrap();
+        }
+    }
+
+    d.close().unwrap();
+}
+
+fn run_reginald(d: &TempDir, output_name: &str, opts: GeneratorOpts) {
+    let output_path = d.path().to_owned().join(PathBuf::from(output_name));
+
+    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+
+    let mut out = String::new();
+    cpp::generate(&mut out, &map, Path::new(output_name), &opts).unwrap();
+
+    fs::write(&output_path, &out).unwrap();
+}
+
+// ==== Tests ==================================================================
+
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_cpp_cpp17() {
+    let d = tempdir().unwrap();
+
+    run_reginald(&d, "out.hpp", GeneratorOpts::default());
+
+    test_generated_code(&d, &["-std=c++17"]);
+
+    finish_test(d);
+}
+
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_cpp_cpp20_nested_namespace() {
+    let d = tempdir().unwrap();
+
+    run_reginald(
+        &d,
+        "out.hpp",
+        GeneratorOpts {
+            namespace: Some("vendor::chip".to_string()),
+            ..GeneratorOpts::default()
+        },
+    );
+
+    test_generated_code(&d, &["-std=c++20", "-DTEST_NESTED_NAMESPACE"]);
+
+    finish_test(d);
+}
@@ -0,0 +1,112 @@
+#include "Unity/unity.h"
+#include "Unity/unity_internals.h"
+#include "out.hpp"
+
+#include <array>
+#include <cstdint>
+#include <optional>
+#include <type_traits>
+
+#ifdef TEST_NESTED_NAMESPACE
+namespace regs = vendor::chip;
+#else
+namespace regs = chip;
+#endif
+
+// ====== COMPILE-TIME TESTS ===================================================
+
+// Packing/unpacking is usable in constant expressions:
+static_assert(regs::Reg1{true, 0xA}.pack_le()[0] == ((0x1 << 0) | (0xA << 2)));
+static_assert(regs::Reg1::unpack_le({(0x1 << 0) | (0xA << 2)}) == regs::Reg1{true, 0xA});
+
+// Register properties are looked up by type:
+static_assert(regs::address<regs::Reg3>() == 0x10);
+static_assert(regs::address<regs::Block2MemberC>() == 0x24);
+static_assert(!regs::reset_le<regs::Reg3>().has_value());
+static_assert((*regs::reset_le<regs::Reg2>())[0] == 0x43 && (*regs::reset_le<regs::Reg2>())[1] == 0x00);
+static_assert(regs::reset<regs::Block1MemberA>()->val == 0x1B);
+
+// Registers with shared layouts get their own type:
+static_assert(std::is_base_of_v<regs::BasicSharedLayout, regs::RegSharedLayoutBasic1>);
+static_assert(std::is_same_v<decltype(regs::RegSharedLayoutBasic1::unpack_le({0, 0})), regs::RegSharedLayoutBasic1>);
+static_assert(regs::block::block2_address + regs::block::member_c_offset == 0x24);
+
+// ====== TESTS ================================================================
+
+void test_pack_unpack(void) {
+  const regs::Reg2 reg{regs::Stat::Hot, regs::Field2::En, true, 0x1F};
+
+  // Fixed bits are set when packing:
+  const std::array<uint8_t, 2> expected_le = {(0x3 << 6) | (0x1 << 4) | (0x1 << 2) | 0x3, 0x1F};
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_le.data(), reg.pack_le().data(), 2);
+
+  const std::array<uint8_t, 2> expected_be = {expected_le[1], expected_le[0]};
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected_be.data(), reg.pack_be().data(), 2);
+
+  TEST_ASSERT_TRUE(regs::Reg2::unpack_le(expected_le) == reg);
+  TEST_ASSERT_TRUE(regs::Reg2::unpack_be(expected_be) == reg);
+}
+
+void test_wide_register(void) {
+  const regs::Reg3 reg{0xBEEF, 0x81};
+  const std::array<uint8_t, 8> expected = {0xEF, 0xBE, 0, 0, 0, 0, 0, 0x81};
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(expected.data(), reg.pack_le().data(), 8);
+  TEST_ASSERT_TRUE(regs::Reg3::unpack_le(expected) == reg);
+
+  const regs::RegFieldHugeEnum huge{regs::HugeEnum::F1};
+  const std::array<uint8_t, 8> ones = {0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF};
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(ones.data(), huge.pack_le().data(), 8);
+  TEST_ASSERT_TRUE(regs::RegFieldHugeEnum::try_unpack_le(ones) == huge);
+}
+
+void test_nested_layouts(void) {
+  const std::array<uint8_t, 2> packed = {0x03, 0xFD};
+  const regs::RegLayoutField reg = regs::RegLayoutField::unpack_be(packed);
+  TEST_ASSERT_TRUE(reg.layout_field.f1);
+  TEST_ASSERT_EQUAL_HEX8(0xFF, reg.layout_field.f2.f22);
+  TEST_ASSERT_EQUAL_HEX8_ARRAY(packed.data(), reg.pack_be().data(), 2);
+
+  // Reset value (0xFD03) is unpacked into nested layouts:
+  const std::optional<regs::RegLayoutField> reset = regs::reset<regs::RegLayoutField>();
+  TEST_ASSERT_TRUE(reset.has_value());
+  TEST_ASSERT_TRUE(reset->layout_field.f1);
+  TEST_ASSERT_EQUAL_HEX8(0x40, reset->layout_field.f2.f22);
+}
+
+void test_try_unpack(void) {
+  // Invalid enum value in field 2:
+  TEST_ASSERT_FALSE(regs::Reg2::try_unpack_le({0x00, 0x00}).has_value());
+  TEST_ASSERT_TRUE(regs::Reg2::try_unpack_le({0x43, 0x00}).has_value());
+
+  // Register types with shared layout:
+  const std::optional<regs::RegSharedLayoutBasic1> r = regs::RegSharedLayoutBasic1::try_unpack_le({0x1E, 0x04});
+  TEST_ASSERT_TRUE(r.has_value());
+  TEST_ASSERT_EQUAL_HEX8(0xF, r->shared_field1);
+  TEST_ASSERT_TRUE(r->shared_field2 == regs::SharedField2::IsOne);
+}
+
+void test_validation(void) {
+  regs::Reg1 reg{false, 0xF};
+  TEST_ASSERT_TRUE(reg.is_valid());
+  reg.field1 = 0x10;
+  TEST_ASSERT_FALSE(reg.is_valid());
+
+  TEST_ASSERT_TRUE(regs::is_valid(regs::Stat::Cool));
+  TEST_ASSERT_FALSE(regs::is_valid(static_cast<regs::Stat>(0)));
+}
+
+// ======= MAIN ================================================================
+
+void setUp(void) {}
+
+void tearDown(void) {}
+
+int main(void) {
+  UNITY_BEGIN();
+  RUN_TEST(test_pack_unpack);
+  RUN_TEST(test_wide_register);
+  RUN_TEST(test_nested_layouts);
+  RUN_TEST(test_try_unpack);
+  RUN_TEST(test_validation);
+  return UNITY_END();
+}
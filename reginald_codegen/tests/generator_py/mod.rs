@@ -0,0 +1,77 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use tempfile::{TempDir, tempdir};
+
+use reginald_codegen::{
+    builtin::py::{self, GeneratorOpts},
+    regmap::RegisterMap,
+    utils::Endianess,
+};
+
+use crate::{TEST_MAP_FILE, print_cmd_output};
+
+// ==== Utils ==================================================================
+
+fn test_generated_code(test_dir: &TempDir, test_file: &str) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let test_resources_dir = manifest_dir.join(PathBuf::from("tests/generator_py/resources"));
+
+    println!("  Running tests...");
+    let test_output = Command::new("python3")
+        .arg(test_resources_dir.join(test_file))
+        .env("PYTHONPATH", test_dir.path())
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .output()
+        .unwrap();
+    print_cmd_output(&test_output);
+    assert!(test_output.status.success());
+
+    println!("  >>> OK!");
+}
+
+fn finish_test(d: TempDir) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let artifacts_dir = manifest_dir.join(PathBuf::from("tests/generator_py/artifacts"));
+    fs::create_dir_all(&artifacts_dir).unwrap();
+
+    for entry in fs::read_dir(d.path()).unwrap() {
+        let entry = entry.unwrap();
+        if !entry.file_type().unwrap().is_dir() {
+            fs::copy(entry.path(), artifacts_dir.join(entry.file_name())).unwrap();
+        }
+    }
+
+    d.close().unwrap();
+}
+
+fn run_reginald(d: &TempDir, output_name: &str, opts: GeneratorOpts) {
+    let output_path = d.path().to_owned().join(PathBuf::from(output_name));
+
+    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+
+    let mut out = String::new();
+    py::generate(&mut out, &map, &opts).unwrap();
+
+    fs::write(&output_path, &out).unwrap();
+}
+
+// ==== Tests ==================================================================
+
+#[test]
+#[cfg_attr(not(feature = "test_gen_output"), ignore)]
+fn generator_py() {
+    let d = tempdir().unwrap();
+
+    run_reginald(&d, "out.py", GeneratorOpts::default());
+    run_reginald(
+        &d,
+        "out_le.py",
+        GeneratorOpts {
+            endian: vec![Endianess::Little],
+        },
+    );
+
+    test_generated_code(&d, "test.py");
+
+    finish_test(d);
+}
@@ -0,0 +1,94 @@
+import unittest
+
+import out
+import out_le
+
+
+class TestPackUnpack(unittest.TestCase):
+    def test_pack(self):
+        reg = out.Reg2(field1=out.Stat.HOT, field2=out.Field2.EN, field3=True, field4=0x1F)
+
+        # Fixed bits are set when packing:
+        expected = ((0x3 << 6) | (0x1 << 4) | (0x1 << 2) | 0x3) | (0x1F << 8)
+        self.assertEqual(reg.to_int(), expected)
+        self.assertEqual(reg.pack_le(), expected.to_bytes(2, "little"))
+        self.assertEqual(reg.pack_be(), expected.to_bytes(2, "big"))
+
+    def test_unpack(self):
+        reg = out.Reg2.unpack_be(bytes([0x1F, 0xC3]))
+        self.assertEqual(reg, out.Reg2(field1=out.Stat.HOT, field2=out.Field2.EN, field3=False, field4=0x1F))
+        self.assertEqual(out.Reg2.unpack_le(bytes([0xC3, 0x1F])), reg)
+
+        with self.assertRaises(ValueError):
+            out.Reg2.unpack_le(bytes([0xC3]))
+
+    def test_wide(self):
+        reg = out.Reg3(field0=0xBEEF, field1=0x81)
+        self.assertEqual(reg.pack_le(), bytes([0xEF, 0xBE, 0, 0, 0, 0, 0, 0x81]))
+        self.assertEqual(out.Reg3.unpack_le(reg.pack_le()), reg)
+
+        huge = out.RegFieldHugeEnum(huge_enum=out.HugeEnum.F1)
+        self.assertEqual(huge.pack_be(), bytes([0xFF] * 8))
+
+    def test_fixed_across_bytes(self):
+        self.assertEqual(out.RegFixedAcrossBytes().pack_le(), bytes([0x40, 0x02]))
+
+    def test_nested_layouts(self):
+        reg = out.RegLayoutField.reset()
+        self.assertEqual(reg.layout_field.f1, 1)
+        self.assertEqual(reg.layout_field.f2.f22, 0x40)
+        self.assertEqual(reg.to_int(), 0xFD03 & 0x03FD)
+
+        # Nested defaults are not shared between instances:
+        other = out.RegLayoutField()
+        other.layout_field.f2.f22 = 0xFF
+        self.assertEqual(out.RegLayoutField().layout_field.f2.f22, 0)
+
+
+class TestValidation(unittest.TestCase):
+    def test_invalid_enum(self):
+        with self.assertRaises(ValueError):
+            out.Reg2.unpack_le(bytes([0x00, 0x00]))
+
+        reg = out.Reg2()
+        reg.field2 = 0
+        with self.assertRaises(ValueError):
+            reg.pack_le()
+
+    def test_uint_range(self):
+        reg = out.Reg1(field1=0x10)
+        with self.assertRaises(ValueError):
+            reg.pack_le()
+        reg.field1 = 0xF
+        self.assertEqual(reg.pack_le(), bytes([0x3C]))
+
+
+class TestRegisters(unittest.TestCase):
+    def test_properties(self):
+        self.assertEqual(out.Reg2.ADDRESS, 0x00)
+        self.assertEqual(out.Reg2.RESET_VAL, 0x43)
+        self.assertIsNone(out.Reg3.RESET_VAL)
+        self.assertEqual(out.Block2MemberC.ADDRESS, 0x24)
+        self.assertEqual(out.BLOCK_INSTANCE_BLOCK2 + out.BLOCK_MEMBER_C_OFFSET, 0x24)
+
+    def test_reset(self):
+        self.assertEqual(out.Reg2.reset(), out.Reg2(field1=out.Stat.COOL, field2=out.Field2.EN))
+        self.assertEqual(out.Block1MemberA.reset().val, 0x1B)
+
+    def test_shared_layout(self):
+        reg = out.RegSharedLayoutBasic1.unpack_le(bytes([0x1E, 0x04]))
+        self.assertIsInstance(reg, out.RegSharedLayoutBasic1)
+        self.assertIsInstance(reg, out.BasicSharedLayout)
+        self.assertEqual(reg.shared_field1, 0xF)
+        self.assertEqual(reg.shared_field2, out.SharedField2.IS_ONE)
+
+
+class TestEndianOption(unittest.TestCase):
+    def test_only_little_endian(self):
+        self.assertTrue(hasattr(out_le.Reg2, "pack_le"))
+        self.assertFalse(hasattr(out_le.Reg2, "pack_be"))
+        self.assertFalse(hasattr(out_le.Reg2, "unpack_be"))
+
+
+if __name__ == "__main__":
+    unittest.main()
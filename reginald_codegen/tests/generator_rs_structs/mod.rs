@@ -5,16 +5,20 @@ use reginald_codegen::{
     regmap::RegisterMap,
 };
 
-use crate::{TEST_MAP_FILE, print_cmd_output};
+use crate::{TEST_MAP_FILE, TEST_SPLIT_FIELDS_MAP_FILE, TEST_WIDE_MAP_FILE, print_cmd_output};
 
 // ==== Utils ==================================================================
 
 fn run_reginald(output_name: &str, opts: GeneratorOpts) {
+    run_reginald_with_map(&TEST_MAP_FILE, output_name, opts);
+}
+
+fn run_reginald_with_map(map_file: &PathBuf, output_name: &str, opts: GeneratorOpts) {
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let output_dir = manifest_dir.join(PathBuf::from("tests/generator_rs_structs/test_proj/src/"));
     let output_file = output_dir.join(output_name);
 
-    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+    let map = RegisterMap::from_file(map_file).unwrap();
 
     let mut out = String::new();
     rs::structs::generate(&mut out, &map, &opts).unwrap();
@@ -57,6 +61,33 @@ fn generator_rs_structs() {
         },
     );
 
+    run_reginald(
+        "out_mmio.rs",
+        GeneratorOpts {
+            register_block_mmio: true,
+            ..GeneratorOpts::default()
+        },
+    );
+
+    run_reginald_with_map(
+        &TEST_SPLIT_FIELDS_MAP_FILE,
+        "out_split_fields.rs",
+        GeneratorOpts {
+            struct_derive: vec!["Debug".to_string(), "Clone".to_string(), "PartialEq".to_string()],
+            ..GeneratorOpts::default()
+        },
+    );
+
+    run_reginald_with_map(
+        &TEST_WIDE_MAP_FILE,
+        "out_wide.rs",
+        GeneratorOpts {
+            struct_derive: vec!["Debug".to_string(), "Clone".to_string(), "PartialEq".to_string()],
+            raw_enum_derive: vec!["Debug".to_string(), "PartialEq".to_string()],
+            ..GeneratorOpts::default()
+        },
+    );
+
     let output = Command::new("cargo")
         .args(&["test".to_string()])
         .current_dir(&test_proj)
@@ -7,6 +7,9 @@
 #![allow(clippy::inline_always)]
 
 pub mod out;
+pub mod out_mmio;
+pub mod out_split_fields;
+pub mod out_wide;
 
 // Unused. Included to ensure they compile:
 pub mod out_crate_traits;
@@ -152,6 +155,184 @@ mod tests {
         assert_eq!(reg_unpacked.field1, 0x81);
     }
 
+    #[test]
+    fn test_signed_fields() {
+        use crate::out::*;
+
+        // Packing:
+        let reg = RegSigned {
+            offset: -2,
+            trim: -8,
+            full: -128,
+        };
+
+        let expected_le: [u8; 3] = [0xFE, 0x8F, 0x80];
+        assert_eq!(expected_le, reg.to_le_bytes());
+
+        // Unpacking sign-extends:
+        let reg_unpacked = RegSigned::from_le_bytes(&expected_le);
+        assert_eq!(reg_unpacked.offset, -2);
+        assert_eq!(reg_unpacked.trim, -8);
+        assert_eq!(reg_unpacked.full, -128);
+
+        let reg_unpacked = RegSigned::from_le_bytes(&[0xFF, 0x77, 0x7F]);
+        assert_eq!(reg_unpacked.offset, 0x7FF);
+        assert_eq!(reg_unpacked.trim, 7);
+        assert_eq!(reg_unpacked.full, 127);
+
+        // Reset value:
+        let reset = RegSigned::default();
+        assert_eq!(reset.offset, -1);
+        assert_eq!(reset.trim, 7);
+        assert_eq!(reset.full, -128);
+    }
+
+    #[test]
+    fn test_scaled_fields() {
+        use crate::out::*;
+
+        let mut reg = RegScaled {
+            vout: 8,
+            ilim: 2,
+            temp: -3,
+            vref: 0,
+        };
+
+        // Linear conversion:
+        assert_eq!(reg.vout_to_millivolts(), 1000);
+        assert_eq!(RegScaled::vout_from_millivolts(1000), Some(8));
+        assert_eq!(RegScaled::vout_from_millivolts(800 + 25 * 127), Some(127));
+        assert_eq!(RegScaled::vout_from_millivolts(1010), None);
+        assert_eq!(RegScaled::vout_from_millivolts(775), None);
+        assert_eq!(RegScaled::vout_from_millivolts(800 + 25 * 128), None);
+        assert_eq!(RegScaled::vout_from_millivolts(i64::MIN), None);
+
+        // Signed linear conversion:
+        assert_eq!(reg.temp_to_degrees_celsius(), 19);
+        assert_eq!(RegScaled::temp_from_degrees_celsius(25 - 64), Some(-32));
+        assert_eq!(RegScaled::temp_from_degrees_celsius(25 + 62), Some(31));
+        assert_eq!(RegScaled::temp_from_degrees_celsius(25 - 66), None);
+        assert_eq!(RegScaled::temp_from_degrees_celsius(25 + 64), None);
+
+        // Lookup table:
+        assert_eq!(reg.ilim_to_milliamps(), 500);
+        assert_eq!(RegScaled::ilim_from_milliamps(1000), Some(3));
+        assert_eq!(RegScaled::ilim_from_milliamps(300), None);
+
+        // Partial lookup table:
+        assert_eq!(reg.vref_to_millivolts(), Some(3300));
+        assert_eq!(RegScaled::vref_from_millivolts(3300), Some(0));
+        assert_eq!(RegScaled::vref_from_millivolts(1800), None);
+        reg.vref = 1;
+        assert_eq!(reg.vref_to_millivolts(), None);
+
+        // Unsigned field wider than 32 bits:
+        let wide = RegScaledWide { vbat: 0xFF_FFFF_FFFF };
+        assert_eq!(wide.vbat_to_millivolts(), 5 + 2 * 0xFF_FFFF_FFFF);
+        assert_eq!(RegScaledWide::vbat_from_millivolts(5 + 2 * 0xFF_FFFF_FFFF), Some(0xFF_FFFF_FFFF));
+        assert_eq!(RegScaledWide::vbat_from_millivolts(5 + 2 * 0x100_0000_0000), None);
+    }
+
+    #[test]
+    fn test_constrained_fields() {
+        use crate::out::*;
+
+        let reg = RegConstrained::try_from_le_bytes(&[0x12]).unwrap();
+        assert_eq!(reg.code, 0x12);
+        assert_eq!(reg.limit, 0);
+
+        let reg = RegConstrained::try_from_le_bytes(&[0x80 | 0x30]).unwrap();
+        assert_eq!(reg.code, 0x30);
+        assert_eq!(reg.limit, 2);
+
+        // Below minimum, off-step, reserved and above maximum:
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0x00]).unwrap_err().pos, 0);
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0x03]).unwrap_err().pos, 0);
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0x04]).unwrap_err().pos, 0);
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0x08]).unwrap_err().pos, 0);
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0x32]).unwrap_err().pos, 0);
+        assert_eq!(RegConstrained::try_from_le_bytes(&[0xC2]).unwrap_err().pos, 6);
+
+        // Reset value:
+        assert_eq!(RegConstrained::default().code, 0x12);
+    }
+
+    #[test]
+    fn test_virtual_fields() {
+        use crate::out::*;
+
+        // ADC: REG_ADC_MSB.VAL holds bits 11-4, REG_ADC_LSB.VAL holds bits 3-0.
+        let mut msb = RegAdcMsb { val: 0xAB };
+        let mut lsb = RegAdcLsb { val: 0xC, ready: true };
+        assert_eq!(adc_assemble(&msb, &lsb), 0xABC);
+
+        adc_split(0x123, &mut msb, &mut lsb);
+        assert_eq!(msb.val, 0x12);
+        assert_eq!(lsb.val, 0x3);
+        assert!(lsb.ready);
+
+        // ADC_TRIM: REG_ADC_LSB.VAL holds bits 7-4, REG1.FIELD1 holds bits 3-0.
+        let mut reg1 = Reg1 { field1: 0xE, field0: true };
+        lsb.val = 0xF;
+        assert_eq!(adc_trim_assemble(&lsb, &reg1), -2);
+        lsb.val = 0x7;
+        assert_eq!(adc_trim_assemble(&lsb, &reg1), 0x7E);
+
+        adc_trim_split(-128, &mut lsb, &mut reg1);
+        assert_eq!(lsb.val, 0x8);
+        assert_eq!(reg1.field1, 0x0);
+        assert!(reg1.field0);
+    }
+
+    #[test]
+    fn test_split_fields() {
+        use crate::out_split_fields::*;
+
+        // VAL: Bit 15 holds bit 4 of the value, bits 3-0 hold bits 3-0.
+        // OFS: Bits 13-12 hold bits 4-3 of the value, bits 9-8 hold bits 2-1, bit 4 holds bit 0.
+        // KEY: Fixed to 0b10 at bits 14 and 5.
+        let reg = RegSplit { val: 0x15, ofs: -3 };
+
+        assert_eq!(reg.to_le_bytes(), [0x15, 0xF2]);
+        assert_eq!(reg.to_be_bytes(), [0xF2, 0x15]);
+        assert_eq!(RegSplit::from_le_bytes(&[0x15, 0xF2]), reg);
+
+        // Bits in between the ranges are ignored:
+        let reg = RegSplit::from_le_bytes(&[0xC0 | 0x0A, 0x0C | 0x40]);
+        assert_eq!(reg.val, 0x0A);
+        assert_eq!(reg.ofs, 0);
+
+        // Reset value:
+        assert_eq!(RegSplit::default(), RegSplit { val: 0x15, ofs: -3 });
+    }
+
+    #[test]
+    fn test_wide_registers() {
+        use crate::out_wide::*;
+
+        // 96-bit register:
+        let cal = Cal {
+            gain: 0x1122_3344,
+            offset: -5,
+            temp_coeff: 0xBEEF,
+        };
+        let packed = [0x44, 0x33, 0x22, 0x11, 0xFB, 0xFF, 0xFF, 0xFF, 0xEF, 0xBE, 0x00, 0x12];
+        assert_eq!(cal.to_le_bytes(), packed);
+        assert_eq!(Cal::from_le_bytes(&packed), cal);
+
+        // 128-bit register:
+        let desc = Desc {
+            addr: 0x0102_0304_0506_0708,
+            len: 0xAABB_CCDD,
+            flags: Flags::Last,
+        };
+        let packed = [
+            0x02, 0x00, 0x00, 0x00, 0xAA, 0xBB, 0xCC, 0xDD, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        ];
+        assert_eq!(desc.to_be_bytes(), packed);
+        assert_eq!(Desc::try_from_be_bytes(&packed).unwrap(), desc);
+    }
+
     #[test]
     fn register_validation() {
         use crate::out::*;
@@ -297,4 +478,28 @@ mod tests {
         assert_eq!(expected, reg.to_be_bytes());
         assert_eq!(expected[0], reg.into());
     }
+
+    #[test]
+    fn test_register_block_mmio() {
+        use crate::out_mmio::*;
+
+        // Layout:
+        assert_eq!(core::mem::size_of::<BlockRegisters>(), 6);
+        assert_eq!(BLOCK_INSTANCE_BLOCK1_PTR as usize, 0x10);
+        assert_eq!(BLOCK_INSTANCE_BLOCK2_PTR as usize, 0x20);
+
+        // Access through overlay:
+        let mut mem: [u16; 3] = [0; 3];
+        let regs = unsafe { &*(mem.as_mut_ptr().cast::<BlockRegisters>()) };
+
+        regs.member_a.write(0xAB);
+        regs.member_b.write(0x12);
+        regs.member_c.write(0x0FED);
+        regs.member_c.modify(|x| x & 0xFF);
+
+        assert_eq!(regs.member_a.read(), 0xAB);
+        assert_eq!(regs.member_b.read(), 0x12);
+        assert_eq!(regs.member_c.read(), 0xED);
+        assert_eq!(mem, [u16::from_ne_bytes([0xAB, 0x12]), 0, 0xED]);
+    }
 }
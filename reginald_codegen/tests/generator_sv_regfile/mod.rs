@@ -0,0 +1,116 @@
+use std::{fs, path::PathBuf};
+
+use reginald_codegen::{
+    builtin::sv::regfile::{self, BusInterface, GeneratorOpts},
+    regmap::RegisterMap,
+};
+
+use crate::TEST_MAP_FILE;
+
+// No SystemVerilog simulator is available during testing, so the generated
+// module is only checked for its key statements. The shared test map contains
+// overlapping registers, so a dedicated map is used.
+
+// ==== Utils ==================================================================
+
+fn run_reginald(output_name: &str, opts: GeneratorOpts) -> String {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let map_file = manifest_dir.join(PathBuf::from("tests/generator_sv_regfile/resources/map.yaml"));
+    let map = RegisterMap::from_file(&map_file).unwrap();
+
+    let mut out = String::new();
+    regfile::generate(&mut out, &map, &opts).unwrap();
+
+    let artifacts_dir = manifest_dir.join(PathBuf::from("tests/generator_sv_regfile/artifacts"));
+    fs::create_dir_all(&artifacts_dir).unwrap();
+    fs::write(artifacts_dir.join(output_name), &out).unwrap();
+
+    out
+}
+
+fn assert_contains(out: &str, lines: &[&str]) {
+    for line in lines {
+        assert!(out.lines().any(|x| x.trim() == *line), "Missing line: {line}");
+    }
+}
+
+fn assert_not_contains(out: &str, lines: &[&str]) {
+    for line in lines {
+        assert!(!out.lines().any(|x| x.trim() == *line), "Unexpected line: {line}");
+    }
+}
+
+// ==== Tests ==================================================================
+
+#[test]
+fn generator_sv_regfile_apb() {
+    let out = run_reginald("out_apb.sv", GeneratorOpts::default());
+
+    assert_contains(
+        &out,
+        &[
+            "module chip_regfile (",
+            "endmodule : chip_regfile",
+            // Bus interface:
+            "input  logic [4:0] paddr,",
+            "input  logic [15:0] pwdata,",
+            "assign pslverr = psel && penable && !hit;",
+            // Writable fields are stored, and driven to hardware:
+            "output logic [1:0] ctrl_mode_o,",
+            "output logic ctrl_wr_o,",
+            "ctrl_mode_q <= 2'h2;",
+            "ctrl_mode_q <= wdata[2:1];",
+            "cfg_timing_hold_q <= 4'h2;",
+            "ch1_data_val_q <= 8'hFF;",
+            // Read-only fields are driven by hardware:
+            "input  logic [7:0] status_level_i,",
+            "rdata[11:4] = status_level_i;",
+            // Fixed fields are constant:
+            "rdata[15:12] = 4'hA;",
+            // Block instances are placed at their addresses:
+            "localparam logic [4:0] CH1_DATA_ADDR = 5'h14;",
+        ],
+    );
+
+    assert_not_contains(
+        &out,
+        &[
+            // Write-only fields are not read back:
+            "rdata[7] = ctrl_kick_q;",
+            // Registers without storage have no write strobe:
+            "output logic status_wr_o,",
+        ],
+    );
+}
+
+#[test]
+fn generator_sv_regfile_valid_ready() {
+    let out = run_reginald(
+        "out_valid_ready.sv",
+        GeneratorOpts {
+            module_name: Some("regs".to_string()),
+            bus: BusInterface::ValidReady,
+            addr_width: Some(8),
+            data_width: Some(32),
+        },
+    );
+
+    assert_contains(
+        &out,
+        &[
+            "module regs (",
+            "input  logic [7:0] req_addr,",
+            "output logic [31:0] rsp_rdata,",
+            "assign req_ready = !rsp_valid || rsp_ready;",
+            "rsp_error <= !hit;",
+        ],
+    );
+}
+
+#[test]
+fn generator_sv_regfile_overlapping() {
+    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+
+    let mut out = String::new();
+    assert!(regfile::generate(&mut out, &map, &GeneratorOpts::default()).is_err());
+}
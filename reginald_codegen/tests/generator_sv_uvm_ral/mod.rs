@@ -0,0 +1,81 @@
+use std::{fs, path::PathBuf};
+
+use reginald_codegen::{
+    builtin::sv::uvm_ral::{self, GeneratorOpts},
+    regmap::RegisterMap,
+    utils::Endianess,
+};
+
+use crate::TEST_MAP_FILE;
+
+// No SystemVerilog simulator with UVM support is available during testing, so
+// the generated package is only checked for its key statements.
+
+// ==== Utils ==================================================================
+
+fn run_reginald(output_name: &str, opts: GeneratorOpts) -> String {
+    let map = RegisterMap::from_file(&TEST_MAP_FILE).unwrap();
+
+    let mut out = String::new();
+    uvm_ral::generate(&mut out, &map, &opts).unwrap();
+
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let artifacts_dir = manifest_dir.join(PathBuf::from("tests/generator_sv_uvm_ral/artifacts"));
+    fs::create_dir_all(&artifacts_dir).unwrap();
+    fs::write(artifacts_dir.join(output_name), &out).unwrap();
+
+    out
+}
+
+fn assert_contains(out: &str, lines: &[&str]) {
+    for line in lines {
+        assert!(out.lines().any(|x| x.trim() == *line), "Missing line: {line}");
+    }
+}
+
+// ==== Tests ==================================================================
+
+#[test]
+fn generator_sv_uvm_ral() {
+    let out = run_reginald("out.sv", GeneratorOpts::default());
+
+    assert_contains(
+        &out,
+        &[
+            "package chip_ral_pkg;",
+            "endpackage : chip_ral_pkg",
+            // Field access policies and reset values:
+            "class reg1_reg extends uvm_reg;",
+            "super.new(name, 8, UVM_NO_COVERAGE);",
+            "field1.configure(this, 4, 2, \"RW\", 0, 4'h0, 1, 1, 0);",
+            "field2.configure(this, 2, 0, \"RO\", 1, 2'h3, 1, 0, 0);",
+            "reserved.configure(this, 2, 4, \"RO\", 0, 2'h1, 1, 0, 0);",
+            "field0.configure(this, 16, 0, \"RW\", 0, 16'h0, 0, 1, 0);",
+            "layout_field_f2_f22.configure(this, 8, 2, \"RW\", 0, 8'h40, 1, 1, 0);",
+            // Register maps:
+            "default_map = create_map(\"default_map\", 0, 8, UVM_LITTLE_ENDIAN, 1);",
+            "default_map.add_reg(reg2, 'h0, \"RO\");",
+            "default_map.add_reg(member_c, 'h4, \"RW\");",
+            "default_map.add_submap(block1.default_map, 'h10);",
+            "default_map.add_submap(block2.default_map, 'h20);",
+            "lock_model();",
+        ],
+    );
+
+    let out = run_reginald(
+        "out_be.sv",
+        GeneratorOpts {
+            package_name: Some("regs_pkg".to_string()),
+            endian: Endianess::Big,
+            bus_width_bytes: Some(4),
+        },
+    );
+
+    assert_contains(
+        &out,
+        &[
+            "package regs_pkg;",
+            "default_map = create_map(\"default_map\", 0, 4, UVM_BIG_ENDIAN, 1);",
+        ],
+    );
+}
@@ -1,10 +1,17 @@
+mod generator_c_driver;
 mod generator_c_funcpack;
 mod generator_c_macromap;
+mod generator_cpp;
+mod generator_py;
 mod generator_rs_structs;
+mod generator_sv_regfile;
+mod generator_sv_uvm_ral;
 
 use std::{path::PathBuf, process::Output, sync::LazyLock};
 
-pub static TEST_MAP_FILE: LazyLock<PathBuf> = LazyLock::new(|| find_test_map_file());
+pub static TEST_MAP_FILE: LazyLock<PathBuf> = LazyLock::new(find_test_map_file);
+pub static TEST_SPLIT_FIELDS_MAP_FILE: LazyLock<PathBuf> = LazyLock::new(find_test_split_fields_map_file);
+pub static TEST_WIDE_MAP_FILE: LazyLock<PathBuf> = LazyLock::new(find_test_wide_map_file);
 
 fn find_test_map_file() -> PathBuf {
     let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -12,6 +19,18 @@ fn find_test_map_file() -> PathBuf {
     d
 }
 
+fn find_test_split_fields_map_file() -> PathBuf {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/map_split_fields.yaml");
+    d
+}
+
+fn find_test_wide_map_file() -> PathBuf {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("tests/map_wide.yaml");
+    d
+}
+
 pub fn print_cmd_output(out: &Output) {
     println!("  {}", &out.status);
     if !out.stdout.is_empty() {
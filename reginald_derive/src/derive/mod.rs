@@ -1,12 +1,13 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{quote, quote_spanned};
 use reginald_utils::{
-    Bits, Endianess, ShiftDirection, field_byte_to_packed_byte_transform, packed_byte_to_field_byte_transform,
+    Bits, Endianess, RangeStyle, ShiftDirection, field_byte_to_packed_byte_transform,
+    packed_byte_to_field_byte_transform,
 };
 
 use crate::{
-    input::{EnumDeriveInput, Field, FieldType, StructDeriveInput, UInt},
-    utils::{prefix_ident, spanned_err},
+    input::{EnumDeriveInput, Field, FieldType, SInt, StructDeriveInput, UInt},
+    utils::{WithTokens, prefix_ident, spanned_err},
 };
 
 pub fn derive_struct_to_bytes(input: &StructDeriveInput) -> syn::Result<TokenStream> {
@@ -111,6 +112,12 @@ pub fn derive_struct_from_bytes(input: &StructDeriveInput) -> syn::Result<TokenS
             FieldType::UInt(UInt::U128) => {
                 quote_spanned! { field.name.span() => #field_name: u128::from_le_bytes(#field_arr_name), }
             }
+            FieldType::SInt(i) => {
+                let ty = sint_type(i);
+                let shift = (i.width_bytes() * 8 - field.bits.positioned_bitwidth()) as u32;
+                // Sign-extend by shifting the sign bit into the MSB and back:
+                quote_spanned! { field.name.span() => #field_name: (#ty::from_le_bytes(#field_arr_name) << #shift) >> #shift, }
+            }
             FieldType::Trait(t) => {
                 let field_type_name = &field.field_type_name;
                 if t.masked {
@@ -218,6 +225,12 @@ pub fn derive_struct_try_from_bytes(input: &StructDeriveInput) -> syn::Result<To
             FieldType::UInt(UInt::U128) => {
                 quote_spanned! { field.name.span() => #field_name: u128::from_le_bytes(#field_arr_name), }
             }
+            FieldType::SInt(i) => {
+                let ty = sint_type(i);
+                let shift = (i.width_bytes() * 8 - field.bits.positioned_bitwidth()) as u32;
+                // Sign-extend by shifting the sign bit into the MSB and back:
+                quote_spanned! { field.name.span() => #field_name: (#ty::from_le_bytes(#field_arr_name) << #shift) >> #shift, }
+            }
             FieldType::Trait(_) => {
                 let field_type_name = &field.field_type_name;
                 let field_pos = field.bits.lsb_pos();
@@ -250,6 +263,80 @@ pub fn derive_struct_try_from_bytes(input: &StructDeriveInput) -> syn::Result<To
     Ok(out)
 }
 
+pub fn derive_struct_register(input: &StructDeriveInput) -> syn::Result<TokenStream> {
+    let width_bytes = input.width_bytes;
+    let name = &input.name;
+
+    // Address:
+    let Some(address) = &input.address else {
+        return spanned_err!(name, "Reginald: Register is missing address attribute.");
+    };
+    let fitting_address_type = match address.inner {
+        0..=0xFF => UInt::U8,
+        0x100..=0xFFFF => UInt::U16,
+        0x1_0000..=0xFFFF_FFFF => UInt::U32,
+        0x1_0000_0000..=0xFFFF_FFFF_FFFF_FFFF => UInt::U64,
+        _ => UInt::U128,
+    };
+    let address_type = input.address_type.clone().unwrap_or(fitting_address_type.clone());
+    if address_type.width_bytes() < fitting_address_type.width_bytes() {
+        return spanned_err!(address, "Reginald: Address does not fit into address type {address_type}.");
+    }
+    let address_type: syn::Ident = syn::parse_str(&address_type.to_string())?;
+    let address_val = Literal::u128_unsuffixed(address.inner);
+
+    // Reset value:
+    let reset_val = if let Some(reset) = &input.reset {
+        validate_reset_val(input, reset)?;
+        let bytes: Vec<u8> = (0..width_bytes)
+            .map(|x| reset.inner.get_byte(input.reset_endian, x, width_bytes))
+            .collect();
+        match input.reset_endian {
+            Endianess::Little => quote! { Some(::reginald::ResetVal::LittleEndian([#(#bytes),*])) },
+            Endianess::Big => quote! { Some(::reginald::ResetVal::BigEndian([#(#bytes),*])) },
+        }
+    } else {
+        quote! { None }
+    };
+
+    let out = quote! {
+        impl ::reginald::Register<#width_bytes, #address_type> for #name {
+            const ADDRESS: #address_type = #address_val;
+            const RESET_VAL: Option<::reginald::ResetVal<#width_bytes>> = #reset_val;
+        }
+    };
+
+    Ok(out)
+}
+
+/// Ensure that the reset value is not changed by unpacking it into the struct.
+fn validate_reset_val(input: &StructDeriveInput, reset: &WithTokens<Bits>) -> syn::Result<()> {
+    let width_bytes = input.width_bytes;
+    if reset.inner.width_bytes() > width_bytes {
+        return spanned_err!(reset, "Reginald: Reset value does not fit into {width_bytes} bytes.");
+    }
+
+    let fixed_bits = &input.fixed_bits;
+    if (&reset.inner & &fixed_bits.mask) != fixed_bits.value {
+        return spanned_err!(reset, "Reginald: Reset value does not match fixed bits.");
+    }
+
+    let mut known_bits = fixed_bits.mask.clone();
+    for field in &input.fields {
+        known_bits |= &field.bits;
+    }
+    let lost_bits = reset.inner.clear_mask(&known_bits);
+    if lost_bits.is_nonzero() {
+        let lost_bits = lost_bits.to_bit_ranges_str(RangeStyle::RustInclusive);
+        return spanned_err!(
+            reset,
+            "Reginald: Reset value sets bits [{lost_bits}] which are not part of any field or fixed bits."
+        );
+    }
+
+    Ok(())
+}
+
 pub fn derive_enum_try_from_bytes(input: &EnumDeriveInput) -> syn::Result<TokenStream> {
     let width_bytes = input.width_bytes;
     let name = &input.name;
@@ -331,6 +418,16 @@ pub fn derive_enum_wrapping_from_bytes(input: &EnumDeriveInput) -> syn::Result<T
 
 // ==== Utils ==================================================================
 
+fn sint_type(i: &SInt) -> TokenStream {
+    match i {
+        SInt::I8 => quote!(i8),
+        SInt::I16 => quote!(i16),
+        SInt::I32 => quote!(i32),
+        SInt::I64 => quote!(i64),
+        SInt::I128 => quote!(i128),
+    }
+}
+
 fn generate_struct_field_to_byte_array(struct_info: &StructDeriveInput, field: &Field) -> Vec<TokenStream> {
     let mut lines = vec![];
     let field_width_bytes = field.field_type.trait_width_bytes();
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use reginald_codegen::{builtin::rs::structs, regmap::RegisterMap};
+use syn::{
+    AttrStyle, Expr, Lit, LitStr, Meta, Token,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::utils::{spanned_err, spanned_error};
+
+pub struct IncludeMapInput {
+    path: LitStr,
+    map_name: Option<String>,
+    opts: structs::GeneratorOpts,
+}
+
+impl Parse for IncludeMapInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: LitStr = input.parse()?;
+        let mut map_name = None;
+        let mut opts = structs::GeneratorOpts::default();
+
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let nested = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+            for meta in nested {
+                // include_map!("map.yaml", external_traits = "reginald::", struct_derive = ["Debug"])
+                let name_value = meta.require_name_value()?;
+                let value = &name_value.value;
+                let Some(key) = name_value.path.get_ident() else {
+                    return spanned_err!(&name_value.path, "Reginald: Unknown include_map option.");
+                };
+                match key.to_string().as_str() {
+                    "map_name" => map_name = Some(parse_str(value)?),
+                    "address_type" => opts.address_type = Some(parse_str(value)?),
+                    "struct_derive" => opts.struct_derive = parse_str_list(value)?,
+                    "enum_derive" => opts.raw_enum_derive = parse_str_list(value)?,
+                    "add_use" => opts.add_use = parse_str_list(value)?,
+                    "add_attribute" => opts.add_attribute = parse_str_list(value)?,
+                    "external_traits" => opts.external_traits = Some(parse_str(value)?),
+                    "generate_uint_conversion" => opts.generate_uint_conversion = parse_bool(value)?,
+                    "register_block_mmio" => opts.register_block_mmio = parse_bool(value)?,
+                    _ => return spanned_err!(key, "Reginald: Unknown include_map option."),
+                }
+            }
+        }
+
+        Ok(Self { path, map_name, opts })
+    }
+}
+
+pub fn include_map(inp: &IncludeMapInput) -> syn::Result<TokenStream> {
+    // Listing paths are relative to the root of the crate being compiled:
+    let mut path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(inp.path.value());
+
+    let mut map = RegisterMap::from_file(&path).map_err(|err| {
+        let path = path.to_string_lossy();
+        spanned_error!(&inp.path, "Reginald: Failed to read listing '{path}': {err}")
+    })?;
+    if let Some(map_name) = &inp.map_name {
+        map.name.clone_from(map_name);
+    }
+
+    let mut out = String::new();
+    structs::generate(&mut out, &map, &inp.opts)
+        .map_err(|err| spanned_error!(&inp.path, "Reginald: Failed to generate register definitions: {err}"))?;
+
+    let file = syn::parse_file(&out)
+        .map_err(|err| spanned_error!(&inp.path, "Reginald: Generated register definitions are invalid: {err}"))?;
+
+    // The macro expands to items in the surrounding module, where inner
+    // attributes are not allowed: Apply module-level attributes to every
+    // item instead, and drop module-level docs.
+    let attrs: Vec<_> = file
+        .attrs
+        .into_iter()
+        .filter(|x| !x.path().is_ident("doc"))
+        .map(|mut x| {
+            x.style = AttrStyle::Outer;
+            x
+        })
+        .collect();
+    let items = file.items.iter().map(|item| quote! { #(#attrs)* #item });
+
+    // Make cargo re-compile the crate if the listing changes:
+    let path = path.to_string_lossy();
+
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#path);
+        #(#items)*
+    })
+}
+
+fn parse_str(inp: &Expr) -> syn::Result<String> {
+    let Expr::Lit(lit) = inp else {
+        return spanned_err!(inp, "Reginald: Expected string literal.");
+    };
+
+    let Lit::Str(lit) = &lit.lit else {
+        return spanned_err!(inp, "Reginald: Expected string literal.");
+    };
+
+    Ok(lit.value())
+}
+
+fn parse_str_list(inp: &Expr) -> syn::Result<Vec<String>> {
+    match inp {
+        Expr::Array(array) => array.elems.iter().map(parse_str).collect(),
+        inp => Ok(vec![parse_str(inp)?]),
+    }
+}
+
+fn parse_bool(inp: &Expr) -> syn::Result<bool> {
+    let Expr::Lit(lit) = inp else {
+        return spanned_err!(inp, "Reginald: Expected boolean literal.");
+    };
+
+    let Lit::Bool(lit) = &lit.lit else {
+        return spanned_err!(inp, "Reginald: Expected boolean literal.");
+    };
+
+    Ok(lit.value)
+}
@@ -3,7 +3,7 @@ mod parse;
 use std::{collections::HashSet, fmt::Display};
 
 use proc_macro2::Ident;
-use reginald_utils::{RangeStyle, bits::Bits};
+use reginald_utils::{Endianess, RangeStyle, bits::Bits};
 use syn::parse::{Parse, ParseStream};
 
 use crate::{
@@ -11,7 +11,7 @@ use crate::{
     utils::{WithTokens, attach_spanned_error, spanned_err},
 };
 
-use self::parse::{FieldTypeInfo, StructFieldInfo, parse_enum};
+use self::parse::{FieldTypeInfo, ResetAttr, StructFieldInfo, parse_enum};
 
 // ==== Derive Input ===========================================================
 
@@ -42,6 +42,10 @@ pub struct StructDeriveInput {
     pub width_bytes: usize,
     pub fixed_bits: FixedBits,
     pub fields: Vec<Field>,
+    pub address: Option<WithTokens<u128>>,
+    pub address_type: Option<UInt>,
+    pub reset: Option<WithTokens<Bits>>,
+    pub reset_endian: Endianess,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -61,6 +65,7 @@ pub struct Field {
 #[derive(Debug, Clone)]
 pub enum FieldType {
     UInt(UInt),
+    SInt(SInt),
     Trait(TraitField),
     Bool,
 }
@@ -74,6 +79,15 @@ pub enum UInt {
     U128,
 }
 
+#[derive(Debug, Clone)]
+pub enum SInt {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
 #[derive(Debug, Clone)]
 pub struct TraitField {
     pub trait_width_bytes: usize,
@@ -127,11 +141,37 @@ fn parse_struct_derive_input(
     }
     check_for_bits_overlap(&all_bits, "Field bits")?;
 
+    // Reset value, with byte arrays given in the reset endianess:
+    let reset_endian = struct_info
+        .reset_endian_attr
+        .map(|x| x.inner)
+        .unwrap_or(Endianess::Little);
+    let reset = struct_info.reset_attr.map(|reset| {
+        reset.map(|x| match x {
+            ResetAttr::Value(val) => Bits::from_uint(*val),
+            ResetAttr::Bytes(bytes) => {
+                let mut bits = Bits::new();
+                for (idx, byte) in bytes.iter().enumerate() {
+                    let byte_pos = match reset_endian {
+                        Endianess::Little => idx,
+                        Endianess::Big => bytes.len() - 1 - idx,
+                    };
+                    bits |= &(Bits::from_uint(*byte as u128) << (byte_pos * 8));
+                }
+                bits
+            }
+        })
+    });
+
     Ok(ReginaldDeriveInput::Struct(StructDeriveInput {
         name: input.ident.clone(),
         fixed_bits: struct_info.fixed_bits_attr.clone(),
         width_bytes,
         fields,
+        address: struct_info.address_attr,
+        address_type: struct_info.address_type_attr.map(|x| x.inner),
+        reset,
+        reset_endian,
     }))
 }
 
@@ -146,6 +186,7 @@ fn convert_field(field_info: &StructFieldInfo) -> syn::Result<Field> {
     let field_type = if let Some(type_attr) = &field_info.type_attr {
         match &type_attr.inner {
             parse::FieldTypeAttr::UInt(uint) => FieldType::UInt(uint.clone()),
+            parse::FieldTypeAttr::SInt(sint) => FieldType::SInt(sint.clone()),
             parse::FieldTypeAttr::Bool => FieldType::Bool,
             parse::FieldTypeAttr::Trait => FieldType::Trait(TraitField {
                 trait_width_bytes,
@@ -159,6 +200,7 @@ fn convert_field(field_info: &StructFieldInfo) -> syn::Result<Field> {
     } else {
         match &field_info.field_type.inner {
             FieldTypeInfo::UInt(uint) => FieldType::UInt(uint.clone()),
+            FieldTypeInfo::SInt(sint) => FieldType::SInt(sint.clone()),
             FieldTypeInfo::Bool => FieldType::Bool,
             FieldTypeInfo::Trait => FieldType::Trait(TraitField {
                 trait_width_bytes,
@@ -174,7 +216,7 @@ fn convert_field(field_info: &StructFieldInfo) -> syn::Result<Field> {
     };
 
     // Error if specified bits is incompatible with field type:
-    let bitwidth = field_info.bits_attr.bitwidth();
+    let bitwidth = field_info.bits_attr.positioned_bitwidth();
     match &field_type {
         FieldType::Bool => {
             if bitwidth > 1 {
@@ -192,14 +234,29 @@ fn convert_field(field_info: &StructFieldInfo) -> syn::Result<Field> {
                 );
             }
         }
+        FieldType::SInt(i) => {
+            if bitwidth > i.width_bytes() * 8 {
+                return spanned_err!(
+                    field_type_tokens,
+                    "Reginald: A {i} field can only be {} bits wide, but this field is {bitwidth} bits wide.",
+                    (i.width_bytes() * 8)
+                );
+            }
+            if field_info.bits_attr.to_bit_ranges().len() != 1 {
+                return spanned_err!(
+                    field_type_tokens,
+                    "Reginald: A signed field must occupy a contiguous range of bits.",
+                );
+            }
+        }
         FieldType::Trait(_) => (),
     }
 
     // Error if trait width is specified but field is not a trait field:
-    if let Some(width_attr) = &field_info.trait_width_bytes_attr {
-        if !matches!(field_type, FieldType::Trait(_)) {
-            return spanned_err!(width_attr, "Reginald: Trait width specified but field is a primitive.");
-        }
+    if let Some(width_attr) = &field_info.trait_width_bytes_attr
+        && !matches!(field_type, FieldType::Trait(_))
+    {
+        return spanned_err!(width_attr, "Reginald: Trait width specified but field is a primitive.");
     }
 
     Ok(Field {
@@ -214,6 +271,7 @@ impl FieldType {
     pub fn trait_width_bytes(&self) -> usize {
         match self {
             FieldType::UInt(u) => u.width_bytes(),
+            FieldType::SInt(i) => i.width_bytes(),
             FieldType::Trait(t) => t.trait_width_bytes,
             FieldType::Bool => 1,
         }
@@ -245,6 +303,31 @@ impl Display for UInt {
     }
 }
 
+impl SInt {
+    pub fn width_bytes(&self) -> usize {
+        match self {
+            SInt::I8 => 1,
+            SInt::I16 => 2,
+            SInt::I32 => 4,
+            SInt::I64 => 8,
+            SInt::I128 => 16,
+        }
+    }
+}
+
+impl Display for SInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SInt::I8 => "i8",
+            SInt::I16 => "i16",
+            SInt::I32 => "i32",
+            SInt::I64 => "i64",
+            SInt::I128 => "i128",
+        };
+        f.write_str(s)
+    }
+}
+
 // ==== Enum ===================================================================
 
 #[derive(Debug)]
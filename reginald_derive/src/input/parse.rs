@@ -1,12 +1,12 @@
 use std::ops::RangeInclusive;
 
 use proc_macro2::Ident;
-use reginald_utils::Bits;
+use reginald_utils::{Bits, Endianess};
 use syn::{DeriveInput, Expr, Field, Lit, Meta, Token, Variant, punctuated::Punctuated};
 
 use crate::utils::{WithTokens, attach_spanned_error, spanned_err, spanned_error};
 
-use super::{FixedBits, UInt, check_for_bits_overlap};
+use super::{FixedBits, SInt, UInt, check_for_bits_overlap};
 
 // ==== Struct =================================================================
 
@@ -17,12 +17,26 @@ pub struct StructInfo {
     pub width_bytes_attr: Option<WithTokens<usize>>,
     pub fixed_bits_attr: FixedBits,
     pub fixed_bits_attr_orig: Vec<WithTokens<Bits>>,
+    pub address_attr: Option<WithTokens<u128>>,
+    pub address_type_attr: Option<WithTokens<UInt>>,
+    pub reset_attr: Option<WithTokens<ResetAttr>>,
+    pub reset_endian_attr: Option<WithTokens<Endianess>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ResetAttr {
+    Value(u128),
+    Bytes(Vec<u8>),
 }
 
 pub fn parse_struct(inp: &DeriveInput) -> syn::Result<StructInfo> {
     // ==== Attributes ====
     let mut width_bytes_attr: Option<WithTokens<usize>> = None;
     let mut fixed_bits_attrs: Vec<WithTokens<FixedBits>> = vec![];
+    let mut address_attr: Option<WithTokens<u128>> = None;
+    let mut address_type_attr: Option<WithTokens<UInt>> = None;
+    let mut reset_attr: Option<WithTokens<ResetAttr>> = None;
+    let mut reset_endian_attr: Option<WithTokens<Endianess>> = None;
 
     for attr in inp.attrs.iter().filter(|x| x.path().is_ident("reginald")) {
         let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
@@ -39,6 +53,31 @@ pub fn parse_struct(inp: &DeriveInput) -> syn::Result<StructInfo> {
                 let name_value = meta.require_name_value()?;
                 let fixed_bits_attr = parse_fixed_bit_tuple(&name_value.value)?;
                 fixed_bits_attrs.push(WithTokens::new(Box::new(name_value.clone()), fixed_bits_attr));
+            } else if meta.path().is_ident("address") {
+                // #[reginald(address = 0x12)]
+                let name_value = meta.require_name_value()?;
+                let new = WithTokens::new(Box::new(name_value.clone()), parse_u128(&name_value.value)?);
+                check_for_repeated_attr(&address_attr, &new)?;
+                address_attr = Some(new);
+            } else if meta.path().is_ident("address_type") {
+                // #[reginald(address_type = u16)]
+                let name_value = meta.require_name_value()?;
+                let new = WithTokens::new(Box::new(name_value.clone()), parse_uint_type(&name_value.value)?);
+                check_for_repeated_attr(&address_type_attr, &new)?;
+                address_type_attr = Some(new);
+            } else if meta.path().is_ident("reset") {
+                // #[reginald(reset = 0x40)]
+                // #[reginald(reset = [0x40, 0x00])]
+                let name_value = meta.require_name_value()?;
+                let new = WithTokens::new(Box::new(name_value.clone()), parse_reset(&name_value.value)?);
+                check_for_repeated_attr(&reset_attr, &new)?;
+                reset_attr = Some(new);
+            } else if meta.path().is_ident("reset_endian") {
+                // #[reginald(reset_endian = "le")]
+                let name_value = meta.require_name_value()?;
+                let new = WithTokens::new(Box::new(name_value.clone()), parse_endian(&name_value.value)?);
+                check_for_repeated_attr(&reset_endian_attr, &new)?;
+                reset_endian_attr = Some(new);
             } else {
                 return spanned_err!(&meta.path(), "Reginald: Unknown struct attribute.");
             };
@@ -65,6 +104,10 @@ pub fn parse_struct(inp: &DeriveInput) -> syn::Result<StructInfo> {
         width_bytes_attr,
         fixed_bits_attr: fixed_bits,
         fixed_bits_attr_orig: fixed_bits_orig,
+        address_attr,
+        address_type_attr,
+        reset_attr,
+        reset_endian_attr,
     })
 }
 
@@ -84,6 +127,7 @@ pub struct StructFieldInfo {
 #[derive(Debug)]
 pub enum FieldTypeAttr {
     UInt(UInt),
+    SInt(SInt),
     Bool,
     Trait,
     TraitMasked,
@@ -92,6 +136,7 @@ pub enum FieldTypeAttr {
 #[derive(Debug)]
 pub enum FieldTypeInfo {
     UInt(UInt),
+    SInt(SInt),
     Trait,
     Bool,
 }
@@ -157,6 +202,16 @@ pub fn parse_struct_field(inp: &Field) -> syn::Result<StructFieldInfo> {
         FieldTypeInfo::UInt(UInt::U64)
     } else if ty.path.is_ident("u128") {
         FieldTypeInfo::UInt(UInt::U128)
+    } else if ty.path.is_ident("i8") {
+        FieldTypeInfo::SInt(SInt::I8)
+    } else if ty.path.is_ident("i16") {
+        FieldTypeInfo::SInt(SInt::I16)
+    } else if ty.path.is_ident("i32") {
+        FieldTypeInfo::SInt(SInt::I32)
+    } else if ty.path.is_ident("i64") {
+        FieldTypeInfo::SInt(SInt::I64)
+    } else if ty.path.is_ident("i128") {
+        FieldTypeInfo::SInt(SInt::I128)
     } else if ty.path.is_ident("bool") {
         FieldTypeInfo::Bool
     } else {
@@ -298,6 +353,59 @@ fn parse_u128(inp: &Expr) -> syn::Result<u128> {
     lit.base10_parse()
 }
 
+fn parse_uint_type(inp: &Expr) -> syn::Result<UInt> {
+    let Expr::Path(path) = inp else {
+        return spanned_err!(inp, "Reginald: Expected unsigned integer type.");
+    };
+
+    if path.path.is_ident("u8") {
+        Ok(UInt::U8)
+    } else if path.path.is_ident("u16") {
+        Ok(UInt::U16)
+    } else if path.path.is_ident("u32") {
+        Ok(UInt::U32)
+    } else if path.path.is_ident("u64") {
+        Ok(UInt::U64)
+    } else if path.path.is_ident("u128") {
+        Ok(UInt::U128)
+    } else {
+        spanned_err!(inp, "Reginald: Expected unsigned integer type.")
+    }
+}
+
+fn parse_reset(inp: &Expr) -> syn::Result<ResetAttr> {
+    match inp {
+        Expr::Lit(_) => Ok(ResetAttr::Value(parse_u128(inp)?)),
+        Expr::Array(array) => {
+            let mut bytes = vec![];
+            for elem in &array.elems {
+                let Ok(byte) = u8::try_from(parse_u128(elem)?) else {
+                    return spanned_err!(elem, "Reginald: Byte value larger than 0xFF.");
+                };
+                bytes.push(byte);
+            }
+            Ok(ResetAttr::Bytes(bytes))
+        }
+        _ => spanned_err!(inp, "Reginald: Expected integer literal or byte array."),
+    }
+}
+
+fn parse_endian(inp: &Expr) -> syn::Result<Endianess> {
+    let Expr::Lit(lit) = inp else {
+        return spanned_err!(inp, "Reginald: Expected \"le\" or \"be\".");
+    };
+
+    let Lit::Str(lit) = &lit.lit else {
+        return spanned_err!(inp, "Reginald: Expected \"le\" or \"be\".");
+    };
+
+    match lit.value().as_str() {
+        "le" => Ok(Endianess::Little),
+        "be" => Ok(Endianess::Big),
+        _ => spanned_err!(inp, "Reginald: Expected \"le\" or \"be\"."),
+    }
+}
+
 fn parse_range(inp: &Expr) -> syn::Result<RangeInclusive<usize>> {
     let Expr::Range(range) = inp else {
         return spanned_err!(inp, "Reginald: Expected range.");
@@ -429,6 +537,16 @@ fn parse_type_attr(inp: &Expr) -> syn::Result<FieldTypeAttr> {
         Ok(FieldTypeAttr::UInt(UInt::U64))
     } else if path.path.is_ident("u128") {
         Ok(FieldTypeAttr::UInt(UInt::U128))
+    } else if path.path.is_ident("i8") {
+        Ok(FieldTypeAttr::SInt(SInt::I8))
+    } else if path.path.is_ident("i16") {
+        Ok(FieldTypeAttr::SInt(SInt::I16))
+    } else if path.path.is_ident("i32") {
+        Ok(FieldTypeAttr::SInt(SInt::I32))
+    } else if path.path.is_ident("i64") {
+        Ok(FieldTypeAttr::SInt(SInt::I64))
+    } else if path.path.is_ident("i128") {
+        Ok(FieldTypeAttr::SInt(SInt::I128))
     } else if path.path.is_ident("Bool") {
         Ok(FieldTypeAttr::Bool)
     } else if path.path.is_ident("trait") {
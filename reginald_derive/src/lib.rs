@@ -1,11 +1,13 @@
 mod utils;
 
 mod derive;
+#[cfg(feature = "include_map")]
+mod include_map;
 mod input;
 
 use derive::{
     derive_enum_from_bytes, derive_enum_to_bytes, derive_enum_try_from_bytes, derive_enum_wrapping_from_bytes,
-    derive_struct_from_bytes, derive_struct_to_bytes, derive_struct_try_from_bytes,
+    derive_struct_from_bytes, derive_struct_register, derive_struct_to_bytes, derive_struct_try_from_bytes,
 };
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
@@ -65,3 +67,100 @@ pub fn derive_wrapping_from_bytes(inp: TokenStream) -> TokenStream {
         Err(e) => e.into_compile_error().into(),
     }
 }
+
+/// Derive `Register` for a struct, from its `address` and optional `reset` attributes.
+///
+/// ```
+/// use reginald::Register;
+///
+/// #[derive(Register)]
+/// #[reginald(address = 0x12, reset = 0x4003, fixed_bits = (14, 1))]
+/// struct Reg {
+///     #[reginald(bits = 0)]
+///     field0: bool,
+///     #[reginald(bits = [1..=3])]
+///     field1: u8,
+/// }
+/// ```
+///
+/// The reset value may not set bits that are not covered by a field or the fixed bits:
+///
+/// ```compile_fail
+/// # use reginald::Register;
+/// #[derive(Register)]
+/// #[reginald(address = 0x12, reset = 0x4013, fixed_bits = (14, 1))]
+/// struct Reg {
+///     #[reginald(bits = 0)]
+///     field0: bool,
+///     #[reginald(bits = [1..=3])]
+///     field1: u8,
+/// }
+/// ```
+///
+/// The reset value has to match the fixed bits:
+///
+/// ```compile_fail
+/// # use reginald::Register;
+/// #[derive(Register)]
+/// #[reginald(address = 0x12, reset = 0x0003, fixed_bits = (14, 1))]
+/// struct Reg {
+///     #[reginald(bits = 0)]
+///     field0: bool,
+///     #[reginald(bits = [1..=3])]
+///     field1: u8,
+/// }
+/// ```
+///
+/// The reset value is only checked bit-wise. The valid values of fields with a trait type (such as
+/// an enum) are not known to the derive, so a reset value that puts an invalid value into such a
+/// field is not rejected at compile time. It is only caught when decoding the reset value, for
+/// example with `TryFromBytes`.
+///
+/// The address has to fit into the address type:
+///
+/// ```compile_fail
+/// # use reginald::Register;
+/// #[derive(Register)]
+/// #[reginald(address = 0x100, address_type = u8, reset = 0x4003, fixed_bits = (14, 1))]
+/// struct Reg {
+///     #[reginald(bits = 0)]
+///     field0: bool,
+///     #[reginald(bits = [1..=3])]
+///     field1: u8,
+/// }
+/// ```
+#[proc_macro_derive(Register, attributes(reginald))]
+pub fn derive_register(inp: TokenStream) -> TokenStream {
+    let inp = parse_macro_input!(inp as input::ReginaldDeriveInput);
+    let result = match inp {
+        input::ReginaldDeriveInput::Struct(struct_data) => derive_struct_register(&struct_data),
+        input::ReginaldDeriveInput::Enum(enum_data) => {
+            spanned_err!(enum_data.name, "Reginald: Register cannot be derived for an enum.")
+        }
+    };
+    match result {
+        Ok(s) => s.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// Generate register definitions from a listing at compile time.
+///
+/// Expands to the items generated by reginald's `rs-structs` generator. The
+/// listing path is relative to the crate root. Generator options may be given
+/// after the path:
+///
+/// ```ignore
+/// mod regs {
+///     reginald::include_map!("regs/chip.yaml", external_traits = "reginald::", struct_derive = ["Debug"]);
+/// }
+/// ```
+#[cfg(feature = "include_map")]
+#[proc_macro]
+pub fn include_map(inp: TokenStream) -> TokenStream {
+    let inp = parse_macro_input!(inp as include_map::IncludeMapInput);
+    match include_map::include_map(&inp) {
+        Ok(s) => s.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
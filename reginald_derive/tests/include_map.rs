@@ -0,0 +1,31 @@
+mod regs {
+    reginald::include_map!(
+        "tests/resources/include_map.yaml",
+        external_traits = "reginald::",
+        struct_derive = ["Debug", "PartialEq"],
+        enum_derive = ["Debug", "PartialEq"]
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::regs::*;
+    use reginald::{Register, ToBytes, TryFromBytes};
+
+    #[test]
+    fn roundtrip() {
+        let reg = Ctrl {
+            en: 1,
+            mode: Mode::Fast,
+        };
+        assert_eq!(reg.to_le_bytes(), [0x05]);
+        assert_eq!(Ctrl::try_from_le_bytes(&[0x05]), Ok(reg));
+    }
+
+    #[test]
+    fn register() {
+        assert_eq!(Ctrl::ADDRESS, 0x10);
+        assert_eq!(Ctrl::reset_val_le(), Some([0x05]));
+        assert!(Ctrl::try_from_le_bytes(&[0x06]).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use reginald::{FromBytes, Register, ToBytes};
+
+#[derive(FromBytes, ToBytes, Register, Debug, PartialEq)]
+#[reginald(address = 0x12, reset = 0x4003, fixed_bits = (14, 1))]
+struct Reg {
+    #[reginald(bits = 0)]
+    field0: bool,
+
+    #[reginald(bits = [1..=3])]
+    field1: u8,
+}
+
+#[derive(FromBytes, ToBytes, Register)]
+#[reginald(address = 0x1234, address_type = u32, reset = [0x01, 0x80], reset_endian = "be")]
+struct RegBigEndian {
+    #[reginald(bits = [0..=15])]
+    field: u16,
+}
+
+#[derive(FromBytes, ToBytes, Register)]
+#[reginald(address = 0x100)]
+struct RegNoReset {
+    #[reginald(bits = [0..=7])]
+    field: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reginald::{FromBytes, Register, ResetVal, ToBytes};
+
+    #[test]
+    fn address() {
+        assert_eq!(<Reg as Register<2, u8>>::ADDRESS, 0x12_u8);
+        assert_eq!(<RegBigEndian as Register<2, u32>>::ADDRESS, 0x1234_u32);
+        assert_eq!(<RegNoReset as Register<1, u16>>::ADDRESS, 0x100_u16);
+    }
+
+    #[test]
+    fn reset_val() {
+        assert!(matches!(<Reg as Register<2, u8>>::RESET_VAL, Some(ResetVal::LittleEndian([0x03, 0x40]))));
+        assert_eq!(<Reg as Register<2, u8>>::reset_val_be(), Some([0x40, 0x03]));
+
+        assert!(matches!(<RegBigEndian as Register<2, u32>>::RESET_VAL, Some(ResetVal::BigEndian([0x01, 0x80]))));
+        assert_eq!(<RegBigEndian as Register<2, u32>>::reset_val_le(), Some([0x80, 0x01]));
+
+        assert!(<RegNoReset as Register<1, u16>>::RESET_VAL.is_none());
+    }
+
+    #[test]
+    fn reset_val_roundtrip() {
+        let reset = <Reg as Register<2, u8>>::reset_val_le().unwrap();
+        let reg = Reg::from_le_bytes(&reset);
+        assert_eq!(
+            reg,
+            Reg {
+                field0: true,
+                field1: 1
+            }
+        );
+        assert_eq!(reg.to_le_bytes(), reset);
+    }
+}
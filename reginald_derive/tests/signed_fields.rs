@@ -0,0 +1,70 @@
+use reginald::{FromBytes, ToBytes, TryFromBytes};
+
+#[derive(FromBytes, ToBytes, Debug, PartialEq)]
+#[reginald(width_bytes = 3)]
+struct Reg {
+    #[reginald(bits = [1..=4])]
+    field0: i8,
+
+    #[reginald(bits = [5..=16])]
+    field1: i16,
+
+    #[reginald(bits = [17..=19], is = i32)]
+    field2: i32,
+}
+
+#[derive(TryFromBytes, Debug, PartialEq)]
+struct TryReg {
+    #[reginald(bits = [0..=7])]
+    field0: i8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reginald::{FromBytes, ToBytes, TryFromBytes};
+
+    #[test]
+    fn roundtrip() {
+        let reg = Reg {
+            field0: -3,
+            field1: 0x7FF,
+            field2: -4,
+        };
+
+        let packed: [u8; 3] = [
+            (0b1101 << 1) // Field 0
+            | (0xFF << 5), // Field 1
+            0xFF,       // Field 1
+            0b100 << 1, // Field 2
+        ];
+
+        assert_eq!(reg.to_le_bytes(), packed);
+        assert_eq!(Reg::from_le_bytes(&packed), reg);
+    }
+
+    #[test]
+    fn sign_extension() {
+        let reg = Reg::from_le_bytes(&[0xFF, 0xFF, 0xFF]);
+        assert_eq!(
+            reg,
+            Reg {
+                field0: -1,
+                field1: -1,
+                field2: -1,
+            }
+        );
+
+        let reg = Reg::from_le_bytes(&[0b1 << 4, 0x00, (0x1 << 3) | 0x1]);
+        assert_eq!(
+            reg,
+            Reg {
+                field0: -8,
+                field1: -2048,
+                field2: -4,
+            }
+        );
+
+        assert_eq!(TryReg::try_from_le_bytes(&[0x80]), Ok(TryReg { field0: -128 }));
+    }
+}
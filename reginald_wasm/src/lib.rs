@@ -88,6 +88,7 @@ mod c_funcpack {
         pub funcs_as_prototypes: bool,
         pub clang_format_guard: bool,
         pub include_guards: bool,
+        pub mmio_access: bool,
         pub gen_enums: bool,
         pub gen_enum_validation: bool,
         pub gen_structs: bool,
@@ -157,6 +158,7 @@ mod c_funcpack {
             funcs_as_prototypes: wasm_opts.funcs_as_prototypes,
             clang_format_guard: wasm_opts.clang_format_guard,
             include_guards: wasm_opts.include_guards,
+            mmio_access: wasm_opts.mmio_access,
             to_generate,
         };
 
@@ -55,6 +55,7 @@ const run_c_funcpack = (
   opts.funcs_as_prototypes = c_funcpack_config.FuncsAsPrototypes;
   opts.clang_format_guard = c_funcpack_config.ClangFormatGuard;
   opts.include_guards = c_funcpack_config.IncludeGuards;
+  opts.mmio_access = c_funcpack_config.MmioAccess;
   opts.gen_enums = c_funcpack_config.GenerateEnums;
   opts.gen_enum_validation = c_funcpack_config.GenerateEnumValidationMacros;
   opts.gen_structs = c_funcpack_config.GenerateStructs;
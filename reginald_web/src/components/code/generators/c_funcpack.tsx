@@ -94,6 +94,16 @@ export function GeneratorSettingsCFunpack({ config }: { config: Config }) {
         description="May be disabled if splitting code into header and source."
       />
 
+      <ConfigCheckbox
+        id="c_funcpack_mmio_access"
+        checked={config.MmioAccess}
+        onCheckedChange={(checked: boolean) =>
+          config.updateProperty("MmioAccess", checked)
+        }
+        label="Generate memory-mapped register access."
+        description="Volatile struct overlays for register blocks, and read/write functions relative to a base pointer."
+      />
+
       <ConfigStringListManager
         id="c_funcpack_includes"
         content={config.Includes}
@@ -11,6 +11,7 @@ export type ConfigData = {
   FuncsAsPrototypes: boolean;
   ClangFormatGuard: boolean;
   IncludeGuards: boolean;
+  MmioAccess: boolean;
   GenerateEnums: boolean;
   GenerateEnumValidationMacros: boolean;
   GenerateStructs: boolean;
@@ -29,6 +30,7 @@ const defaultConfig: ConfigData = {
   FuncsAsPrototypes: false,
   ClangFormatGuard: true,
   IncludeGuards: true,
+  MmioAccess: false,
   GenerateEnums: true,
   GenerateEnumValidationMacros: true,
   GenerateStructs: true,